@@ -3,3 +3,34 @@ pub const DEFAULT_LP_BPS: u16 = 7_000;
 pub const DEFAULT_INSURANCE_BPS: u16 = 2_000;
 pub const DEFAULT_PROTOCOL_BPS: u16 = 1_000;
 pub const DEFAULT_EXECUTION_REBATE_USDC: u64 = 1_000; // 0.001 USDC if mint is 6 decimals.
+pub const FEE_WINDOW_SECS: i64 = 86_400;
+// Deposits and withdrawals are refused once the engine's NAV mark is older.
+pub const MAX_NAV_AGE_SECS: i64 = 120;
+// Minted to nobody on the first deposit so total shares never return to zero
+// and the share price cannot be reset or inflated from an empty pool.
+pub const LOCKED_INITIAL_SHARES: u128 = 1_000;
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+pub const MAX_MANAGEMENT_FEE_BPS: u16 = 500;
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 3_000;
+pub const MAX_INSTANT_WITHDRAW_FEE_BPS: u16 = 1_000;
+// Cap on the share of a referred LP's fee income paid to the referrer.
+pub const MAX_LP_REFERRAL_FEE_BPS: u16 = 1_000;
+// Fixed-point scale for the share price high-water mark.
+pub const SHARE_PRICE_SCALE: u128 = 1_000_000_000_000;
+// Daily share price samples kept by `PoolMetrics` (a 30-day window).
+pub const POOL_METRICS_SAMPLES: usize = 30;
+pub const POOL_METRICS_INTERVAL_SECS: i64 = 86_400;
+// Minimum spacing of `FeeSnapshot`s.
+pub const FEE_SNAPSHOT_INTERVAL_SECS: i64 = 86_400;
+// Notice LPs get before a `configure_pool` proposal can take effect.
+pub const DEFAULT_CONFIG_DELAY_SECS: i64 = 86_400;
+pub const MAX_CONFIG_DELAY_SECS: i64 = 30 * 86_400;
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+// Break-glass vault withdrawals wait this long, leaving LPs time to exit.
+pub const EMERGENCY_WITHDRAW_DELAY_SECS: i64 = 7 * 86_400;
+// Account layout versions. New fields take bytes from the reserved tail or are
+// appended after it, and `migrate_*` brings older accounts up to date.
+pub const POOL_VERSION: u8 = 3;
+pub const LP_POSITION_VERSION: u8 = 3;
+pub const POOL_RESERVED_BYTES: usize = 4;
+pub const LP_POSITION_RESERVED_BYTES: usize = 32;
@@ -44,4 +44,60 @@ pub enum ErrorCode {
     InsuranceShortfall,
     #[msg("Invalid pool state")]
     InvalidPoolState,
+    #[msg("Liquidity vault does not match the pool")]
+    LiquidityVaultMismatch,
+    #[msg("Insurance vault does not match the pool")]
+    InsuranceVaultMismatch,
+    #[msg("Protocol fee vault does not match the pool")]
+    ProtocolFeeVaultMismatch,
+    #[msg("Maker rebate exceeds the LP share of collected fees")]
+    MakerRebateExceedsLpFees,
+    #[msg("Treasury account is not set or does not match the pool")]
+    InvalidTreasury,
+    #[msg("Deposit would exceed the pool deposit cap")]
+    PoolDepositCapExceeded,
+    #[msg("Deposit would exceed the per-position deposit cap")]
+    PositionDepositCapExceeded,
+    #[msg("Pool deposits are paused")]
+    DepositsPaused,
+    #[msg("Pool withdrawals are paused")]
+    WithdrawalsPaused,
+    #[msg("Instant withdrawals are disabled for this pool")]
+    InstantWithdrawDisabled,
+    #[msg("Insufficient liquidity vault balance")]
+    InsufficientLiquidityVault,
+    #[msg("Pool metrics were sampled too recently")]
+    MetricsSampleTooEarly,
+    #[msg("Rebalance would take insurance below its open interest floor")]
+    InsuranceBelowTarget,
+    #[msg("Rebalance would take insurance above its open interest ceiling")]
+    InsuranceAboveTarget,
+    #[msg("Pool config change is still timelocked")]
+    ConfigTimelockActive,
+    #[msg("Slippage limit exceeded")]
+    SlippageExceeded,
+    #[msg("Withdraw request is waiting in the withdrawal queue")]
+    WithdrawQueued,
+    #[msg("Withdraw request is not at the head of the queue")]
+    NotQueueHead,
+    #[msg("Depositor is not on the pool allowlist")]
+    NotAllowlisted,
+    #[msg("Emergency withdrawal is still timelocked")]
+    EmergencyTimelockActive,
+    #[msg("Vault does not match the emergency withdrawal")]
+    EmergencyVaultMismatch,
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    #[msg("Mint does not match the pool collateral mint")]
+    UsdcMintMismatch,
+    #[msg("Liquidity vault holds no surplus to sweep")]
+    NoSurplus,
+    #[msg("Fee recipients are invalid or do not match the distribution")]
+    InvalidFeeRecipients,
+    #[msg("Fee snapshot was taken too recently")]
+    SnapshotTooEarly,
+    #[msg("Referrer is not valid for this position")]
+    InvalidReferrer,
+    #[msg("LP NAV mark is stale")]
+    StaleNav,
 }
@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolVault;
+
+#[event]
+pub struct LpFeesAccrued {
+    pub pool: Pubkey,
+    pub market_id: u64,
+    pub fee: u64,
+    pub lp_fee: u64,
+    pub cumulative_lp_fees: u64,
+    pub accounted_liquidity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMetricsRecorded {
+    pub pool: Pubkey,
+    pub share_price: u128,
+    pub trailing_apy_bps: i64,
+    pub max_drawdown_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub pool: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub reserved_for_rebates: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceRebalanced {
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub to_liquidity: bool,
+    pub insurance_balance: u64,
+    pub accounted_liquidity: u64,
+    pub open_interest: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpDeposited {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub minted_shares: u128,
+    pub share_price: u128,
+    pub total_shares: u128,
+    pub accounted_liquidity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpWithdrawRequested {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub share_amount: u128,
+    pub share_price: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpWithdrawQueued {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub share_amount: u128,
+    pub queue_position: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpWithdrawClaimed {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub share_amount: u128,
+    pub amount: u64,
+    // Shares left on the request after a partial claim.
+    pub remaining_shares: u128,
+    pub share_price: u128,
+    pub accounted_liquidity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeFillApplied {
+    pub pool: Pubkey,
+    pub market_id: u64,
+    pub user: Pubkey,
+    pub order_id: u64,
+    pub keeper: Pubkey,
+    pub notional: u64,
+    pub fee: u64,
+    pub lp_fee: u64,
+    pub insurance_fee: u64,
+    pub protocol_fee: u64,
+    pub pnl_delta: i64,
+    pub keeper_credit: u64,
+    pub share_price: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidationApplied {
+    pub pool: Pubkey,
+    pub market_id: u64,
+    pub user: Pubkey,
+    pub penalty: u64,
+    pub insurance_portion: u64,
+    pub bad_debt: u64,
+    pub staker_loss: u64,
+    pub pnl_delta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperRebateClaimed {
+    pub pool: Pubkey,
+    pub keeper: Pubkey,
+    pub execution_rebate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawalProposed {
+    pub pool: Pubkey,
+    pub vault: PoolVault,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawalCancelled {
+    pub pool: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawalExecuted {
+    pub pool: Pubkey,
+    pub vault: PoolVault,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SurplusSwept {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub amount: u64,
+    pub accounted_liquidity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExecutionRebateCredited {
+    pub pool: Pubkey,
+    pub keeper: Pubkey,
+    pub market_id: u64,
+    pub amount: u64,
+    // Owed but not credited because the protocol fee vault was short.
+    pub unfunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidationRewardPaid {
+    pub pool: Pubkey,
+    pub keeper: Pubkey,
+    pub market_id: u64,
+    // Paid straight to the keeper's token account by the engine.
+    pub amount: u64,
+    // Keeper portion above `max_liquidation_reward`, sent to protocol fees.
+    pub capped: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesDistributed {
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub recipient_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSnapshotRecorded {
+    pub pool: Pubkey,
+    pub index: u64,
+    pub total_shares: u128,
+    pub cumulative_lp_fees: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub pool: Pubkey,
+    // Trader PnL gained against the pool within the current window.
+    pub trader_pnl_gain: i128,
+    pub window_nav: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpReferralRewardClaimed {
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub lp_position: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSplitSyncQueued {
+    pub pool: Pubkey,
+    pub lp_fee_bps: u16,
+    pub insurance_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct FeeSplitSynced {
+    pub pool: Pubkey,
+    pub lp_fee_bps: u16,
+    pub insurance_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub timestamp: i64,
+}
@@ -1,4 +1,4 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 
 use crate::{constants::BPS_DENOM, error::ErrorCode, state::Pool};
 
@@ -24,3 +24,49 @@ pub fn mul_bps(value: u64, bps: u64) -> Result<u64> {
     .ok_or_else(|| error!(ErrorCode::MathOverflow))
     .map(|v| v as u64)
 }
+
+// The share of `min_amount_out` owed on a claim paying `paid_shares` of
+// `requested_shares`, rounded up so a partial payout is held to at least the
+// same price as the full request.
+pub fn pro_rata_min_amount_out(
+    min_amount_out: u64,
+    paid_shares: u128,
+    requested_shares: u128,
+) -> Result<u64> {
+    require!(requested_shares > 0, ErrorCode::InvalidAmount);
+    let scaled = (min_amount_out as u128)
+        .checked_mul(paid_shares)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .div_ceil(requested_shares);
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+// Resizes an account up to `new_len`, topping up rent from `payer`. Used by
+// layout migrations; new bytes are zeroed by the runtime.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
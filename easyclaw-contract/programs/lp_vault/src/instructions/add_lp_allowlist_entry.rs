@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{LpAllowlistEntry, Pool},
+};
+
+pub fn handler(ctx: Context<AddLpAllowlistEntry>, user: Pubkey) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.pool = ctx.accounts.pool.key();
+    entry.user = user;
+    entry.bump = ctx.bumps.allowlist_entry;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddLpAllowlistEntry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"lp-allow", pool.key().as_ref(), user.as_ref()],
+        bump,
+        space = 8 + LpAllowlistEntry::INIT_SPACE,
+    )]
+    pub allowlist_entry: Account<'info, LpAllowlistEntry>,
+    pub system_program: Program<'info, System>,
+}
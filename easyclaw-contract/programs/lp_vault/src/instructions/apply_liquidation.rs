@@ -1,76 +1,114 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
     error::ErrorCode,
+    events::{LiquidationApplied, LiquidationRewardPaid},
     helpers::{assert_engine_authority, mul_bps},
-    state::{KeeperRebate, Pool},
+    state::{MarketInsurance, MarketVaultStats, Pool},
 };
 
 pub fn handler(
     ctx: Context<ApplyLiquidation>,
-    _market_id: u64,
-    _user: Pubkey,
+    market_id: u64,
+    user: Pubkey,
     penalty: u64,
     bad_debt: u64,
+    pnl_delta: i64,
 ) -> Result<()> {
     assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
 
-    let pool = &mut ctx.accounts.pool;
-    let keeper_portion = mul_bps(penalty, 1_000)?; // 10%
+    // The keeper's 10% is paid straight to the keeper by the engine, up to
+    // `max_liquidation_reward`; the rest goes to the protocol fee vault.
+    let keeper_portion = mul_bps(penalty, 1_000)?;
     let insurance_portion = penalty
         .checked_sub(keeper_portion)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let rebate = &mut ctx.accounts.keeper_rebate;
-    require_keys_eq!(rebate.pool, pool.key(), ErrorCode::InvalidKeeperRebate);
-    require_keys_eq!(
-        rebate.keeper,
-        ctx.accounts.keeper.key(),
-        ErrorCode::InvalidKeeperRebate
-    );
-
-    rebate.amount = rebate
-        .amount
-        .checked_add(keeper_portion)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    pool.pending_keeper_rebates = pool
-        .pending_keeper_rebates
-        .checked_add(keeper_portion)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
     require!(
         insurance_portion <= ctx.accounts.insurance_vault.amount,
         ErrorCode::InsufficientInsuranceVault
     );
 
+    let pool = &mut ctx.accounts.pool;
+    let market_insurance = &mut ctx.accounts.market_insurance;
+    market_insurance.contribute(insurance_portion)?;
+    pool.credit_insurance_stakers(insurance_portion)?;
+    let mut staker_loss = 0;
     if bad_debt > 0 {
         require!(
             bad_debt <= ctx.accounts.insurance_vault.amount,
             ErrorCode::InsuranceShortfall
         );
+        // Stakers take first loss; the rest falls on the market earmark and
+        // then the shared pot.
+        staker_loss = pool.charge_insurance_stakers(bad_debt)?;
+        market_insurance.absorb_bad_debt(bad_debt - staker_loss)?;
+        pool.cumulative_bad_debt_covered =
+            pool.cumulative_bad_debt_covered.saturating_add(bad_debt);
     }
 
+    let keeper_reward = pool.liquidation_reward(keeper_portion);
+
+    // Liquidations close at the oracle price, so their PnL counts toward the
+    // same realized trader PnL as ordinary fills.
+    pool.cumulative_trader_pnl = pool
+        .cumulative_trader_pnl
+        .checked_add(pnl_delta as i128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts
+        .market_stats
+        .record_liquidation(penalty, bad_debt, pnl_delta)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    emit!(LiquidationRewardPaid {
+        pool: pool.key(),
+        keeper: ctx.accounts.keeper.key(),
+        market_id,
+        amount: keeper_reward,
+        capped: keeper_portion - keeper_reward,
+        timestamp: now,
+    });
+
+    emit!(LiquidationApplied {
+        pool: pool.key(),
+        market_id,
+        user,
+        penalty,
+        insurance_portion,
+        bad_debt,
+        staker_loss,
+        pnl_delta,
+        timestamp: now,
+    });
+
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(market_id: u64)]
 pub struct ApplyLiquidation<'info> {
     pub engine_authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
-    /// CHECK: used for keeper rebate identity.
+    /// CHECK: the liquidating keeper, recorded in `LiquidationRewardPaid`.
     pub keeper: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds = [b"keeper-rebate", pool.key().as_ref(), keeper.key().as_ref()],
-        bump = keeper_rebate.bump,
+        seeds = [b"market-insurance", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump = market_insurance.bump,
+    )]
+    pub market_insurance: Account<'info, MarketInsurance>,
+    #[account(
+        mut,
+        seeds = [b"market-stats", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump = market_stats.bump,
     )]
-    pub keeper_rebate: Account<'info, KeeperRebate>,
-    #[account(address = pool.insurance_vault)]
-    pub insurance_vault: Account<'info, TokenAccount>,
+    pub market_stats: Account<'info, MarketVaultStats>,
+    #[account(address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
 }
@@ -1,41 +1,62 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
     error::ErrorCode,
-    helpers::{assert_engine_authority, mul_bps},
-    state::{KeeperRebate, Pool},
+    events::{
+        CircuitBreakerTripped, ExecutionRebateCredited, KeeperRebateClaimed, LpFeesAccrued,
+        TradeFillApplied,
+    },
+    helpers::assert_engine_authority,
+    state::{KeeperRebate, MarketInsurance, MarketVaultStats, Pool},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<ApplyTradeFill>,
-    _market_id: u64,
-    _user: Pubkey,
-    _order_id: u64,
-    _notional: u64,
+    market_id: u64,
+    user: Pubkey,
+    order_id: u64,
+    notional: u64,
     fee: u64,
     pnl_delta: i64,
+    keeper_tip: u64,
 ) -> Result<()> {
     assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
 
     let pool = &mut ctx.accounts.pool;
-    let lp_fee = mul_bps(fee, pool.lp_fee_bps as u64)?;
-    let insurance_fee = mul_bps(fee, pool.insurance_fee_bps as u64)?;
-    let protocol_fee = fee
-        .checked_sub(lp_fee)
-        .and_then(|x| x.checked_sub(insurance_fee))
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let (lp_fee, insurance_fee, protocol_fee) = pool.split_fee(fee)?;
 
-    pool.total_trading_fees = pool
-        .total_trading_fees
-        .checked_add(fee)
+    let now = Clock::get()?.unix_timestamp;
+    pool.record_trading_fee(now, fee)?;
+    pool.accounted_liquidity = pool
+        .accounted_liquidity
+        .checked_add(lp_fee)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.cumulative_lp_fees = pool
+        .cumulative_lp_fees
+        .checked_add(lp_fee)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.accrue_lp_fees_per_share(lp_fee)?;
     pool.cumulative_trader_pnl = pool
         .cumulative_trader_pnl
         .checked_add(pnl_delta as i128)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    if let Some(trader_pnl_gain) = pool.check_circuit_breaker(now) {
+        emit!(CircuitBreakerTripped {
+            pool: pool.key(),
+            trader_pnl_gain,
+            window_nav: pool.pnl_window_nav,
+            timestamp: now,
+        });
+    }
 
-    // Fee splits are transferred into dedicated vaults by order_engine.
+    // Fee splits are transferred into dedicated vaults by order_engine. The LP
+    // share must already be in the liquidity vault before it is counted.
+    require!(
+        pool.accounted_liquidity <= ctx.accounts.liquidity_vault.amount,
+        ErrorCode::InsufficientLiquidityVault
+    );
     require!(
         protocol_fee <= ctx.accounts.protocol_fee_vault.amount,
         ErrorCode::InsufficientProtocolFeeVault
@@ -44,9 +65,77 @@ pub fn handler(
         insurance_fee <= ctx.accounts.insurance_vault.amount,
         ErrorCode::InsufficientInsuranceVault
     );
+    ctx.accounts.market_insurance.contribute(insurance_fee)?;
+    pool.credit_insurance_stakers(insurance_fee)?;
+    emit!(LpFeesAccrued {
+        pool: pool.key(),
+        market_id,
+        fee,
+        lp_fee,
+        cumulative_lp_fees: pool.cumulative_lp_fees,
+        accounted_liquidity: pool.accounted_liquidity,
+        timestamp: now,
+    });
 
-    if pool.execution_rebate_usdc > 0 {
-        let rebate = &mut ctx.accounts.keeper_rebate;
+    // Order tips were moved into the protocol fee vault by order_engine and are
+    // paid together with the market's execution rebate: straight to the
+    // keeper's token account when one is passed, otherwise accrued for
+    // `claim_keeper_rebate`.
+    let flat_rebate = ctx
+        .accounts
+        .market_insurance
+        .execution_rebate(pool.execution_rebate_usdc);
+    let owed = pool
+        .execution_rebate(fee, flat_rebate)
+        .checked_add(keeper_tip)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    // Only credit what the vault can still pay on top of earlier rebates, so
+    // pending claims never outgrow it.
+    let unreserved = ctx
+        .accounts
+        .protocol_fee_vault
+        .amount
+        .saturating_sub(pool.reserved_protocol_fees());
+    let keeper_credit = owed.min(unreserved);
+    pool.unfunded_keeper_rebates = pool
+        .unfunded_keeper_rebates
+        .checked_add(owed - keeper_credit)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let inline_payout = ctx
+        .accounts
+        .keeper_token_account
+        .as_ref()
+        .filter(|_| keeper_credit > 0);
+    if let Some(keeper_token_account) = inline_payout {
+        let pool_key = pool.key();
+        let bump = ctx.bumps.protocol_fee_auth;
+        let signer_seed_group: &[&[u8]] = &[b"protocol-fee-auth", pool_key.as_ref(), &[bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.protocol_fee_vault.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                    to: keeper_token_account.to_account_info(),
+                    authority: ctx.accounts.protocol_fee_auth.to_account_info(),
+                },
+                &[signer_seed_group],
+            ),
+            keeper_credit,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+        emit!(KeeperRebateClaimed {
+            pool: pool_key,
+            keeper: ctx.accounts.keeper.key(),
+            execution_rebate: keeper_credit,
+            timestamp: now,
+        });
+    } else if keeper_credit > 0 {
+        let rebate = ctx
+            .accounts
+            .keeper_rebate
+            .as_mut()
+            .ok_or_else(|| error!(ErrorCode::InvalidKeeperRebate))?;
         require_keys_eq!(rebate.pool, pool.key(), ErrorCode::InvalidKeeperRebate);
         require_keys_eq!(
             rebate.keeper,
@@ -54,25 +143,61 @@ pub fn handler(
             ErrorCode::InvalidKeeperRebate
         );
 
-        rebate.amount = rebate
-            .amount
-            .checked_add(pool.execution_rebate_usdc)
+        rebate.execution_rebate = rebate
+            .execution_rebate
+            .checked_add(keeper_credit)
             .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
         pool.pending_keeper_rebates = pool
             .pending_keeper_rebates
-            .checked_add(pool.execution_rebate_usdc)
+            .checked_add(keeper_credit)
             .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        emit!(ExecutionRebateCredited {
+            pool: pool.key(),
+            keeper: ctx.accounts.keeper.key(),
+            market_id,
+            amount: keeper_credit,
+            unfunded: owed - keeper_credit,
+            timestamp: now,
+        });
     }
 
+    ctx.accounts.market_stats.record_fill(
+        notional,
+        fee,
+        lp_fee,
+        insurance_fee,
+        protocol_fee,
+        keeper_credit,
+        pnl_delta,
+    )?;
+
+    emit!(TradeFillApplied {
+        pool: pool.key(),
+        market_id,
+        user,
+        order_id,
+        keeper: ctx.accounts.keeper.key(),
+        notional,
+        fee,
+        lp_fee,
+        insurance_fee,
+        protocol_fee,
+        pnl_delta,
+        keeper_credit,
+        share_price: pool.share_price()?,
+        timestamp: now,
+    });
+
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(market_id: u64)]
 pub struct ApplyTradeFill<'info> {
     pub engine_authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
@@ -83,11 +208,36 @@ pub struct ApplyTradeFill<'info> {
         seeds = [b"keeper-rebate", pool.key().as_ref(), keeper.key().as_ref()],
         bump = keeper_rebate.bump,
     )]
-    pub keeper_rebate: Account<'info, KeeperRebate>,
-    #[account(address = pool.liquidity_vault)]
-    pub liquidity_vault: Account<'info, TokenAccount>,
-    #[account(address = pool.insurance_vault)]
-    pub insurance_vault: Account<'info, TokenAccount>,
-    #[account(address = pool.protocol_fee_vault)]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    pub keeper_rebate: Option<Account<'info, KeeperRebate>>,
+    #[account(
+        mut,
+        seeds = [b"market-insurance", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump = market_insurance.bump,
+    )]
+    pub market_insurance: Account<'info, MarketInsurance>,
+    #[account(
+        mut,
+        seeds = [b"market-stats", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump = market_stats.bump,
+    )]
+    pub market_stats: Account<'info, MarketVaultStats>,
+    #[account(address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = pool.protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: protocol fee authority PDA, signs inline keeper rebates.
+    #[account(seeds = [b"protocol-fee-auth", pool.key().as_ref()], bump)]
+    pub protocol_fee_auth: UncheckedAccount<'info>,
+    // Pays the rebate inline instead of accruing it on `keeper_rebate`.
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = keeper_token_account.owner == keeper.key() @ ErrorCode::Unauthorized,
+    )]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::EmergencyWithdrawalCancelled,
+    helpers::require_admin,
+    state::{EmergencyWithdrawal, Pool},
+};
+
+pub fn handler(ctx: Context<CancelEmergencyWithdrawal>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    emit!(EmergencyWithdrawalCancelled {
+        pool: ctx.accounts.pool.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelEmergencyWithdrawal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency-withdraw", pool.key().as_ref()],
+        bump = emergency_withdrawal.bump,
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{PendingPoolConfig, Pool},
+};
+
+pub fn handler(ctx: Context<CancelPoolConfig>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)
+}
+
+#[derive(Accounts)]
+pub struct CancelPoolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending-config", pool.key().as_ref()],
+        bump = pending_config.bump,
+    )]
+    pub pending_config: Account<'info, PendingPoolConfig>,
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
     error::ErrorCode,
+    events::KeeperRebateClaimed,
     state::{KeeperRebate, Pool},
 };
 
@@ -17,8 +18,20 @@ pub fn handler(ctx: Context<ClaimKeeperRebate>) -> Result<()> {
         ctx.accounts.keeper.key(),
         ErrorCode::InvalidKeeperRebate
     );
+    match ctx.accounts.keeper_rebate.payout_account {
+        Some(payout_account) => require_keys_eq!(
+            ctx.accounts.keeper_token_account.key(),
+            payout_account,
+            ErrorCode::InvalidTokenAccount
+        ),
+        None => require_keys_eq!(
+            ctx.accounts.keeper_token_account.owner,
+            ctx.accounts.keeper.key(),
+            ErrorCode::Unauthorized
+        ),
+    }
 
-    let amount = ctx.accounts.keeper_rebate.amount;
+    let amount = ctx.accounts.keeper_rebate.execution_rebate;
     require!(amount > 0, ErrorCode::NothingToClaim);
     require!(
         amount <= ctx.accounts.protocol_fee_vault.amount,
@@ -33,27 +46,36 @@ pub fn handler(ctx: Context<ClaimKeeperRebate>) -> Result<()> {
         &[protocol_fee_auth_bump],
     ];
     let signer_seeds = &[signer_seed_group];
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.protocol_fee_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
         to: ctx.accounts.keeper_token_account.to_account_info(),
         authority: ctx.accounts.protocol_fee_auth.to_account_info(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds,
         ),
         amount,
+        ctx.accounts.usdc_mint.decimals,
     )?;
 
-    ctx.accounts.keeper_rebate.amount = 0;
+    ctx.accounts.keeper_rebate.execution_rebate = 0;
     let pool = &mut ctx.accounts.pool;
     pool.pending_keeper_rebates = pool
         .pending_keeper_rebates
         .checked_sub(amount)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
+    emit!(KeeperRebateClaimed {
+        pool: pool.key(),
+        keeper: ctx.accounts.keeper.key(),
+        execution_rebate: amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -63,7 +85,7 @@ pub struct ClaimKeeperRebate<'info> {
     pub keeper: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
@@ -76,13 +98,14 @@ pub struct ClaimKeeperRebate<'info> {
     /// CHECK: protocol fee authority PDA.
     #[account(seeds = [b"protocol-fee-auth", pool.key().as_ref()], bump)]
     pub protocol_fee_auth: UncheckedAccount<'info>,
-    #[account(mut, address = pool.protocol_fee_vault)]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = pool.protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         constraint = keeper_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
-        constraint = keeper_token_account.owner == keeper.key() @ ErrorCode::Unauthorized,
     )]
-    pub keeper_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
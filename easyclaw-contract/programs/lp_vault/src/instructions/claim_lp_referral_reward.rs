@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    events::LpReferralRewardClaimed,
+    state::{LpPosition, Pool},
+};
+
+// Settles the referred position up to now and pays the referrer's accrued cut
+// out of the protocol fee vault.
+pub fn handler(ctx: Context<ClaimLpReferralReward>) -> Result<()> {
+    require!(
+        ctx.accounts.lp_position.referrer == Some(ctx.accounts.referrer.key()),
+        ErrorCode::InvalidReferrer
+    );
+
+    let lp = &mut ctx.accounts.lp_position;
+    lp.settle_referral(&mut ctx.accounts.pool)?;
+    let amount = lp.referral_accrued;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    require!(
+        amount <= ctx.accounts.protocol_fee_vault.amount,
+        ErrorCode::InsufficientProtocolFeeVault
+    );
+
+    let protocol_fee_auth_bump = ctx.bumps.protocol_fee_auth;
+    let protocol_fee_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"protocol-fee-auth",
+        protocol_fee_auth_key.as_ref(),
+        &[protocol_fee_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.protocol_fee_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.referrer_token_account.to_account_info(),
+        authority: ctx.accounts.protocol_fee_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    ctx.accounts.lp_position.referral_accrued = 0;
+    let pool = &mut ctx.accounts.pool;
+    pool.pending_referral_rewards = pool
+        .pending_referral_rewards
+        .checked_sub(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    emit!(LpReferralRewardClaimed {
+        pool: pool.key(),
+        referrer: ctx.accounts.referrer.key(),
+        lp_position: ctx.accounts.lp_position.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimLpReferralReward<'info> {
+    pub referrer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"lp-pos", pool.key().as_ref(), lp_position.owner.as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    /// CHECK: protocol fee authority PDA.
+    #[account(seeds = [b"protocol-fee-auth", pool.key().as_ref()], bump)]
+    pub protocol_fee_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = referrer_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = referrer_token_account.owner == referrer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
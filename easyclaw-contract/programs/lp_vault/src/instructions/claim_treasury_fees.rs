@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, helpers::require_admin, state::Pool};
+
+// Redeems the treasury's accrued fee shares at NAV into the treasury token
+// account, subject to the same liquidity buffer as LP withdrawals.
+pub fn handler(ctx: Context<ClaimTreasuryFees>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+
+    ctx.accounts
+        .pool
+        .accrue_treasury_fees(Clock::get()?.unix_timestamp)?;
+    let share_amount = ctx.accounts.pool.treasury_shares;
+    require!(share_amount > 0, ErrorCode::NothingToClaim);
+    let amount = ctx.accounts.pool.shares_to_amount(share_amount)?;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    let post_liquidity = ctx.accounts.pool.liquidity_after_withdrawal(amount)?;
+
+    let liquidity_auth_bump = ctx.bumps.liquidity_auth;
+    let liquidity_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"liquidity-auth",
+        liquidity_auth_key.as_ref(),
+        &[liquidity_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.liquidity_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.liquidity_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = post_liquidity;
+    pool.total_shares = pool
+        .total_shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.treasury_shares = 0;
+    pool.cumulative_withdrawals = pool.cumulative_withdrawals.saturating_add(amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimTreasuryFees<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = pool.treasury @ ErrorCode::InvalidTreasury,
+        constraint = treasury.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
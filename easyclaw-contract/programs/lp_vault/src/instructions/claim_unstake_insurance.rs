@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    state::{InsuranceStake, Pool},
+};
+
+// Pays out all pending shares at the stake value after any losses taken
+// during the cooldown.
+pub fn handler(ctx: Context<ClaimUnstakeInsurance>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+    require_keys_eq!(
+        ctx.accounts.insurance_stake.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+    let share_amount = ctx.accounts.insurance_stake.pending_shares;
+    require!(share_amount > 0, ErrorCode::NothingToClaim);
+    require!(
+        now >= ctx.accounts.insurance_stake.unstake_requested_at + ctx.accounts.pool.cooldown_secs,
+        ErrorCode::CooldownNotFinished
+    );
+
+    let amount = ctx.accounts.pool.insurance_stake_value(share_amount)?;
+    require!(
+        amount <= ctx.accounts.insurance_vault.amount,
+        ErrorCode::InsufficientInsuranceVault
+    );
+
+    if amount > 0 {
+        let insurance_auth_bump = ctx.bumps.insurance_auth;
+        let insurance_auth_key = ctx.accounts.pool.key();
+        let signer_seed_group: &[&[u8]] = &[
+            b"insurance-auth",
+            insurance_auth_key.as_ref(),
+            &[insurance_auth_bump],
+        ];
+        let signer_seeds = &[signer_seed_group];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.insurance_auth.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    pool.insurance_staked = pool
+        .insurance_staked
+        .checked_sub(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.insurance_stake_shares = pool
+        .insurance_stake_shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.insurance_stake.pending_shares = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimUnstakeInsurance<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"insurance-stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = insurance_stake.bump,
+    )]
+    pub insurance_stake: Account<'info, InsuranceStake>,
+    /// CHECK: insurance auth PDA.
+    #[account(seeds = [b"insurance-auth", pool.key().as_ref()], bump)]
+    pub insurance_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
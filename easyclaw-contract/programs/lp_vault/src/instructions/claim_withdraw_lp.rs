@@ -1,18 +1,34 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
-    constants::BPS_DENOM,
     error::ErrorCode,
+    events::{LpWithdrawClaimed, LpWithdrawQueued},
+    helpers::pro_rata_min_amount_out,
     state::{LpPosition, Pool, WithdrawRequest},
 };
 
-pub fn handler(ctx: Context<ClaimWithdrawLp>) -> Result<()> {
+pub fn handler(
+    ctx: Context<ClaimWithdrawLp>,
+    min_amount_out: u64,
+    allow_partial: bool,
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+    ctx.accounts.pool.require_fresh_nav(now)?;
 
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == ctx.accounts.owner.key()
+            || ctx.accounts.lp_position.delegate == Some(authority),
+        ErrorCode::Unauthorized
+    );
     require_keys_eq!(
         ctx.accounts.withdraw_request.owner,
-        ctx.accounts.user.key(),
+        ctx.accounts.owner.key(),
         ErrorCode::Unauthorized
     );
     require_keys_eq!(
@@ -24,6 +40,10 @@ pub fn handler(ctx: Context<ClaimWithdrawLp>) -> Result<()> {
         !ctx.accounts.withdraw_request.claimed,
         ErrorCode::AlreadyClaimed
     );
+    require!(
+        !ctx.accounts.withdraw_request.queued,
+        ErrorCode::WithdrawQueued
+    );
     require!(
         now >= ctx.accounts.withdraw_request.requested_at + ctx.accounts.pool.cooldown_secs,
         ErrorCode::CooldownNotFinished
@@ -37,32 +57,61 @@ pub fn handler(ctx: Context<ClaimWithdrawLp>) -> Result<()> {
         ErrorCode::InvalidPoolState
     );
 
-    let liquidity_before = ctx.accounts.liquidity_vault.amount;
-    let share_amount = ctx.accounts.withdraw_request.share_amount;
-    let total_shares = ctx.accounts.pool.total_shares;
-    let min_liquidity_buffer_bps = ctx.accounts.pool.min_liquidity_buffer_bps;
-    let withdraw_amount = (share_amount
-        .checked_mul(liquidity_before as u128)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?)
-    .checked_div(total_shares)
-    .ok_or_else(|| error!(ErrorCode::MathOverflow))? as u64;
-
-    require!(withdraw_amount > 0, ErrorCode::InvalidAmount);
-
-    let post_liquidity = liquidity_before
-        .checked_sub(withdraw_amount)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-    let min_buffer_amount = ((liquidity_before as u128)
-        .checked_mul(min_liquidity_buffer_bps as u128)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?)
-    .checked_div(BPS_DENOM as u128)
-    .ok_or_else(|| error!(ErrorCode::MathOverflow))? as u64;
-
+    ctx.accounts.pool.accrue_treasury_fees(now)?;
+    let requested_shares = ctx.accounts.withdraw_request.share_amount;
+    // Paid at NAV so leaving LPs take their share of open trader PnL.
+    let requested_amount = ctx.accounts.pool.shares_to_amount(requested_shares)?;
+    require!(requested_amount > 0, ErrorCode::InvalidAmount);
     require!(
-        post_liquidity >= min_buffer_amount,
-        ErrorCode::LiquidityBufferViolation
+        requested_amount >= min_amount_out,
+        ErrorCode::SlippageExceeded
     );
+    let withdrawable = ctx.accounts.pool.withdrawable_liquidity();
+    let (share_amount, withdraw_amount, post_liquidity) = match ctx
+        .accounts
+        .pool
+        .liquidity_after_withdrawal(requested_amount)
+    {
+        Ok(post_liquidity) => (requested_shares, requested_amount, post_liquidity),
+        // A partial claim takes what the buffer allows at the same price and
+        // leaves the rest of the request pending.
+        Err(_) if allow_partial && withdrawable > 0 => {
+            let partial_shares = requested_shares
+                .checked_mul(withdrawable as u128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+                / requested_amount as u128;
+            let partial_amount = ctx.accounts.pool.shares_to_amount(partial_shares)?;
+            require!(partial_amount > 0, ErrorCode::LiquidityBufferViolation);
+            require!(
+                partial_amount
+                    >= pro_rata_min_amount_out(min_amount_out, partial_shares, requested_shares)?,
+                ErrorCode::SlippageExceeded
+            );
+            let post_liquidity = ctx
+                .accounts
+                .pool
+                .liquidity_after_withdrawal(partial_amount)?;
+            (partial_shares, partial_amount, post_liquidity)
+        }
+        // Otherwise a claim the buffer blocks joins the withdrawal queue
+        // instead of failing; the crank pays it at NAV once liquidity frees up.
+        Err(_) => {
+            let pool = &mut ctx.accounts.pool;
+            let req = &mut ctx.accounts.withdraw_request;
+            req.queued = true;
+            req.queue_position = pool.enqueue_withdrawal()?;
+            req.min_amount_out = min_amount_out;
+            emit!(LpWithdrawQueued {
+                pool: pool.key(),
+                owner: req.owner,
+                nonce: req.nonce,
+                share_amount: requested_shares,
+                queue_position: req.queue_position,
+                timestamp: now,
+            });
+            return Ok(());
+        }
+    };
 
     let liquidity_auth_bump = ctx.bumps.liquidity_auth;
     let liquidity_auth_key = ctx.accounts.pool.key();
@@ -72,69 +121,98 @@ pub fn handler(ctx: Context<ClaimWithdrawLp>) -> Result<()> {
         &[liquidity_auth_bump],
     ];
     let signer_seeds = &[signer_seed_group];
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.liquidity_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
         to: ctx.accounts.user_token_account.to_account_info(),
         authority: ctx.accounts.liquidity_auth.to_account_info(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds,
         ),
         withdraw_amount,
+        ctx.accounts.usdc_mint.decimals,
     )?;
 
     let lp = &mut ctx.accounts.lp_position;
+    lp.settle_referral(&mut ctx.accounts.pool)?;
     lp.pending_shares = lp
         .pending_shares
         .checked_sub(share_amount)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
     let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = post_liquidity;
+    pool.cumulative_withdrawals = pool.cumulative_withdrawals.saturating_add(withdraw_amount);
     pool.total_shares = pool
         .total_shares
         .checked_sub(share_amount)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let remaining_shares = requested_shares - share_amount;
+
+    emit!(LpWithdrawClaimed {
+        pool: pool.key(),
+        owner: ctx.accounts.withdraw_request.owner,
+        nonce: ctx.accounts.withdraw_request.nonce,
+        share_amount,
+        amount: withdraw_amount,
+        remaining_shares,
+        share_price: pool.share_price()?,
+        accounted_liquidity: pool.accounted_liquidity,
+        timestamp: now,
+    });
 
+    if remaining_shares > 0 {
+        ctx.accounts.withdraw_request.share_amount = remaining_shares;
+        return Ok(());
+    }
     ctx.accounts.withdraw_request.claimed = true;
+    ctx.accounts
+        .withdraw_request
+        .close(ctx.accounts.owner.to_account_info())?;
 
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ClaimWithdrawLp<'info> {
+    // The owner or the position's delegate.
+    pub authority: Signer<'info>,
+    /// CHECK: position owner; receives the payout and the request rent.
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub owner: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
     #[account(
         mut,
-        seeds = [b"lp-pos", pool.key().as_ref(), user.key().as_ref()],
+        seeds = [b"lp-pos", pool.key().as_ref(), owner.key().as_ref()],
         bump = lp_position.bump,
     )]
     pub lp_position: Account<'info, LpPosition>,
     #[account(
         mut,
-        close = user,
-        seeds = [b"withdraw-req", pool.key().as_ref(), user.key().as_ref(), &withdraw_request.nonce.to_le_bytes()],
+        seeds = [b"withdraw-req", pool.key().as_ref(), owner.key().as_ref(), &withdraw_request.nonce.to_le_bytes()],
         bump = withdraw_request.bump,
     )]
     pub withdraw_request: Account<'info, WithdrawRequest>,
     /// CHECK: liquidity auth PDA.
     #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
     pub liquidity_auth: UncheckedAccount<'info>,
-    #[account(mut, address = pool.liquidity_vault)]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         constraint = user_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
-        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.owner == owner.key() @ ErrorCode::Unauthorized,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
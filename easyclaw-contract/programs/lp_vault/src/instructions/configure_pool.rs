@@ -1,32 +1,46 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    error::ErrorCode,
     helpers::require_admin,
-    state::{Pool, PoolConfigParams},
+    state::{PendingPoolConfig, Pool, PoolConfigParams},
 };
 
+// Queues new parameters behind the pool's config delay. They take effect
+// through `execute_pool_config`; an earlier proposal must be cancelled first.
 pub fn handler(ctx: Context<ConfigurePool>, params: PoolConfigParams) -> Result<()> {
     require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
     params.validate()?;
 
-    let pool = &mut ctx.accounts.pool;
-    pool.cooldown_secs = params.cooldown_secs;
-    pool.min_liquidity_buffer_bps = params.min_liquidity_buffer_bps;
-    pool.lp_fee_bps = params.lp_fee_bps;
-    pool.insurance_fee_bps = params.insurance_fee_bps;
-    pool.protocol_fee_bps = params.protocol_fee_bps;
-    pool.execution_rebate_usdc = params.execution_rebate_usdc;
+    let now = Clock::get()?.unix_timestamp;
+    let pending = &mut ctx.accounts.pending_config;
+    pending.pool = ctx.accounts.pool.key();
+    pending.params = params;
+    pending.proposed_at = now;
+    pending.eta = now
+        .checked_add(ctx.accounts.pool.config_delay_secs)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pending.bump = ctx.bumps.pending_config;
 
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ConfigurePool<'info> {
+    #[account(mut)]
     pub admin: Signer<'info>,
     #[account(
-        mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"pending-config", pool.key().as_ref()],
+        bump,
+        space = 8 + PendingPoolConfig::INIT_SPACE,
+    )]
+    pub pending_config: Account<'info, PendingPoolConfig>,
+    pub system_program: Program<'info, System>,
 }
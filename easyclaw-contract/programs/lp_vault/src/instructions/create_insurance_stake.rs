@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{InsuranceStake, Pool};
+
+pub fn handler(ctx: Context<CreateInsuranceStake>) -> Result<()> {
+    let stake = &mut ctx.accounts.insurance_stake;
+    stake.owner = ctx.accounts.user.key();
+    stake.pool = ctx.accounts.pool.key();
+    stake.shares = 0;
+    stake.pending_shares = 0;
+    stake.unstake_requested_at = 0;
+    stake.bump = ctx.bumps.insurance_stake;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateInsuranceStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"insurance-stake", pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + InsuranceStake::INIT_SPACE,
+    )]
+    pub insurance_stake: Account<'info, InsuranceStake>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,15 +1,30 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{LpPosition, Pool};
+use crate::{
+    constants::{LP_POSITION_RESERVED_BYTES, LP_POSITION_VERSION},
+    error::ErrorCode,
+    state::{LpPosition, Pool},
+};
+
+pub fn handler(ctx: Context<CreateLpPosition>, referrer: Option<Pubkey>) -> Result<()> {
+    require!(
+        referrer != Some(ctx.accounts.user.key()),
+        ErrorCode::InvalidReferrer
+    );
 
-pub fn handler(ctx: Context<CreateLpPosition>) -> Result<()> {
     let lp = &mut ctx.accounts.lp_position;
+    lp.version = LP_POSITION_VERSION;
     lp.owner = ctx.accounts.user.key();
     lp.pool = ctx.accounts.pool.key();
     lp.shares = 0;
     lp.pending_shares = 0;
     lp.withdraw_nonce = 0;
     lp.bump = ctx.bumps.lp_position;
+    lp.reserved = [0; LP_POSITION_RESERVED_BYTES];
+    lp.delegate = None;
+    lp.referrer = referrer;
+    lp.referral_fee_checkpoint = ctx.accounts.pool.lp_fees_per_share;
+    lp.referral_accrued = 0;
     Ok(())
 }
 
@@ -18,7 +33,7 @@ pub struct CreateLpPosition<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
+    constants::LOCKED_INITIAL_SHARES,
     error::ErrorCode,
-    state::{LpPosition, Pool},
+    events::LpDeposited,
+    state::{LpAllowlistEntry, LpPosition, Pool},
 };
 
-pub fn handler(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<DepositLp>, amount: u64, min_shares_out: u128) -> Result<()> {
     require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.pool.deposits_paused,
+        ErrorCode::DepositsPaused
+    );
 
     require_keys_eq!(
         ctx.accounts.lp_position.owner,
@@ -19,33 +25,99 @@ pub fn handler(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
         ctx.accounts.pool.key(),
         ErrorCode::InvalidLpPosition
     );
+    require!(
+        !ctx.accounts.pool.allowlist_enabled || ctx.accounts.allowlist_entry.is_some(),
+        ErrorCode::NotAllowlisted
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.require_fresh_nav(now)?;
+    ctx.accounts.pool.accrue_treasury_fees(now)?;
 
-    let pre_liquidity = ctx.accounts.liquidity_vault.amount;
+    // Share math uses tracked liquidity net of trader PnL, so neither direct
+    // vault donations nor open exposure can move the price.
+    let pre_nav = ctx.accounts.pool.net_asset_value();
     let total_shares_before = ctx.accounts.pool.total_shares;
-    token::transfer(ctx.accounts.deposit_ctx(), amount)?;
+    require!(
+        total_shares_before == 0 || pre_nav > 0,
+        ErrorCode::InvalidPoolState
+    );
+    let vault_before = ctx.accounts.liquidity_vault.amount;
+    token_interface::transfer_checked(
+        ctx.accounts.deposit_ctx(),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+    // Token-2022 transfer fees are withheld from the vault side, so only what
+    // actually arrived is credited.
+    ctx.accounts.liquidity_vault.reload()?;
+    let amount = ctx
+        .accounts
+        .liquidity_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let minted_shares = if total_shares_before == 0 {
-        amount as u128
+    let (minted_shares, locked_shares) = if total_shares_before == 0 {
+        let minted = (amount as u128)
+            .checked_sub(LOCKED_INITIAL_SHARES)
+            .ok_or_else(|| error!(ErrorCode::InvalidAmount))?;
+        (minted, LOCKED_INITIAL_SHARES)
     } else {
-        ((amount as u128)
+        let minted = ((amount as u128)
             .checked_mul(total_shares_before)
             .ok_or_else(|| error!(ErrorCode::MathOverflow))?)
-        .checked_div(pre_liquidity as u128)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(pre_nav as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        (minted, 0)
     };
 
     require!(minted_shares > 0, ErrorCode::InvalidAmount);
+    require!(minted_shares >= min_shares_out, ErrorCode::SlippageExceeded);
 
     let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = pool
+        .accounted_liquidity
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.cumulative_deposits = pool.cumulative_deposits.saturating_add(amount);
     pool.total_shares = pool
         .total_shares
         .checked_add(minted_shares)
+        .and_then(|total| total.checked_add(locked_shares))
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        pool.pool_deposit_cap == 0 || pool.accounted_liquidity <= pool.pool_deposit_cap,
+        ErrorCode::PoolDepositCapExceeded
+    );
     let lp = &mut ctx.accounts.lp_position;
+    lp.settle_referral(pool)?;
     lp.shares = lp
         .shares
         .checked_add(minted_shares)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    // Shares queued for withdrawal still count toward the position cap.
+    if pool.position_deposit_cap > 0 {
+        let position_shares = lp
+            .shares
+            .checked_add(lp.pending_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        require!(
+            pool.shares_to_amount(position_shares)? <= pool.position_deposit_cap,
+            ErrorCode::PositionDepositCapExceeded
+        );
+    }
+
+    emit!(LpDeposited {
+        pool: pool.key(),
+        owner: lp.owner,
+        amount,
+        minted_shares,
+        share_price: pool.share_price()?,
+        total_shares: pool.total_shares,
+        accounted_liquidity: pool.accounted_liquidity,
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -56,7 +128,7 @@ pub struct DepositLp<'info> {
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
@@ -71,16 +143,25 @@ pub struct DepositLp<'info> {
         constraint = user_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
         constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut, address = pool.liquidity_vault)]
-    pub liquidity_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // Only required while the pool's allowlist is enabled.
+    #[account(
+        seeds = [b"lp-allow", pool.key().as_ref(), user.key().as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, LpAllowlistEntry>>,
 }
 
 impl<'info> DepositLp<'info> {
-    fn deposit_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
+    fn deposit_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
             from: self.user_token_account.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
             to: self.liquidity_vault.to_account_info(),
             authority: self.user.to_account_info(),
         };
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    events::{ProtocolFeesDistributed, ProtocolFeesWithdrawn},
+    helpers::{mul_bps, require_admin},
+    state::{FeeDistribution, Pool},
+};
+
+// Splits `amount` of protocol fees across the configured recipients, whose
+// token accounts are passed as remaining accounts in the same order. The last
+// recipient takes the rounding remainder. Keeper rebates stay reserved, as in
+// `withdraw_protocol_fees`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeProtocolFees<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let available = ctx
+        .accounts
+        .protocol_fee_vault
+        .amount
+        .saturating_sub(ctx.accounts.pool.reserved_protocol_fees());
+    require!(amount <= available, ErrorCode::InsufficientProtocolFeeVault);
+
+    let recipients = &ctx.accounts.fee_distribution.recipients;
+    require!(
+        ctx.remaining_accounts.len() == recipients.len(),
+        ErrorCode::InvalidFeeRecipients
+    );
+
+    let pool_key = ctx.accounts.pool.key();
+    let bump = ctx.bumps.protocol_fee_auth;
+    let signer_seed_group: &[&[u8]] = &[b"protocol-fee-auth", pool_key.as_ref(), &[bump]];
+    let now = Clock::get()?.unix_timestamp;
+    let mut remaining = amount;
+    for (index, (recipient, info)) in recipients
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .enumerate()
+    {
+        require_keys_eq!(
+            info.key(),
+            recipient.destination,
+            ErrorCode::InvalidFeeRecipients
+        );
+        let destination = InterfaceAccount::<TokenAccount>::try_from(info)?;
+        require_keys_eq!(
+            destination.mint,
+            ctx.accounts.pool.usdc_mint,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let share = if index + 1 == recipients.len() {
+            remaining
+        } else {
+            mul_bps(amount, recipient.bps as u64)?
+        };
+        remaining -= share;
+        if share == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.protocol_fee_vault.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: ctx.accounts.protocol_fee_auth.to_account_info(),
+                },
+                &[signer_seed_group],
+            ),
+            share,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+        emit!(ProtocolFeesWithdrawn {
+            pool: pool_key,
+            destination: recipient.destination,
+            amount: share,
+            reserved_for_rebates: ctx.accounts.pool.reserved_protocol_fees(),
+            timestamp: now,
+        });
+    }
+
+    emit!(ProtocolFeesDistributed {
+        pool: pool_key,
+        amount,
+        recipient_count: recipients.len() as u8,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeProtocolFees<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        seeds = [b"fee-distribution", pool.key().as_ref()],
+        bump = fee_distribution.bump,
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+    /// CHECK: protocol fee authority PDA.
+    #[account(seeds = [b"protocol-fee-auth", pool.key().as_ref()], bump)]
+    pub protocol_fee_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
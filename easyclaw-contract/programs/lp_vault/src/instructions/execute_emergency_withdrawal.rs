@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    events::EmergencyWithdrawalExecuted,
+    helpers::require_admin,
+    state::{EmergencyWithdrawal, Pool, PoolVault},
+};
+
+// Funds leaving the liquidity vault also leave accounted liquidity, so the
+// loss lands on the share price rather than breaking later vault checks.
+pub fn handler(ctx: Context<ExecuteEmergencyWithdrawal>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let withdrawal = &ctx.accounts.emergency_withdrawal;
+    require!(now >= withdrawal.eta, ErrorCode::EmergencyTimelockActive);
+    let vault = withdrawal.vault;
+    require_keys_eq!(
+        ctx.accounts.source_vault.key(),
+        vault.address(&ctx.accounts.pool),
+        ErrorCode::EmergencyVaultMismatch
+    );
+    let amount = withdrawal.amount.min(ctx.accounts.source_vault.amount);
+
+    let pool_key = ctx.accounts.pool.key();
+    let (authority, bump) =
+        Pubkey::find_program_address(&[vault.authority_seed(), pool_key.as_ref()], &crate::ID);
+    require_keys_eq!(
+        ctx.accounts.source_authority.key(),
+        authority,
+        ErrorCode::EmergencyVaultMismatch
+    );
+    let signer_seed_group: &[&[u8]] = &[vault.authority_seed(), pool_key.as_ref(), &[bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.source_vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.source_authority.to_account_info(),
+            },
+            &[signer_seed_group],
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    if vault == PoolVault::Liquidity {
+        let pool = &mut ctx.accounts.pool;
+        pool.accounted_liquidity = pool.accounted_liquidity.saturating_sub(amount);
+    }
+
+    emit!(EmergencyWithdrawalExecuted {
+        pool: pool_key,
+        vault,
+        amount,
+        destination: ctx.accounts.destination.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteEmergencyWithdrawal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency-withdraw", pool.key().as_ref()],
+        bump = emergency_withdrawal.bump,
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    /// CHECK: authority PDA of the source vault, checked in the handler.
+    pub source_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = emergency_withdrawal.destination @ ErrorCode::InvalidTokenAccount)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
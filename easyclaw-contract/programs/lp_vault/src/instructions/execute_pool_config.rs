@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{PendingPoolConfig, Pool},
+};
+
+pub fn handler(ctx: Context<ExecutePoolConfig>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let pending = &ctx.accounts.pending_config;
+    require!(now >= pending.eta, ErrorCode::ConfigTimelockActive);
+    let params = pending.params;
+    params.validate()?;
+    ctx.accounts.pool.apply_config(&params, now)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecutePoolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending-config", pool.key().as_ref()],
+        bump = pending_config.bump,
+    )]
+    pub pending_config: Account<'info, PendingPoolConfig>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{FeeDistribution, FeeRecipient, Pool},
+};
+
+pub fn handler(ctx: Context<InitFeeDistribution>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    FeeDistribution::validate_recipients(&recipients)?;
+
+    let distribution = &mut ctx.accounts.fee_distribution;
+    distribution.pool = ctx.accounts.pool.key();
+    distribution.recipients = recipients;
+    distribution.bump = ctx.bumps.fee_distribution;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitFeeDistribution<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"fee-distribution", pool.key().as_ref()],
+        bump,
+        space = 8 + FeeDistribution::INIT_SPACE,
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+    pub system_program: Program<'info, System>,
+}
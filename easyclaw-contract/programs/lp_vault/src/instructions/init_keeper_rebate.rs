@@ -6,7 +6,8 @@ pub fn handler(ctx: Context<InitKeeperRebate>) -> Result<()> {
     let rebate = &mut ctx.accounts.keeper_rebate;
     rebate.pool = ctx.accounts.pool.key();
     rebate.keeper = ctx.accounts.keeper.key();
-    rebate.amount = 0;
+    rebate.execution_rebate = 0;
+    rebate.payout_account = None;
     rebate.bump = ctx.bumps.keeper_rebate;
     Ok(())
 }
@@ -16,7 +17,7 @@ pub struct InitKeeperRebate<'info> {
     #[account(mut)]
     pub keeper: Signer<'info>,
     #[account(
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
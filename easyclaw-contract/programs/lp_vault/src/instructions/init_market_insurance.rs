@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{MarketInsurance, Pool},
+};
+
+pub fn handler(ctx: Context<InitMarketInsurance>, market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let insurance = &mut ctx.accounts.market_insurance;
+    insurance.pool = ctx.accounts.pool.key();
+    insurance.market_id = market_id;
+    insurance.earmarked = 0;
+    insurance.total_contributed = 0;
+    insurance.total_bad_debt = 0;
+    insurance.drawn_from_shared = 0;
+    insurance.bump = ctx.bumps.market_insurance;
+    insurance.execution_rebate_override = None;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitMarketInsurance<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"market-insurance", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketInsurance::INIT_SPACE,
+    )]
+    pub market_insurance: Account<'info, MarketInsurance>,
+    pub system_program: Program<'info, System>,
+}
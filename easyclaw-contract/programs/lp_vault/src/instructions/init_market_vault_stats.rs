@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{MarketVaultStats, Pool},
+};
+
+pub fn handler(ctx: Context<InitMarketVaultStats>, market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let stats = &mut ctx.accounts.market_stats;
+    stats.pool = ctx.accounts.pool.key();
+    stats.market_id = market_id;
+    stats.fill_count = 0;
+    stats.cumulative_notional = 0;
+    stats.cumulative_fees = 0;
+    stats.lp_fees = 0;
+    stats.insurance_fees = 0;
+    stats.protocol_fees = 0;
+    stats.keeper_credits = 0;
+    stats.trader_pnl = 0;
+    stats.liquidation_count = 0;
+    stats.liquidation_penalties = 0;
+    stats.bad_debt = 0;
+    stats.bump = ctx.bumps.market_stats;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitMarketVaultStats<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"market-stats", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketVaultStats::INIT_SPACE,
+    )]
+    pub market_stats: Account<'info, MarketVaultStats>,
+    pub system_program: Program<'info, System>,
+}
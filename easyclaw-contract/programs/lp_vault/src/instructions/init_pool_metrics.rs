@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{Pool, PoolMetrics},
+};
+
+pub fn handler(ctx: Context<InitPoolMetrics>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let metrics = &mut ctx.accounts.pool_metrics;
+    metrics.pool = ctx.accounts.pool.key();
+    metrics.bump = ctx.bumps.pool_metrics;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitPoolMetrics<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"pool-metrics", pool.key().as_ref()],
+        bump,
+        space = 8 + PoolMetrics::INIT_SPACE,
+    )]
+    pub pool_metrics: Account<'info, PoolMetrics>,
+    pub system_program: Program<'info, System>,
+}
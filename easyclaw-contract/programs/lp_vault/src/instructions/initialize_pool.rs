@@ -1,25 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::{
-        BPS_DENOM, DEFAULT_EXECUTION_REBATE_USDC, DEFAULT_INSURANCE_BPS, DEFAULT_LP_BPS,
-        DEFAULT_PROTOCOL_BPS,
+        DEFAULT_CONFIG_DELAY_SECS, DEFAULT_EXECUTION_REBATE_USDC, DEFAULT_INSURANCE_BPS,
+        DEFAULT_LP_BPS, DEFAULT_PROTOCOL_BPS, POOL_RESERVED_BYTES, POOL_VERSION,
     },
     error::ErrorCode,
-    state::Pool,
+    state::{FeeSplitCurve, Pool},
 };
 
 pub fn handler(
     ctx: Context<InitializePool>,
     cooldown_secs: i64,
-    min_liquidity_buffer_bps: u16,
+    liquidity_buffer_oi_bps: u16,
+    pool_index: u16,
 ) -> Result<()> {
     require!(cooldown_secs >= 0, ErrorCode::InvalidCooldown);
-    require!(
-        min_liquidity_buffer_bps <= BPS_DENOM as u16,
-        ErrorCode::InvalidBps
-    );
     require!(
         ctx.accounts.order_engine_program.executable,
         ErrorCode::InvalidProgramAccount
@@ -36,33 +33,90 @@ pub fn handler(
     );
 
     let pool = &mut ctx.accounts.pool;
+    pool.version = POOL_VERSION;
     pool.admin = ctx.accounts.admin.key();
     pool.usdc_mint = ctx.accounts.usdc_mint.key();
+    pool.pool_index = pool_index;
     pool.order_engine_program = ctx.accounts.order_engine_program.key();
     pool.engine_authority = ctx.accounts.engine_authority.key();
     pool.liquidity_vault = ctx.accounts.liquidity_vault.key();
     pool.insurance_vault = ctx.accounts.insurance_vault.key();
     pool.protocol_fee_vault = ctx.accounts.protocol_fee_vault.key();
     pool.cooldown_secs = cooldown_secs;
-    pool.min_liquidity_buffer_bps = min_liquidity_buffer_bps;
+    pool.liquidity_buffer_oi_bps = liquidity_buffer_oi_bps;
     pool.lp_fee_bps = DEFAULT_LP_BPS;
     pool.insurance_fee_bps = DEFAULT_INSURANCE_BPS;
     pool.protocol_fee_bps = DEFAULT_PROTOCOL_BPS;
+    pool.fee_curve = FeeSplitCurve::default();
+    pool.marked_utilization_bps = 0;
+    pool.max_liquidation_reward = 0;
+    pool.cumulative_bad_debt_covered = 0;
+    pool.fee_snapshot_count = 0;
+    pool.last_fee_snapshot_at = 0;
+    pool.circuit_breaker_drawdown_bps = 0;
+    pool.circuit_breaker_window_secs = 0;
+    pool.pnl_window_start = 0;
+    pool.pnl_window_trader_pnl = 0;
+    pool.pnl_window_nav = 0;
+    pool.referral_fee_bps = 0;
+    pool.pending_referral_rewards = 0;
+    pool.lp_fees_per_share = 0;
+    pool.pending_lp_fee_bps = 0;
+    pool.pending_insurance_fee_bps = 0;
+    pool.pending_protocol_fee_bps = 0;
+    pool.pending_fee_split_eta = 0;
     pool.execution_rebate_usdc = DEFAULT_EXECUTION_REBATE_USDC;
+    pool.execution_rebate_fee_bps = 0;
     pool.total_shares = 0;
+    pool.accounted_liquidity = 0;
     pool.pending_keeper_rebates = 0;
+    pool.unfunded_keeper_rebates = 0;
     pool.total_trading_fees = 0;
+    pool.cumulative_lp_fees = 0;
+    pool.cumulative_deposits = 0;
+    pool.cumulative_withdrawals = 0;
+    pool.fee_window_start = Clock::get()?.unix_timestamp;
+    pool.fee_window_fees = 0;
+    pool.prev_fee_window_fees = 0;
     pool.cumulative_trader_pnl = 0;
+    pool.total_maker_rebates = 0;
+    pool.unrealized_trader_pnl = 0;
+    pool.nav_marked_at = 0;
+    pool.open_interest = 0;
+    pool.min_insurance_oi_bps = 0;
+    pool.min_insurance_coverage_bps = 0;
+    pool.max_insurance_oi_bps = 0;
+    pool.insurance_staked = 0;
+    pool.insurance_stake_shares = 0;
+    pool.insurance_staker_fee_bps = 0;
+    pool.total_staker_losses = 0;
+    pool.management_fee_bps = 0;
+    pool.performance_fee_bps = 0;
+    pool.high_water_mark = 0;
+    pool.pool_deposit_cap = 0;
+    pool.position_deposit_cap = 0;
+    pool.instant_withdraw_fee_bps = 0;
+    pool.deposits_paused = false;
+    pool.withdrawals_paused = false;
+    pool.allowlist_enabled = false;
+    pool.withdraw_queue_head = 0;
+    pool.withdraw_queue_tail = 0;
+    pool.config_delay_secs = DEFAULT_CONFIG_DELAY_SECS;
+    pool.treasury = Pubkey::default();
+    pool.treasury_shares = 0;
+    pool.last_management_fee_ts = pool.fee_window_start;
     pool.bump = ctx.bumps.pool;
+    pool.reserved = [0; POOL_RESERVED_BYTES];
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(cooldown_secs: i64, liquidity_buffer_oi_bps: u16, pool_index: u16)]
 pub struct InitializePool<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
     /// CHECK: external program id pinned into pool config.
     pub order_engine_program: UncheckedAccount<'info>,
     /// CHECK: PDA owned by order_engine used as CPI signer.
@@ -70,7 +124,7 @@ pub struct InitializePool<'info> {
     #[account(
         init,
         payer = admin,
-        seeds = [b"pool", usdc_mint.key().as_ref()],
+        seeds = [b"pool", usdc_mint.key().as_ref(), &pool_index.to_le_bytes()],
         bump,
         space = 8 + Pool::INIT_SPACE,
     )]
@@ -91,8 +145,9 @@ pub struct InitializePool<'info> {
         bump,
         token::mint = usdc_mint,
         token::authority = liquidity_auth,
+        token::token_program = token_program,
     )]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init,
         payer = admin,
@@ -100,8 +155,9 @@ pub struct InitializePool<'info> {
         bump,
         token::mint = usdc_mint,
         token::authority = insurance_auth,
+        token::token_program = token_program,
     )]
-    pub insurance_vault: Account<'info, TokenAccount>,
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init,
         payer = admin,
@@ -109,8 +165,9 @@ pub struct InitializePool<'info> {
         bump,
         token::mint = usdc_mint,
         token::authority = protocol_fee_auth,
+        token::token_program = token_program,
     )]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
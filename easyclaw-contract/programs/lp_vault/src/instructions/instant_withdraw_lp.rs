@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    helpers::mul_bps,
+    state::{LpPosition, Pool},
+};
+
+// Emergency exit that skips the withdrawal cooldown. The shares are redeemed at
+// NAV less `instant_withdraw_fee_bps`; the haircut stays in the pool for the
+// remaining LPs.
+pub fn handler(ctx: Context<InstantWithdrawLp>, share_amount: u128) -> Result<()> {
+    require!(share_amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+    require!(
+        ctx.accounts.pool.instant_withdraw_fee_bps > 0,
+        ErrorCode::InstantWithdrawDisabled
+    );
+    require_keys_eq!(
+        ctx.accounts.lp_position.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+    require_keys_eq!(
+        ctx.accounts.lp_position.pool,
+        ctx.accounts.pool.key(),
+        ErrorCode::InvalidLpPosition
+    );
+    require!(
+        ctx.accounts.lp_position.shares >= share_amount,
+        ErrorCode::InsufficientShares
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.require_fresh_nav(now)?;
+    ctx.accounts.pool.accrue_treasury_fees(now)?;
+    let gross_amount = ctx.accounts.pool.shares_to_amount(share_amount)?;
+    let haircut = mul_bps(
+        gross_amount,
+        ctx.accounts.pool.instant_withdraw_fee_bps as u64,
+    )?;
+    let withdraw_amount = gross_amount
+        .checked_sub(haircut)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(withdraw_amount > 0, ErrorCode::InvalidAmount);
+    let post_liquidity = ctx
+        .accounts
+        .pool
+        .liquidity_after_withdrawal(withdraw_amount)?;
+
+    let liquidity_auth_bump = ctx.bumps.liquidity_auth;
+    let liquidity_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"liquidity-auth",
+        liquidity_auth_key.as_ref(),
+        &[liquidity_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.liquidity_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.liquidity_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        withdraw_amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    let lp = &mut ctx.accounts.lp_position;
+    lp.settle_referral(&mut ctx.accounts.pool)?;
+    lp.shares = lp
+        .shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = post_liquidity;
+    pool.cumulative_withdrawals = pool.cumulative_withdrawals.saturating_add(withdraw_amount);
+    pool.total_shares = pool
+        .total_shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InstantWithdrawLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"lp-pos", pool.key().as_ref(), user.key().as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::CircuitBreakerTripped, helpers::assert_engine_authority, state::Pool};
+
+// Records the engine's mark of unrealized trader PnL across all markets, which
+// LP share pricing nets out of accounted liquidity, and the open interest the
+// insurance bounds are sized against. Utilization for the fee curve is sampled
+// and the PnL circuit breaker checked here as well.
+pub fn handler(
+    ctx: Context<MarkNav>,
+    unrealized_trader_pnl: i64,
+    open_interest: u64,
+) -> Result<()> {
+    assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.unrealized_trader_pnl = unrealized_trader_pnl;
+    pool.open_interest = open_interest;
+    pool.marked_utilization_bps = pool.utilization_bps();
+    let now = Clock::get()?.unix_timestamp;
+    pool.nav_marked_at = now;
+    if let Some(trader_pnl_gain) = pool.check_circuit_breaker(now) {
+        emit!(CircuitBreakerTripped {
+            pool: pool.key(),
+            trader_pnl_gain,
+            window_nav: pool.pnl_window_nav,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MarkNav<'info> {
+    pub engine_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, helpers::grow_account, state::KeeperRebate};
+
+// Permissionless, like `migrate_lp_position`: grows a rebate account created
+// before `payout_account` was appended, so its accrued rebate can be claimed.
+pub fn handler(ctx: Context<MigrateKeeperRebate>) -> Result<()> {
+    let info = ctx.accounts.keeper_rebate.to_account_info();
+    let rebate = migrated_keeper_rebate(&info.try_borrow_data()?)?;
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + KeeperRebate::INIT_SPACE,
+    )?;
+    let mut data = info.try_borrow_mut_data()?;
+    rebate.try_serialize(&mut &mut data[..])
+}
+
+// Only `payout_account` was appended, so an older rebate reads correctly once
+// zero-extended (no payout account).
+pub fn migrated_keeper_rebate(data: &[u8]) -> Result<KeeperRebate> {
+    require!(
+        data.len() < 8 + KeeperRebate::INIT_SPACE,
+        ErrorCode::AlreadyMigrated
+    );
+    let mut padded = data.to_vec();
+    padded.resize(8 + KeeperRebate::INIT_SPACE, 0);
+    KeeperRebate::try_deserialize(&mut &padded[..])
+}
+
+#[derive(Accounts)]
+pub struct MigrateKeeperRebate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: the older layout does not deserialize as `KeeperRebate`;
+    /// ownership is checked here and the discriminator by
+    /// `migrated_keeper_rebate`.
+    #[account(mut, owner = crate::ID)]
+    pub keeper_rebate: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
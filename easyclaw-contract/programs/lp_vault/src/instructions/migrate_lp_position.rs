@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::LP_POSITION_VERSION, error::ErrorCode, helpers::grow_account, state::LpPosition,
+};
+
+// Permissionless: only changes the layout, so anyone may pay to migrate a
+// position.
+pub fn handler(ctx: Context<MigrateLpPosition>) -> Result<()> {
+    let info = ctx.accounts.lp_position.to_account_info();
+    let lp = migrated_lp_position(&info.try_borrow_data()?)?;
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + LpPosition::INIT_SPACE,
+    )?;
+    let mut data = info.try_borrow_mut_data()?;
+    lp.try_serialize(&mut &mut data[..])
+}
+
+// Every layout so far only appended fields, so older positions read correctly
+// once zero-extended to the current size.
+pub fn migrated_lp_position(data: &[u8]) -> Result<LpPosition> {
+    let mut padded = data.to_vec();
+    padded.resize(padded.len().max(8 + LpPosition::INIT_SPACE), 0);
+    let mut lp = LpPosition::try_deserialize(&mut &padded[..])?;
+    require!(lp.version < LP_POSITION_VERSION, ErrorCode::AlreadyMigrated);
+    lp.version = LP_POSITION_VERSION;
+    Ok(lp)
+}
+
+#[derive(Accounts)]
+pub struct MigrateLpPosition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: an older layout may not deserialize as `LpPosition`; ownership
+    /// is checked here and the discriminator by `migrated_lp_position`.
+    #[account(mut, owner = crate::ID)]
+    pub lp_position: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
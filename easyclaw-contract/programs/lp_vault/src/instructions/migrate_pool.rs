@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::POOL_VERSION,
+    error::ErrorCode,
+    helpers::grow_account,
+    state::{LegacyPool, Pool},
+};
+
+// Brings a pool to the current layout and version. Unversioned pools are
+// decoded field by field as `LegacyPool`; versioned ones are grown (new bytes
+// are zeroed) and restamped.
+pub fn handler(ctx: Context<MigratePool>) -> Result<()> {
+    let info = ctx.accounts.pool.to_account_info();
+    let pool = migrated_pool(&info.try_borrow_data()?)?;
+    require_keys_eq!(
+        ctx.accounts.admin.key(),
+        pool.admin,
+        ErrorCode::Unauthorized
+    );
+
+    grow_account(
+        &info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + Pool::INIT_SPACE,
+    )?;
+    let mut data = info.try_borrow_mut_data()?;
+    pool.try_serialize(&mut &mut data[..])
+}
+
+// The current-layout pool for the given account data, or `AlreadyMigrated`.
+pub fn migrated_pool(data: &[u8]) -> Result<Pool> {
+    require!(
+        data.len() >= 8 && data[..8] == *Pool::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    if data.len() == LegacyPool::ACCOUNT_LEN {
+        let legacy = LegacyPool::deserialize(&mut &data[8..])?;
+        return Ok(legacy.into_pool());
+    }
+
+    let mut padded = data.to_vec();
+    padded.resize(padded.len().max(8 + Pool::INIT_SPACE), 0);
+    let mut pool = Pool::try_deserialize(&mut &padded[..])?;
+    require!(pool.version < POOL_VERSION, ErrorCode::AlreadyMigrated);
+    pool.version = POOL_VERSION;
+    Ok(pool)
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: an older layout may not deserialize as `Pool`; ownership is
+    /// checked here and the discriminator by `migrated_pool`.
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
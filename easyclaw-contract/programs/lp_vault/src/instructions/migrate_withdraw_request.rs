@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, helpers::grow_account, state::WithdrawRequest};
+
+// Permissionless, like `migrate_lp_position`: grows a withdraw request created
+// before the queue fields were appended, so it can still be claimed.
+pub fn handler(ctx: Context<MigrateWithdrawRequest>) -> Result<()> {
+    let info = ctx.accounts.withdraw_request.to_account_info();
+    let request = migrated_withdraw_request(&info.try_borrow_data()?)?;
+    grow_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + WithdrawRequest::INIT_SPACE,
+    )?;
+    let mut data = info.try_borrow_mut_data()?;
+    request.try_serialize(&mut &mut data[..])
+}
+
+// Only the queue fields were appended, so an older request reads correctly
+// once zero-extended (not queued, no minimum payout).
+pub fn migrated_withdraw_request(data: &[u8]) -> Result<WithdrawRequest> {
+    require!(
+        data.len() < 8 + WithdrawRequest::INIT_SPACE,
+        ErrorCode::AlreadyMigrated
+    );
+    let mut padded = data.to_vec();
+    padded.resize(8 + WithdrawRequest::INIT_SPACE, 0);
+    WithdrawRequest::try_deserialize(&mut &padded[..])
+}
+
+#[derive(Accounts)]
+pub struct MigrateWithdrawRequest<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: the older layout does not deserialize as `WithdrawRequest`;
+    /// ownership is checked here and the discriminator by
+    /// `migrated_withdraw_request`.
+    #[account(mut, owner = crate::ID)]
+    pub withdraw_request: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
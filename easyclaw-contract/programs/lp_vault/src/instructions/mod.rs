@@ -1,21 +1,105 @@
+pub mod add_lp_allowlist_entry;
 pub mod apply_liquidation;
 pub mod apply_trade_fill;
+pub mod cancel_emergency_withdrawal;
+pub mod cancel_pool_config;
 pub mod claim_keeper_rebate;
+pub mod claim_lp_referral_reward;
+pub mod claim_treasury_fees;
+pub mod claim_unstake_insurance;
 pub mod claim_withdraw_lp;
 pub mod configure_pool;
+pub mod create_insurance_stake;
 pub mod create_lp_position;
 pub mod deposit_lp;
+pub mod distribute_protocol_fees;
+pub mod execute_emergency_withdrawal;
+pub mod execute_pool_config;
+pub mod init_fee_distribution;
 pub mod init_keeper_rebate;
+pub mod init_market_insurance;
+pub mod init_market_vault_stats;
+pub mod init_pool_metrics;
 pub mod initialize_pool;
+pub mod instant_withdraw_lp;
+pub mod mark_nav;
+pub mod migrate_keeper_rebate;
+pub mod migrate_lp_position;
+pub mod migrate_pool;
+pub mod migrate_withdraw_request;
+pub mod pay_insurance;
+pub mod pay_maker_rebate;
+pub mod process_withdraw_queue;
+pub mod propose_emergency_withdrawal;
+pub mod rebalance_insurance;
+pub mod record_fee_snapshot;
+pub mod record_pool_metrics;
+pub mod remove_lp_allowlist_entry;
+pub mod request_unstake_insurance;
 pub mod request_withdraw_lp;
+pub mod set_fee_recipients;
+pub mod set_keeper_payout_account;
+pub mod set_lp_allowlist_enabled;
+pub mod set_lp_delegate;
+pub mod set_market_execution_rebate;
+pub mod set_pool_pause;
+pub mod stake_insurance;
+pub mod sweep_surplus;
+pub mod sync_donations;
+pub mod sync_fee_split;
+pub mod view_max_withdrawable;
+pub mod view_share_price;
+pub mod withdraw_protocol_fees;
 
+pub use add_lp_allowlist_entry::*;
 pub use apply_liquidation::*;
 pub use apply_trade_fill::*;
+pub use cancel_emergency_withdrawal::*;
+pub use cancel_pool_config::*;
 pub use claim_keeper_rebate::*;
+pub use claim_lp_referral_reward::*;
+pub use claim_treasury_fees::*;
+pub use claim_unstake_insurance::*;
 pub use claim_withdraw_lp::*;
 pub use configure_pool::*;
+pub use create_insurance_stake::*;
 pub use create_lp_position::*;
 pub use deposit_lp::*;
+pub use distribute_protocol_fees::*;
+pub use execute_emergency_withdrawal::*;
+pub use execute_pool_config::*;
+pub use init_fee_distribution::*;
 pub use init_keeper_rebate::*;
+pub use init_market_insurance::*;
+pub use init_market_vault_stats::*;
+pub use init_pool_metrics::*;
 pub use initialize_pool::*;
+pub use instant_withdraw_lp::*;
+pub use mark_nav::*;
+pub use migrate_keeper_rebate::*;
+pub use migrate_lp_position::*;
+pub use migrate_pool::*;
+pub use migrate_withdraw_request::*;
+pub use pay_insurance::*;
+pub use pay_maker_rebate::*;
+pub use process_withdraw_queue::*;
+pub use propose_emergency_withdrawal::*;
+pub use rebalance_insurance::*;
+pub use record_fee_snapshot::*;
+pub use record_pool_metrics::*;
+pub use remove_lp_allowlist_entry::*;
+pub use request_unstake_insurance::*;
 pub use request_withdraw_lp::*;
+pub use set_fee_recipients::*;
+pub use set_keeper_payout_account::*;
+pub use set_lp_allowlist_enabled::*;
+pub use set_lp_delegate::*;
+pub use set_market_execution_rebate::*;
+pub use set_pool_pause::*;
+pub use stake_insurance::*;
+pub use sweep_surplus::*;
+pub use sync_donations::*;
+pub use sync_fee_split::*;
+pub use view_max_withdrawable::*;
+pub use view_share_price::*;
+pub use withdraw_protocol_fees::*;
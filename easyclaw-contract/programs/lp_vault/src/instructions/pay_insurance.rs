@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, helpers::assert_engine_authority, state::Pool};
+
+pub fn handler(ctx: Context<PayInsurance>, amount: u64) -> Result<()> {
+    assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    // Staker funds only leave the vault as first loss on bad debt, which
+    // `apply_liquidation` charges to them explicitly; never through here.
+    let available = ctx
+        .accounts
+        .pool
+        .unstaked_insurance(ctx.accounts.insurance_vault.amount);
+    require!(amount <= available, ErrorCode::InsufficientInsuranceVault);
+
+    let insurance_auth_bump = ctx.bumps.insurance_auth;
+    let insurance_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"insurance-auth",
+        insurance_auth_key.as_ref(),
+        &[insurance_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.insurance_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.insurance_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )
+}
+
+#[derive(Accounts)]
+pub struct PayInsurance<'info> {
+    pub engine_authority: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: insurance auth PDA.
+    #[account(seeds = [b"insurance-auth", pool.key().as_ref()], bump)]
+    pub insurance_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
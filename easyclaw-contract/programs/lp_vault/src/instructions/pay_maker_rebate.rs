@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, helpers::assert_engine_authority, state::Pool};
+
+pub fn handler(ctx: Context<PayMakerRebate>, amount: u64) -> Result<()> {
+    assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.pool.maker_rebate_headroom(),
+        ErrorCode::MakerRebateExceedsLpFees
+    );
+
+    let liquidity_auth_bump = ctx.bumps.liquidity_auth;
+    let liquidity_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"liquidity-auth",
+        liquidity_auth_key.as_ref(),
+        &[liquidity_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.liquidity_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.liquidity_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = pool
+        .accounted_liquidity
+        .checked_sub(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.total_maker_rebates = pool
+        .total_maker_rebates
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayMakerRebate<'info> {
+    pub engine_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    events::LpWithdrawClaimed,
+    state::{LpPosition, Pool, WithdrawRequest},
+};
+
+// Permissionless crank: pays the request at the head of the withdrawal queue
+// at the current NAV and closes it to its owner. Fails while the buffer still
+// blocks it. A request now worth less than its `min_amount_out` is dropped
+// from the queue instead, so it cannot stall the requests behind it; the owner
+// can claim it again later.
+pub fn handler(ctx: Context<ProcessWithdrawQueue>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+    ctx.accounts.pool.require_fresh_nav(now)?;
+    require_keys_eq!(
+        ctx.accounts.withdraw_request.pool,
+        ctx.accounts.pool.key(),
+        ErrorCode::InvalidWithdrawRequest
+    );
+    require!(
+        ctx.accounts.withdraw_request.queued,
+        ErrorCode::InvalidWithdrawRequest
+    );
+
+    ctx.accounts.pool.accrue_treasury_fees(now)?;
+    ctx.accounts
+        .pool
+        .pop_withdrawal(ctx.accounts.withdraw_request.queue_position)?;
+
+    let share_amount = ctx.accounts.withdraw_request.share_amount;
+    let withdraw_amount = ctx.accounts.pool.shares_to_amount(share_amount)?;
+    if withdraw_amount == 0 || withdraw_amount < ctx.accounts.withdraw_request.min_amount_out {
+        ctx.accounts.withdraw_request.queued = false;
+        return Ok(());
+    }
+    let post_liquidity = ctx
+        .accounts
+        .pool
+        .liquidity_after_withdrawal(withdraw_amount)?;
+
+    let liquidity_auth_bump = ctx.bumps.liquidity_auth;
+    let liquidity_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"liquidity-auth",
+        liquidity_auth_key.as_ref(),
+        &[liquidity_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.liquidity_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: ctx.accounts.liquidity_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        withdraw_amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    let lp = &mut ctx.accounts.lp_position;
+    lp.settle_referral(&mut ctx.accounts.pool)?;
+    lp.pending_shares = lp
+        .pending_shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let pool = &mut ctx.accounts.pool;
+    pool.accounted_liquidity = post_liquidity;
+    pool.cumulative_withdrawals = pool.cumulative_withdrawals.saturating_add(withdraw_amount);
+    pool.total_shares = pool
+        .total_shares
+        .checked_sub(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    emit!(LpWithdrawClaimed {
+        pool: pool.key(),
+        owner: ctx.accounts.withdraw_request.owner,
+        nonce: ctx.accounts.withdraw_request.nonce,
+        share_amount,
+        amount: withdraw_amount,
+        remaining_shares: 0,
+        share_price: pool.share_price()?,
+        accounted_liquidity: pool.accounted_liquidity,
+        timestamp: now,
+    });
+
+    ctx.accounts.withdraw_request.claimed = true;
+    ctx.accounts
+        .withdraw_request
+        .close(ctx.accounts.owner.to_account_info())?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProcessWithdrawQueue<'info> {
+    pub cranker: Signer<'info>,
+    /// CHECK: request owner; receives the payout and the request rent.
+    #[account(mut, address = withdraw_request.owner @ ErrorCode::Unauthorized)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"lp-pos", pool.key().as_ref(), owner.key().as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    #[account(
+        mut,
+        seeds = [b"withdraw-req", pool.key().as_ref(), owner.key().as_ref(), &withdraw_request.nonce.to_le_bytes()],
+        bump = withdraw_request.bump,
+    )]
+    pub withdraw_request: Account<'info, WithdrawRequest>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ ErrorCode::Unauthorized,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
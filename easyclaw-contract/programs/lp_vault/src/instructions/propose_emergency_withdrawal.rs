@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    constants::EMERGENCY_WITHDRAW_DELAY_SECS,
+    error::ErrorCode,
+    events::EmergencyWithdrawalProposed,
+    helpers::require_admin,
+    state::{EmergencyWithdrawal, Pool, PoolVault},
+};
+
+// Break-glass path for catastrophic bugs. The proposal is public on-chain for
+// the whole delay and the destination cannot change afterwards.
+pub fn handler(
+    ctx: Context<ProposeEmergencyWithdrawal>,
+    vault: PoolVault,
+    amount: u64,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    let eta = now
+        .checked_add(EMERGENCY_WITHDRAW_DELAY_SECS)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let withdrawal = &mut ctx.accounts.emergency_withdrawal;
+    withdrawal.pool = ctx.accounts.pool.key();
+    withdrawal.vault = vault;
+    withdrawal.amount = amount;
+    withdrawal.destination = ctx.accounts.destination.key();
+    withdrawal.proposed_at = now;
+    withdrawal.eta = eta;
+    withdrawal.bump = ctx.bumps.emergency_withdrawal;
+
+    emit!(EmergencyWithdrawalProposed {
+        pool: withdrawal.pool,
+        vault,
+        amount,
+        destination: withdrawal.destination,
+        eta,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeEmergencyWithdrawal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"emergency-withdraw", pool.key().as_ref()],
+        bump,
+        space = 8 + EmergencyWithdrawal::INIT_SPACE,
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    #[account(
+        constraint = destination.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    events::InsuranceRebalanced,
+    helpers::{mul_bps, require_admin},
+    state::{Pool, RebalanceDirection},
+};
+
+// Moves funds between the insurance and liquidity vaults. The insurance vault
+// must end within [min, max]_insurance_oi_bps of the open interest reported at
+// the last NAV mark, and funds leaving liquidity respect the usual buffer.
+// Staked insurance is never moved into liquidity.
+pub fn handler(
+    ctx: Context<RebalanceInsurance>,
+    amount: u64,
+    direction: RebalanceDirection,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let pool_key = ctx.accounts.pool.key();
+    let open_interest = ctx.accounts.pool.open_interest;
+    let insurance_balance = ctx.accounts.insurance_vault.amount;
+    let insurance_after = match direction {
+        RebalanceDirection::InsuranceToLiquidity => {
+            let insurance_after = insurance_balance
+                .checked_sub(amount)
+                .ok_or_else(|| error!(ErrorCode::InsufficientInsuranceVault))?;
+            // Staker funds never leave the insurance vault this way.
+            require!(
+                insurance_after
+                    >= mul_bps(open_interest, ctx.accounts.pool.min_insurance_oi_bps as u64)?
+                        .max(ctx.accounts.pool.insurance_staked),
+                ErrorCode::InsuranceBelowTarget
+            );
+
+            let bump = ctx.bumps.insurance_auth;
+            let signer_seed_group: &[&[u8]] = &[b"insurance-auth", pool_key.as_ref(), &[bump]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.insurance_vault.to_account_info(),
+                        mint: ctx.accounts.usdc_mint.to_account_info(),
+                        to: ctx.accounts.liquidity_vault.to_account_info(),
+                        authority: ctx.accounts.insurance_auth.to_account_info(),
+                    },
+                    &[signer_seed_group],
+                ),
+                amount,
+                ctx.accounts.usdc_mint.decimals,
+            )?;
+
+            let pool = &mut ctx.accounts.pool;
+            pool.accounted_liquidity = pool
+                .accounted_liquidity
+                .checked_add(amount)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            insurance_after
+        }
+        RebalanceDirection::LiquidityToInsurance => {
+            let insurance_after = insurance_balance
+                .checked_add(amount)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            require!(
+                insurance_after
+                    <= mul_bps(open_interest, ctx.accounts.pool.max_insurance_oi_bps as u64)?,
+                ErrorCode::InsuranceAboveTarget
+            );
+            let post_liquidity = ctx.accounts.pool.liquidity_after_withdrawal(amount)?;
+
+            let bump = ctx.bumps.liquidity_auth;
+            let signer_seed_group: &[&[u8]] = &[b"liquidity-auth", pool_key.as_ref(), &[bump]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.liquidity_vault.to_account_info(),
+                        mint: ctx.accounts.usdc_mint.to_account_info(),
+                        to: ctx.accounts.insurance_vault.to_account_info(),
+                        authority: ctx.accounts.liquidity_auth.to_account_info(),
+                    },
+                    &[signer_seed_group],
+                ),
+                amount,
+                ctx.accounts.usdc_mint.decimals,
+            )?;
+
+            ctx.accounts.pool.accounted_liquidity = post_liquidity;
+            insurance_after
+        }
+    };
+
+    emit!(InsuranceRebalanced {
+        pool: pool_key,
+        amount,
+        to_liquidity: direction == RebalanceDirection::InsuranceToLiquidity,
+        insurance_balance: insurance_after,
+        accounted_liquidity: ctx.accounts.pool.accounted_liquidity,
+        open_interest,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RebalanceInsurance<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    /// CHECK: insurance auth PDA.
+    #[account(seeds = [b"insurance-auth", pool.key().as_ref()], bump)]
+    pub insurance_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::FEE_SNAPSHOT_INTERVAL_SECS,
+    error::ErrorCode,
+    events::FeeSnapshotRecorded,
+    state::{FeeSnapshot, Pool},
+};
+
+// Permissionless crank: at most once per interval, writes the next snapshot
+// after bringing treasury fees up to date.
+pub fn handler(ctx: Context<RecordFeeSnapshot>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    require!(
+        pool.fee_snapshot_count == 0
+            || now
+                >= pool
+                    .last_fee_snapshot_at
+                    .saturating_add(FEE_SNAPSHOT_INTERVAL_SECS),
+        ErrorCode::SnapshotTooEarly
+    );
+    pool.accrue_treasury_fees(now)?;
+
+    let snapshot = &mut ctx.accounts.fee_snapshot;
+    snapshot.pool = pool.key();
+    snapshot.index = pool.fee_snapshot_count;
+    snapshot.timestamp = now;
+    snapshot.total_shares = pool.total_shares;
+    snapshot.share_price = pool.share_price()?;
+    snapshot.cumulative_lp_fees = pool.cumulative_lp_fees;
+    snapshot.total_trading_fees = pool.total_trading_fees;
+    snapshot.bump = ctx.bumps.fee_snapshot;
+
+    pool.fee_snapshot_count = pool
+        .fee_snapshot_count
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.last_fee_snapshot_at = now;
+
+    emit!(FeeSnapshotRecorded {
+        pool: pool.key(),
+        index: snapshot.index,
+        total_shares: snapshot.total_shares,
+        cumulative_lp_fees: snapshot.cumulative_lp_fees,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordFeeSnapshot<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"fee-snapshot", pool.key().as_ref(), &pool.fee_snapshot_count.to_le_bytes()],
+        bump,
+        space = 8 + FeeSnapshot::INIT_SPACE,
+    )]
+    pub fee_snapshot: Account<'info, FeeSnapshot>,
+    pub system_program: Program<'info, System>,
+}
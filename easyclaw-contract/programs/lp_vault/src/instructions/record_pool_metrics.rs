@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::POOL_METRICS_INTERVAL_SECS,
+    error::ErrorCode,
+    events::PoolMetricsRecorded,
+    state::{Pool, PoolMetrics},
+};
+
+// Permissionless crank: at most once per interval, brings treasury fees up to
+// date, copies the pool's cumulative counters and samples the share price.
+pub fn handler(ctx: Context<RecordPoolMetrics>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(latest) = ctx.accounts.pool_metrics.latest_sample() {
+        require!(
+            now >= latest.timestamp.saturating_add(POOL_METRICS_INTERVAL_SECS),
+            ErrorCode::MetricsSampleTooEarly
+        );
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    pool.accrue_treasury_fees(now)?;
+    let share_price = pool.share_price()?;
+
+    let metrics = &mut ctx.accounts.pool_metrics;
+    metrics.cumulative_deposits = pool.cumulative_deposits;
+    metrics.cumulative_withdrawals = pool.cumulative_withdrawals;
+    metrics.cumulative_lp_fees = pool.cumulative_lp_fees;
+    metrics.realized_trader_pnl = pool.cumulative_trader_pnl;
+    metrics.record_sample(share_price, now)?;
+
+    emit!(PoolMetricsRecorded {
+        pool: pool.key(),
+        share_price,
+        trailing_apy_bps: metrics.trailing_apy_bps()?,
+        max_drawdown_bps: metrics.max_drawdown_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordPoolMetrics<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"pool-metrics", pool.key().as_ref()],
+        bump = pool_metrics.bump,
+    )]
+    pub pool_metrics: Account<'info, PoolMetrics>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{LpAllowlistEntry, Pool},
+};
+
+pub fn handler(ctx: Context<RemoveLpAllowlistEntry>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)
+}
+
+#[derive(Accounts)]
+pub struct RemoveLpAllowlistEntry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"lp-allow", pool.key().as_ref(), allowlist_entry.user.as_ref()],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, LpAllowlistEntry>,
+}
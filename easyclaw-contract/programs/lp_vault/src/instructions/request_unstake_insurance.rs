@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    state::{InsuranceStake, Pool},
+};
+
+// Queues shares to leave after the pool cooldown. A new request adds to any
+// pending shares and restarts the cooldown, so stakers cannot step out ahead of
+// a loss they can see coming.
+pub fn handler(ctx: Context<RequestUnstakeInsurance>, share_amount: u128) -> Result<()> {
+    require!(share_amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
+
+    let stake = &mut ctx.accounts.insurance_stake;
+    require_keys_eq!(
+        stake.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(stake.shares >= share_amount, ErrorCode::InsufficientShares);
+
+    stake.shares -= share_amount;
+    stake.pending_shares = stake
+        .pending_shares
+        .checked_add(share_amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    stake.unstake_requested_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstakeInsurance<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"insurance-stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = insurance_stake.bump,
+    )]
+    pub insurance_stake: Account<'info, InsuranceStake>,
+}
@@ -2,11 +2,16 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::ErrorCode,
+    events::LpWithdrawRequested,
     state::{LpPosition, Pool, WithdrawRequest},
 };
 
 pub fn handler(ctx: Context<RequestWithdrawLp>, share_amount: u128) -> Result<()> {
     require!(share_amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.pool.withdrawals_paused,
+        ErrorCode::WithdrawalsPaused
+    );
 
     let lp = &mut ctx.accounts.lp_position;
     require_keys_eq!(lp.owner, ctx.accounts.user.key(), ErrorCode::Unauthorized);
@@ -34,12 +39,24 @@ pub fn handler(ctx: Context<RequestWithdrawLp>, share_amount: u128) -> Result<()
     req.requested_at = Clock::get()?.unix_timestamp;
     req.claimed = false;
     req.bump = ctx.bumps.withdraw_request;
+    req.queued = false;
+    req.queue_position = 0;
+    req.min_amount_out = 0;
 
     lp.withdraw_nonce = lp
         .withdraw_nonce
         .checked_add(1)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
+    emit!(LpWithdrawRequested {
+        pool: req.pool,
+        owner: req.owner,
+        nonce: req.nonce,
+        share_amount,
+        share_price: ctx.accounts.pool.share_price()?,
+        timestamp: req.requested_at,
+    });
+
     Ok(())
 }
 
@@ -49,7 +66,7 @@ pub struct RequestWithdrawLp<'info> {
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"pool", pool.usdc_mint.as_ref()],
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
         bump = pool.bump,
     )]
     pub pool: Account<'info, Pool>,
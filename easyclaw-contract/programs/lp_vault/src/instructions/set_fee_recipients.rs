@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{FeeDistribution, FeeRecipient, Pool},
+};
+
+pub fn handler(ctx: Context<SetFeeRecipients>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    FeeDistribution::validate_recipients(&recipients)?;
+
+    ctx.accounts.fee_distribution.recipients = recipients;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeRecipients<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"fee-distribution", pool.key().as_ref()],
+        bump = fee_distribution.bump,
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    error::ErrorCode,
+    state::{KeeperRebate, Pool},
+};
+
+// Points rebate claims at `payout_account`, which may be owned by another
+// wallet so the keeper's hot key never holds earnings. Omitting it pays the
+// keeper's own token account again.
+pub fn handler(ctx: Context<SetKeeperPayoutAccount>) -> Result<()> {
+    ctx.accounts.keeper_rebate.payout_account = ctx
+        .accounts
+        .payout_account
+        .as_ref()
+        .map(|account| account.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetKeeperPayoutAccount<'info> {
+    pub keeper: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"keeper-rebate", pool.key().as_ref(), keeper.key().as_ref()],
+        bump = keeper_rebate.bump,
+    )]
+    pub keeper_rebate: Account<'info, KeeperRebate>,
+    #[account(constraint = payout_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount)]
+    pub payout_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
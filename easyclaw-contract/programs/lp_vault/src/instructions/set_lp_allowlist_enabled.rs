@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{helpers::require_admin, state::Pool};
+
+pub fn handler(ctx: Context<SetLpAllowlistEnabled>, enabled: bool) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    ctx.accounts.pool.allowlist_enabled = enabled;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLpAllowlistEnabled<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{LpPosition, Pool};
+
+// Lets a custodian or bot finish the two-step withdrawal; `None` revokes it.
+pub fn handler(ctx: Context<SetLpDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.lp_position.delegate = delegate;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLpDelegate<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"lp-pos", pool.key().as_ref(), user.key().as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{MarketInsurance, Pool},
+};
+
+// `None` clears the override so the market pays the pool-wide rebate again.
+pub fn handler(
+    ctx: Context<SetMarketExecutionRebate>,
+    _market_id: u64,
+    execution_rebate_usdc: Option<u64>,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    ctx.accounts.market_insurance.execution_rebate_override = execution_rebate_usdc;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SetMarketExecutionRebate<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"market-insurance", pool.key().as_ref(), &market_id.to_le_bytes()],
+        bump = market_insurance.bump,
+    )]
+    pub market_insurance: Account<'info, MarketInsurance>,
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{helpers::require_admin, state::Pool};
+
+pub fn handler(
+    ctx: Context<SetPoolPause>,
+    deposits_paused: bool,
+    withdrawals_paused: bool,
+) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.deposits_paused = deposits_paused;
+    pool.withdrawals_paused = withdrawals_paused;
+    // Resuming after a circuit breaker trip measures PnL from here on.
+    if !withdrawals_paused {
+        pool.reset_pnl_window(Clock::get()?.unix_timestamp);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoolPause<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+}
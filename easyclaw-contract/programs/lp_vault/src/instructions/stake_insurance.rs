@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    error::ErrorCode,
+    state::{InsuranceStake, Pool},
+};
+
+// Stake shares are priced against `insurance_staked`, which only moves through
+// insurance income and bad debt, so direct vault transfers cannot skew it.
+pub fn handler(ctx: Context<StakeInsurance>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.pool.deposits_paused,
+        ErrorCode::DepositsPaused
+    );
+    require_keys_eq!(
+        ctx.accounts.insurance_stake.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let vault_before = ctx.accounts.insurance_vault.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+    // Credit only what arrived, net of any Token-2022 transfer fee.
+    ctx.accounts.insurance_vault.reload()?;
+    let amount = ctx
+        .accounts
+        .insurance_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let minted_shares = ctx.accounts.pool.insurance_stake_shares_for(amount)?;
+    require!(minted_shares > 0, ErrorCode::InvalidAmount);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.insurance_staked = pool
+        .insurance_staked
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    pool.insurance_stake_shares = pool
+        .insurance_stake_shares
+        .checked_add(minted_shares)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let stake = &mut ctx.accounts.insurance_stake;
+    stake.shares = stake
+        .shares
+        .checked_add(minted_shares)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct StakeInsurance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"insurance-stake", pool.key().as_ref(), user.key().as_ref()],
+        bump = insurance_stake.bump,
+    )]
+    pub insurance_stake: Account<'info, InsuranceStake>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::SurplusSwept, state::Pool};
+
+// Moves liquidity vault balance above accounted liquidity into the insurance
+// vault. Anyone may call it, so a donation can never be held back to inflate
+// the share price for a later deposit.
+pub fn handler(ctx: Context<SweepSurplus>) -> Result<()> {
+    let surplus = ctx
+        .accounts
+        .liquidity_vault
+        .amount
+        .checked_sub(ctx.accounts.pool.accounted_liquidity)
+        .ok_or_else(|| error!(ErrorCode::InvalidPoolState))?;
+    require!(surplus > 0, ErrorCode::NoSurplus);
+
+    let pool_key = ctx.accounts.pool.key();
+    let bump = ctx.bumps.liquidity_auth;
+    let signer_seed_group: &[&[u8]] = &[b"liquidity-auth", pool_key.as_ref(), &[bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+                authority: ctx.accounts.liquidity_auth.to_account_info(),
+            },
+            &[signer_seed_group],
+        ),
+        surplus,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    emit!(SurplusSwept {
+        pool: pool_key,
+        caller: ctx.accounts.caller.key(),
+        amount: surplus,
+        accounted_liquidity: ctx.accounts.pool.accounted_liquidity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: liquidity auth PDA.
+    #[account(seeds = [b"liquidity-auth", pool.key().as_ref()], bump)]
+    pub liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = pool.insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{error::ErrorCode, helpers::require_admin, state::Pool};
+
+// Absorbs tokens sent straight to the liquidity vault into accounted liquidity,
+// raising the share price for existing LPs.
+pub fn handler(ctx: Context<SyncDonations>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+
+    let vault_balance = ctx.accounts.liquidity_vault.amount;
+    let pool = &mut ctx.accounts.pool;
+    require!(
+        vault_balance >= pool.accounted_liquidity,
+        ErrorCode::InvalidPoolState
+    );
+    pool.accounted_liquidity = vault_balance;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncDonations<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(address = pool.liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub liquidity_vault: InterfaceAccount<'info, TokenAccount>,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::BPS_DENOM,
+    error::ErrorCode,
+    events::{FeeSplitSyncQueued, FeeSplitSynced},
+    helpers::assert_engine_authority,
+    state::Pool,
+};
+
+// Called by order_engine with the registry's fee split. A new split is only
+// queued; it replaces the pool's base bps once the pool's own config delay
+// has passed, so LPs get the same notice as for a `configure_pool` change.
+pub fn handler(
+    ctx: Context<SyncFeeSplit>,
+    lp_fee_bps: u16,
+    insurance_fee_bps: u16,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    assert_engine_authority(&ctx.accounts.pool, &ctx.accounts.engine_authority)?;
+    let sum = lp_fee_bps
+        .checked_add(insurance_fee_bps)
+        .and_then(|x| x.checked_add(protocol_fee_bps))
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(sum == BPS_DENOM as u16, ErrorCode::InvalidBps);
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    let split = (lp_fee_bps, insurance_fee_bps, protocol_fee_bps);
+
+    if pool.base_fee_split() == split {
+        pool.pending_fee_split_eta = 0;
+        return Ok(());
+    }
+
+    if pool.pending_fee_split_eta == 0 || pool.pending_fee_split() != split {
+        pool.pending_lp_fee_bps = lp_fee_bps;
+        pool.pending_insurance_fee_bps = insurance_fee_bps;
+        pool.pending_protocol_fee_bps = protocol_fee_bps;
+        pool.pending_fee_split_eta = now
+            .checked_add(pool.config_delay_secs)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        emit!(FeeSplitSyncQueued {
+            pool: pool.key(),
+            lp_fee_bps,
+            insurance_fee_bps,
+            protocol_fee_bps,
+            eta: pool.pending_fee_split_eta,
+        });
+        return Ok(());
+    }
+
+    require!(
+        now >= pool.pending_fee_split_eta,
+        ErrorCode::ConfigTimelockActive
+    );
+    pool.lp_fee_bps = lp_fee_bps;
+    pool.insurance_fee_bps = insurance_fee_bps;
+    pool.protocol_fee_bps = protocol_fee_bps;
+    pool.pending_fee_split_eta = 0;
+
+    emit!(FeeSplitSynced {
+        pool: pool.key(),
+        lp_fee_bps,
+        insurance_fee_bps,
+        protocol_fee_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncFeeSplit<'info> {
+    pub engine_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+}
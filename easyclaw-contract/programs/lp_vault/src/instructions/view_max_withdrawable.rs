@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    state::{LpPosition, MaxWithdrawableView, Pool, WithdrawRequest},
+};
+
+// Read-only, like `view_share_price`. The LP's open withdraw requests are
+// passed as remaining accounts; queued ones are left to the crank.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ViewMaxWithdrawable<'info>>,
+) -> Result<MaxWithdrawableView> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut pool = ctx.accounts.pool.clone().into_inner();
+    pool.accrue_treasury_fees(now)?;
+
+    let mut matured_shares = 0u128;
+    let mut cooling_shares = 0u128;
+    let mut next_claimable_at = 0i64;
+    for info in ctx.remaining_accounts {
+        let req = Account::<WithdrawRequest>::try_from(info)?;
+        require_keys_eq!(
+            req.owner,
+            ctx.accounts.lp_position.owner,
+            ErrorCode::InvalidWithdrawRequest
+        );
+        require_keys_eq!(
+            req.pool,
+            ctx.accounts.pool.key(),
+            ErrorCode::InvalidWithdrawRequest
+        );
+        if req.claimed || req.queued {
+            continue;
+        }
+        let claimable_at = req.requested_at + pool.cooldown_secs;
+        if now >= claimable_at {
+            matured_shares = matured_shares
+                .checked_add(req.share_amount)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        } else {
+            cooling_shares = cooling_shares
+                .checked_add(req.share_amount)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            if next_claimable_at == 0 || claimable_at < next_claimable_at {
+                next_claimable_at = claimable_at;
+            }
+        }
+    }
+
+    let matured_value = if pool.total_shares == 0 {
+        0
+    } else {
+        pool.shares_to_amount(matured_shares)?
+    };
+    let withdrawable_liquidity = pool.withdrawable_liquidity();
+
+    Ok(MaxWithdrawableView {
+        matured_shares,
+        matured_value,
+        cooling_shares,
+        next_claimable_at,
+        withdrawable_liquidity,
+        claimable_now: matured_value.min(withdrawable_liquidity),
+    })
+}
+
+#[derive(Accounts)]
+pub struct ViewMaxWithdrawable<'info> {
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        seeds = [b"lp-pos", pool.key().as_ref(), lp_position.owner.as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    state::{LpPosition, Pool, SharePriceView},
+};
+
+// Read-only: treasury fees are accrued on a copy of the pool, as claims would
+// do first, and the result goes back through return data.
+pub fn handler(ctx: Context<ViewSharePrice>) -> Result<SharePriceView> {
+    let mut pool = ctx.accounts.pool.clone().into_inner();
+    pool.accrue_treasury_fees(Clock::get()?.unix_timestamp)?;
+
+    let lp = &ctx.accounts.lp_position;
+    let position_shares = lp
+        .shares
+        .checked_add(lp.pending_shares)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let redeemable_value = if pool.total_shares == 0 {
+        0
+    } else {
+        pool.shares_to_amount(position_shares)?
+    };
+
+    Ok(SharePriceView {
+        share_price: pool.share_price()?,
+        net_asset_value: pool.net_asset_value(),
+        total_shares: pool.total_shares,
+        shares: lp.shares,
+        pending_shares: lp.pending_shares,
+        redeemable_value,
+    })
+}
+
+#[derive(Accounts)]
+pub struct ViewSharePrice<'info> {
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        seeds = [b"lp-pos", pool.key().as_ref(), lp_position.owner.as_ref()],
+        bump = lp_position.bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+}
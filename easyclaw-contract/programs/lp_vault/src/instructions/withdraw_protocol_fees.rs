@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{error::ErrorCode, events::ProtocolFeesWithdrawn, helpers::require_admin, state::Pool};
+
+// Moves protocol fees out of the protocol fee vault. Unclaimed keeper rebates
+// and referral rewards are paid from the same vault, so they stay reserved.
+pub fn handler(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.pool)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let available = ctx
+        .accounts
+        .protocol_fee_vault
+        .amount
+        .saturating_sub(ctx.accounts.pool.reserved_protocol_fees());
+    require!(amount <= available, ErrorCode::InsufficientProtocolFeeVault);
+
+    let protocol_fee_auth_bump = ctx.bumps.protocol_fee_auth;
+    let protocol_fee_auth_key = ctx.accounts.pool.key();
+    let signer_seed_group: &[&[u8]] = &[
+        b"protocol-fee-auth",
+        protocol_fee_auth_key.as_ref(),
+        &[protocol_fee_auth_bump],
+    ];
+    let signer_seeds = &[signer_seed_group];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.protocol_fee_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.protocol_fee_auth.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    emit!(ProtocolFeesWithdrawn {
+        pool: ctx.accounts.pool.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        reserved_for_rebates: ctx.accounts.pool.reserved_protocol_fees(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool", pool.usdc_mint.as_ref(), &pool.pool_index.to_le_bytes()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: protocol fee authority PDA.
+    #[account(seeds = [b"protocol-fee-auth", pool.key().as_ref()], bump)]
+    pub protocol_fee_auth: UncheckedAccount<'info>,
+    #[account(mut, address = pool.protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub protocol_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == pool.usdc_mint @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = pool.usdc_mint @ ErrorCode::UsdcMintMismatch)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
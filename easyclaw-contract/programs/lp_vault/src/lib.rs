@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod helpers;
 pub mod instructions;
 pub mod state;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -20,35 +22,184 @@ pub mod lp_vault {
     pub fn initialize_pool(
         ctx: Context<InitializePool>,
         cooldown_secs: i64,
-        min_liquidity_buffer_bps: u16,
+        liquidity_buffer_oi_bps: u16,
+        pool_index: u16,
     ) -> Result<()> {
-        instructions::initialize_pool::handler(ctx, cooldown_secs, min_liquidity_buffer_bps)
+        instructions::initialize_pool::handler(
+            ctx,
+            cooldown_secs,
+            liquidity_buffer_oi_bps,
+            pool_index,
+        )
     }
 
     pub fn configure_pool(ctx: Context<ConfigurePool>, params: PoolConfigParams) -> Result<()> {
         instructions::configure_pool::handler(ctx, params)
     }
 
-    pub fn create_lp_position(ctx: Context<CreateLpPosition>) -> Result<()> {
-        instructions::create_lp_position::handler(ctx)
+    pub fn execute_pool_config(ctx: Context<ExecutePoolConfig>) -> Result<()> {
+        instructions::execute_pool_config::handler(ctx)
+    }
+
+    pub fn cancel_pool_config(ctx: Context<CancelPoolConfig>) -> Result<()> {
+        instructions::cancel_pool_config::handler(ctx)
+    }
+
+    pub fn set_pool_pause(
+        ctx: Context<SetPoolPause>,
+        deposits_paused: bool,
+        withdrawals_paused: bool,
+    ) -> Result<()> {
+        instructions::set_pool_pause::handler(ctx, deposits_paused, withdrawals_paused)
+    }
+
+    pub fn set_lp_allowlist_enabled(
+        ctx: Context<SetLpAllowlistEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_lp_allowlist_enabled::handler(ctx, enabled)
+    }
+
+    pub fn add_lp_allowlist_entry(ctx: Context<AddLpAllowlistEntry>, user: Pubkey) -> Result<()> {
+        instructions::add_lp_allowlist_entry::handler(ctx, user)
+    }
+
+    pub fn remove_lp_allowlist_entry(ctx: Context<RemoveLpAllowlistEntry>) -> Result<()> {
+        instructions::remove_lp_allowlist_entry::handler(ctx)
+    }
+
+    pub fn propose_emergency_withdrawal(
+        ctx: Context<ProposeEmergencyWithdrawal>,
+        vault: PoolVault,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::propose_emergency_withdrawal::handler(ctx, vault, amount)
+    }
+
+    pub fn cancel_emergency_withdrawal(ctx: Context<CancelEmergencyWithdrawal>) -> Result<()> {
+        instructions::cancel_emergency_withdrawal::handler(ctx)
+    }
+
+    pub fn execute_emergency_withdrawal(ctx: Context<ExecuteEmergencyWithdrawal>) -> Result<()> {
+        instructions::execute_emergency_withdrawal::handler(ctx)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::handler(ctx)
+    }
+
+    pub fn migrate_lp_position(ctx: Context<MigrateLpPosition>) -> Result<()> {
+        instructions::migrate_lp_position::handler(ctx)
+    }
+
+    pub fn migrate_keeper_rebate(ctx: Context<MigrateKeeperRebate>) -> Result<()> {
+        instructions::migrate_keeper_rebate::handler(ctx)
+    }
+
+    pub fn migrate_withdraw_request(ctx: Context<MigrateWithdrawRequest>) -> Result<()> {
+        instructions::migrate_withdraw_request::handler(ctx)
+    }
+
+    pub fn create_lp_position(
+        ctx: Context<CreateLpPosition>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::create_lp_position::handler(ctx, referrer)
+    }
+
+    pub fn claim_lp_referral_reward(ctx: Context<ClaimLpReferralReward>) -> Result<()> {
+        instructions::claim_lp_referral_reward::handler(ctx)
+    }
+
+    pub fn create_insurance_stake(ctx: Context<CreateInsuranceStake>) -> Result<()> {
+        instructions::create_insurance_stake::handler(ctx)
+    }
+
+    pub fn stake_insurance(ctx: Context<StakeInsurance>, amount: u64) -> Result<()> {
+        instructions::stake_insurance::handler(ctx, amount)
+    }
+
+    pub fn request_unstake_insurance(
+        ctx: Context<RequestUnstakeInsurance>,
+        share_amount: u128,
+    ) -> Result<()> {
+        instructions::request_unstake_insurance::handler(ctx, share_amount)
+    }
+
+    pub fn claim_unstake_insurance(ctx: Context<ClaimUnstakeInsurance>) -> Result<()> {
+        instructions::claim_unstake_insurance::handler(ctx)
     }
 
     pub fn init_keeper_rebate(ctx: Context<InitKeeperRebate>) -> Result<()> {
         instructions::init_keeper_rebate::handler(ctx)
     }
 
-    pub fn deposit_lp(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
-        instructions::deposit_lp::handler(ctx, amount)
+    pub fn init_market_insurance(ctx: Context<InitMarketInsurance>, market_id: u64) -> Result<()> {
+        instructions::init_market_insurance::handler(ctx, market_id)
+    }
+
+    pub fn init_market_vault_stats(
+        ctx: Context<InitMarketVaultStats>,
+        market_id: u64,
+    ) -> Result<()> {
+        instructions::init_market_vault_stats::handler(ctx, market_id)
+    }
+
+    pub fn set_market_execution_rebate(
+        ctx: Context<SetMarketExecutionRebate>,
+        market_id: u64,
+        execution_rebate_usdc: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_market_execution_rebate::handler(ctx, market_id, execution_rebate_usdc)
+    }
+
+    pub fn init_pool_metrics(ctx: Context<InitPoolMetrics>) -> Result<()> {
+        instructions::init_pool_metrics::handler(ctx)
+    }
+
+    pub fn record_pool_metrics(ctx: Context<RecordPoolMetrics>) -> Result<()> {
+        instructions::record_pool_metrics::handler(ctx)
+    }
+
+    pub fn record_fee_snapshot(ctx: Context<RecordFeeSnapshot>) -> Result<()> {
+        instructions::record_fee_snapshot::handler(ctx)
+    }
+
+    pub fn deposit_lp(ctx: Context<DepositLp>, amount: u64, min_shares_out: u128) -> Result<()> {
+        instructions::deposit_lp::handler(ctx, amount, min_shares_out)
     }
 
     pub fn request_withdraw_lp(ctx: Context<RequestWithdrawLp>, share_amount: u128) -> Result<()> {
         instructions::request_withdraw_lp::handler(ctx, share_amount)
     }
 
-    pub fn claim_withdraw_lp(ctx: Context<ClaimWithdrawLp>) -> Result<()> {
-        instructions::claim_withdraw_lp::handler(ctx)
+    pub fn claim_withdraw_lp(
+        ctx: Context<ClaimWithdrawLp>,
+        min_amount_out: u64,
+        allow_partial: bool,
+    ) -> Result<()> {
+        instructions::claim_withdraw_lp::handler(ctx, min_amount_out, allow_partial)
+    }
+
+    pub fn process_withdraw_queue(ctx: Context<ProcessWithdrawQueue>) -> Result<()> {
+        instructions::process_withdraw_queue::handler(ctx)
+    }
+
+    pub fn view_share_price(ctx: Context<ViewSharePrice>) -> Result<SharePriceView> {
+        instructions::view_share_price::handler(ctx)
+    }
+
+    pub fn view_max_withdrawable<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ViewMaxWithdrawable<'info>>,
+    ) -> Result<MaxWithdrawableView> {
+        instructions::view_max_withdrawable::handler(ctx)
+    }
+
+    pub fn instant_withdraw_lp(ctx: Context<InstantWithdrawLp>, share_amount: u128) -> Result<()> {
+        instructions::instant_withdraw_lp::handler(ctx, share_amount)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn apply_trade_fill(
         ctx: Context<ApplyTradeFill>,
         market_id: u64,
@@ -57,9 +208,10 @@ pub mod lp_vault {
         notional: u64,
         fee: u64,
         pnl_delta: i64,
+        keeper_tip: u64,
     ) -> Result<()> {
         instructions::apply_trade_fill::handler(
-            ctx, market_id, user, order_id, notional, fee, pnl_delta,
+            ctx, market_id, user, order_id, notional, fee, pnl_delta, keeper_tip,
         )
     }
 
@@ -69,11 +221,547 @@ pub mod lp_vault {
         user: Pubkey,
         penalty: u64,
         bad_debt: u64,
+        pnl_delta: i64,
+    ) -> Result<()> {
+        instructions::apply_liquidation::handler(ctx, market_id, user, penalty, bad_debt, pnl_delta)
+    }
+
+    pub fn pay_insurance(ctx: Context<PayInsurance>, amount: u64) -> Result<()> {
+        instructions::pay_insurance::handler(ctx, amount)
+    }
+
+    pub fn pay_maker_rebate(ctx: Context<PayMakerRebate>, amount: u64) -> Result<()> {
+        instructions::pay_maker_rebate::handler(ctx, amount)
+    }
+
+    pub fn mark_nav(
+        ctx: Context<MarkNav>,
+        unrealized_trader_pnl: i64,
+        open_interest: u64,
+    ) -> Result<()> {
+        instructions::mark_nav::handler(ctx, unrealized_trader_pnl, open_interest)
+    }
+
+    pub fn sync_fee_split(
+        ctx: Context<SyncFeeSplit>,
+        lp_fee_bps: u16,
+        insurance_fee_bps: u16,
+        protocol_fee_bps: u16,
     ) -> Result<()> {
-        instructions::apply_liquidation::handler(ctx, market_id, user, penalty, bad_debt)
+        instructions::sync_fee_split::handler(ctx, lp_fee_bps, insurance_fee_bps, protocol_fee_bps)
+    }
+
+    pub fn rebalance_insurance(
+        ctx: Context<RebalanceInsurance>,
+        amount: u64,
+        direction: RebalanceDirection,
+    ) -> Result<()> {
+        instructions::rebalance_insurance::handler(ctx, amount, direction)
+    }
+
+    pub fn set_lp_delegate(ctx: Context<SetLpDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+        instructions::set_lp_delegate::handler(ctx, delegate)
     }
 
     pub fn claim_keeper_rebate(ctx: Context<ClaimKeeperRebate>) -> Result<()> {
         instructions::claim_keeper_rebate::handler(ctx)
     }
+
+    pub fn set_keeper_payout_account(ctx: Context<SetKeeperPayoutAccount>) -> Result<()> {
+        instructions::set_keeper_payout_account::handler(ctx)
+    }
+
+    pub fn claim_treasury_fees(ctx: Context<ClaimTreasuryFees>) -> Result<()> {
+        instructions::claim_treasury_fees::handler(ctx)
+    }
+
+    pub fn sync_donations(ctx: Context<SyncDonations>) -> Result<()> {
+        instructions::sync_donations::handler(ctx)
+    }
+
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>) -> Result<()> {
+        instructions::sweep_surplus::handler(ctx)
+    }
+
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees::handler(ctx, amount)
+    }
+
+    pub fn init_fee_distribution(
+        ctx: Context<InitFeeDistribution>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::init_fee_distribution::handler(ctx, recipients)
+    }
+
+    pub fn set_fee_recipients(
+        ctx: Context<SetFeeRecipients>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::set_fee_recipients::handler(ctx, recipients)
+    }
+
+    pub fn distribute_protocol_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeProtocolFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::distribute_protocol_fees::handler(ctx, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::pro_rata_min_amount_out;
+
+    fn account_data<T: AnchorSerialize>(discriminator: &[u8], value: &T, len: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend(borsh::to_vec(value).unwrap());
+        data.resize(len, 0);
+        data
+    }
+
+    fn legacy_pool() -> LegacyPool {
+        let mut legacy = LegacyPool::deserialize(&mut &[0u8; LegacyPool::INIT_SPACE][..]).unwrap();
+        legacy.admin = Pubkey::new_unique();
+        legacy.usdc_mint = Pubkey::new_unique();
+        legacy.pool_index = 3;
+        legacy.total_shares = 5_000_000;
+        legacy.accounted_liquidity = 4_200_000;
+        legacy.insurance_staked = 7_000;
+        legacy.config_delay_secs = 86_400;
+        legacy.withdraw_queue_tail = 9;
+        legacy.withdrawals_paused = true;
+        legacy.bump = 254;
+        legacy
+    }
+
+    fn zeroed_pool() -> Pool {
+        Pool::deserialize(&mut &vec![0u8; Pool::INIT_SPACE][..]).unwrap()
+    }
+
+    fn zeroed_lp_position() -> LpPosition {
+        LpPosition::deserialize(&mut &vec![0u8; LpPosition::INIT_SPACE][..]).unwrap()
+    }
+
+    #[test]
+    fn test_split_fee_base() {
+        let mut pool = zeroed_pool();
+        (
+            pool.lp_fee_bps,
+            pool.insurance_fee_bps,
+            pool.protocol_fee_bps,
+        ) = (7_000, 2_000, 1_000);
+
+        // The protocol share takes the rounding remainder.
+        assert_eq!(
+            pool.split_fee(1_000_001).unwrap(),
+            (700_000, 200_000, 100_001)
+        );
+        assert_eq!(pool.split_fee(0).unwrap(), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_split_fee_follows_curve() {
+        let mut pool = zeroed_pool();
+        (
+            pool.lp_fee_bps,
+            pool.insurance_fee_bps,
+            pool.protocol_fee_bps,
+        ) = (7_000, 2_000, 1_000);
+        pool.fee_curve = FeeSplitCurve {
+            utilization_bps: 5_000,
+            lp_fee_bps: 5_000,
+            insurance_fee_bps: 3_000,
+            protocol_fee_bps: 2_000,
+        };
+
+        pool.marked_utilization_bps = 0;
+        assert_eq!(pool.split_fee(10_000).unwrap(), (7_000, 2_000, 1_000));
+        pool.marked_utilization_bps = 2_500;
+        assert_eq!(pool.split_fee(10_000).unwrap(), (6_000, 2_500, 1_500));
+        // Held at the curve's split beyond its utilization.
+        pool.marked_utilization_bps = 9_000;
+        assert_eq!(pool.split_fee(10_000).unwrap(), (5_000, 3_000, 2_000));
+    }
+
+    #[test]
+    fn test_accrue_lp_fees_per_share() {
+        let mut pool = zeroed_pool();
+        // No shares to credit: nothing accrues.
+        pool.accrue_lp_fees_per_share(10_000).unwrap();
+        assert_eq!(pool.lp_fees_per_share, 0);
+
+        pool.total_shares = 1_000_000;
+        pool.accrue_lp_fees_per_share(10_000).unwrap();
+        assert_eq!(
+            pool.lp_fees_per_share,
+            10_000 * SHARE_PRICE_SCALE / 1_000_000
+        );
+        pool.accrue_lp_fees_per_share(5_000).unwrap();
+        assert_eq!(
+            pool.lp_fees_per_share,
+            15_000 * SHARE_PRICE_SCALE / 1_000_000
+        );
+    }
+
+    #[test]
+    fn test_settle_referral() {
+        let mut pool = zeroed_pool();
+        pool.total_shares = 1_000_000;
+        pool.referral_fee_bps = 1_000;
+
+        let mut referred = zeroed_lp_position();
+        referred.referrer = Some(Pubkey::new_unique());
+        referred.shares = 200_000;
+        referred.pending_shares = 50_000;
+        let mut unreferred = zeroed_lp_position();
+        unreferred.shares = 250_000;
+
+        // 10_000 of LP fees; a quarter of the shares earned 2_500 of it, and
+        // the referrer gets 10% of that.
+        pool.accrue_lp_fees_per_share(10_000).unwrap();
+        referred.settle_referral(&mut pool).unwrap();
+        assert_eq!(referred.referral_accrued, 250);
+        assert_eq!(referred.referral_fee_checkpoint, pool.lp_fees_per_share);
+        assert_eq!(pool.pending_referral_rewards, 250);
+
+        // Settling again without new fees credits nothing.
+        referred.settle_referral(&mut pool).unwrap();
+        assert_eq!(referred.referral_accrued, 250);
+        assert_eq!(pool.pending_referral_rewards, 250);
+
+        // Positions without a referrer only move their checkpoint.
+        unreferred.settle_referral(&mut pool).unwrap();
+        assert_eq!(unreferred.referral_accrued, 0);
+        assert_eq!(unreferred.referral_fee_checkpoint, pool.lp_fees_per_share);
+        assert_eq!(pool.pending_referral_rewards, 250);
+    }
+
+    #[test]
+    fn test_fee_split_sync_waits_for_config_delay() {
+        let mut pool = zeroed_pool();
+        (
+            pool.lp_fee_bps,
+            pool.insurance_fee_bps,
+            pool.protocol_fee_bps,
+        ) = (7_000, 2_000, 1_000);
+        let registry = (6_000, 3_000, 1_000);
+
+        // In step with the registry: nothing to do.
+        assert!(!pool.fee_split_sync_due(pool.base_fee_split(), 100));
+        // A new registry split is due to be queued...
+        assert!(pool.fee_split_sync_due(registry, 100));
+        (
+            pool.pending_lp_fee_bps,
+            pool.pending_insurance_fee_bps,
+            pool.pending_protocol_fee_bps,
+        ) = registry;
+        pool.pending_fee_split_eta = 200;
+        // ...then left alone until its eta...
+        assert!(!pool.fee_split_sync_due(registry, 199));
+        assert!(pool.fee_split_sync_due(registry, 200));
+        // ...unless the registry changes again or reverts.
+        assert!(pool.fee_split_sync_due((5_000, 4_000, 1_000), 150));
+        assert!(pool.fee_split_sync_due(pool.base_fee_split(), 150));
+    }
+
+    #[test]
+    fn test_migrate_legacy_pool() {
+        let mut legacy = legacy_pool();
+        let mut queued = LegacyPoolConfigParams::deserialize(
+            &mut &[0u8; LegacyPoolConfigParams::INIT_SPACE][..],
+        )
+        .unwrap();
+        queued.lp_fee_bps = 7_000;
+        legacy.pending_config = Some(queued);
+        legacy.pending_config_eta = 1_000;
+        let data = account_data(Pool::DISCRIMINATOR, &legacy, LegacyPool::ACCOUNT_LEN);
+
+        let pool = migrated_pool(&data).unwrap();
+        assert_eq!(pool.version, POOL_VERSION);
+        assert_eq!(pool.admin, legacy.admin);
+        assert_eq!(pool.usdc_mint, legacy.usdc_mint);
+        assert_eq!(pool.pool_index, 3);
+        assert_eq!(pool.total_shares, 5_000_000);
+        assert_eq!(pool.accounted_liquidity, 4_200_000);
+        assert_eq!(pool.insurance_staked, 7_000);
+        assert_eq!(pool.config_delay_secs, 86_400);
+        assert_eq!(pool.withdraw_queue_tail, 9);
+        assert!(pool.withdrawals_paused);
+        assert!(!pool.deposits_paused);
+        assert_eq!(pool.bump, 254);
+        assert_eq!(pool.lp_fees_per_share, 0);
+
+        // The migrated account reads back as `Pool` and cannot be migrated again.
+        let mut migrated = Vec::new();
+        pool.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), 8 + Pool::INIT_SPACE);
+        let reread = Pool::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(reread.total_shares, 5_000_000);
+        assert_eq!(reread.bump, 254);
+        assert!(migrated_pool(&migrated).is_err());
+    }
+
+    #[test]
+    fn test_migrate_versioned_pool() {
+        let mut pool = zeroed_pool();
+        pool.version = 2;
+        pool.total_shares = 5_000_000;
+        pool.lp_fees_per_share = 42;
+        pool.bump = 254;
+        let mut data = Vec::new();
+        pool.try_serialize(&mut data).unwrap();
+        // Version 2 pools end before the queued fee split fields.
+        data.truncate(8 + Pool::INIT_SPACE - 14);
+
+        let migrated = migrated_pool(&data).unwrap();
+        assert_eq!(migrated.version, POOL_VERSION);
+        assert_eq!(migrated.total_shares, 5_000_000);
+        assert_eq!(migrated.lp_fees_per_share, 42);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.pending_fee_split_eta, 0);
+    }
+
+    #[test]
+    fn test_market_vault_stats_accumulate_trader_pnl() {
+        let mut stats =
+            MarketVaultStats::deserialize(&mut &vec![0u8; MarketVaultStats::INIT_SPACE][..])
+                .unwrap();
+        stats.record_fill(10_000, 10, 7, 2, 1, 0, 250).unwrap();
+        stats.record_fill(5_000, 5, 3, 1, 1, 0, -400).unwrap();
+        assert_eq!(stats.fill_count, 2);
+        assert_eq!(stats.cumulative_notional, 15_000);
+        assert_eq!(stats.trader_pnl, -150);
+    }
+
+    #[test]
+    fn test_market_vault_stats_liquidation_pnl() {
+        let mut stats =
+            MarketVaultStats::deserialize(&mut &vec![0u8; MarketVaultStats::INIT_SPACE][..])
+                .unwrap();
+        stats.record_fill(10_000, 10, 7, 2, 1, 0, 250).unwrap();
+        stats.record_liquidation(50, 0, -900).unwrap();
+        assert_eq!(stats.liquidation_count, 1);
+        assert_eq!(stats.liquidation_penalties, 50);
+        assert_eq!(stats.trader_pnl, -650);
+    }
+
+    #[test]
+    fn test_require_fresh_nav() {
+        let mut pool = zeroed_pool();
+        // The first deposit does not depend on a mark.
+        assert!(pool.require_fresh_nav(1_000).is_ok());
+        pool.total_shares = 1_000;
+        pool.nav_marked_at = 1_000;
+        assert!(pool.require_fresh_nav(1_000 + MAX_NAV_AGE_SECS).is_ok());
+        assert!(pool.require_fresh_nav(1_001 + MAX_NAV_AGE_SECS).is_err());
+    }
+
+    #[test]
+    fn test_insurance_staking() {
+        let mut pool = zeroed_pool();
+        pool.insurance_staker_fee_bps = 5_000;
+        // No stakers: income stays with the protocol.
+        assert_eq!(pool.credit_insurance_stakers(1_000).unwrap(), 0);
+
+        // The first stake mints 1:1.
+        let shares = pool.insurance_stake_shares_for(10_000).unwrap();
+        assert_eq!(shares, 10_000);
+        pool.insurance_staked = 10_000;
+        pool.insurance_stake_shares = shares;
+
+        // Half of the insurance income accrues to stakers.
+        assert_eq!(pool.credit_insurance_stakers(2_000).unwrap(), 1_000);
+        assert_eq!(pool.insurance_staked, 11_000);
+        assert_eq!(pool.insurance_stake_value(5_000).unwrap(), 5_500);
+        // Later stakers buy in at the higher value.
+        assert_eq!(pool.insurance_stake_shares_for(11_000).unwrap(), 10_000);
+
+        // Stakers take first loss, capped at what they hold.
+        assert_eq!(pool.charge_insurance_stakers(4_000).unwrap(), 4_000);
+        assert_eq!(pool.charge_insurance_stakers(9_000).unwrap(), 7_000);
+        assert_eq!(pool.insurance_staked, 0);
+        assert_eq!(pool.total_staker_losses, 11_000);
+        // Wiped-out shares cannot be bought into.
+        assert!(pool.insurance_stake_shares_for(1_000).is_err());
+    }
+
+    #[test]
+    fn test_pay_insurance_excludes_staked_funds() {
+        let mut pool = zeroed_pool();
+        assert_eq!(pool.unstaked_insurance(50_000), 50_000);
+        pool.insurance_staked = 30_000;
+        assert_eq!(pool.unstaked_insurance(50_000), 20_000);
+        // After first loss the vault can fall below the staked balance.
+        assert_eq!(pool.unstaked_insurance(25_000), 0);
+    }
+
+    #[test]
+    fn test_withdraw_queue() {
+        let mut pool = zeroed_pool();
+        pool.accounted_liquidity = 1_000;
+        pool.total_shares = 1_000;
+        pool.open_interest = 4_000;
+        pool.liquidity_buffer_oi_bps = 2_000;
+        // 800 of the 1_000 must stay to back open interest.
+        assert_eq!(pool.withdrawable_liquidity(), 200);
+        assert!(pool.liquidity_after_withdrawal(300).is_err());
+
+        // Blocked claims queue up in order and only leave from the head.
+        assert_eq!(pool.enqueue_withdrawal().unwrap(), 0);
+        assert_eq!(pool.enqueue_withdrawal().unwrap(), 1);
+        assert!(pool.pop_withdrawal(1).is_err());
+        pool.pop_withdrawal(0).unwrap();
+        assert!(pool.pop_withdrawal(0).is_err());
+
+        // Once open interest shrinks the head can be paid at NAV.
+        pool.open_interest = 1_000;
+        let amount = pool.shares_to_amount(300).unwrap();
+        assert_eq!(amount, 300);
+        assert_eq!(pool.liquidity_after_withdrawal(amount).unwrap(), 700);
+        pool.pop_withdrawal(1).unwrap();
+        assert_eq!((pool.withdraw_queue_head, pool.withdraw_queue_tail), (2, 2));
+    }
+
+    #[test]
+    fn test_trailing_window_fees() {
+        let mut pool = zeroed_pool();
+        pool.record_trading_fee(100, 1_000).unwrap();
+        assert_eq!(pool.trailing_window_fees(100), 1_000);
+        // Half a window past the bucket, half of it still counts.
+        assert_eq!(pool.trailing_window_fees(129_600), 500);
+
+        pool.record_trading_fee(129_600, 200).unwrap();
+        assert_eq!(pool.fee_window_start, 86_400);
+        assert_eq!(pool.prev_fee_window_fees, 1_000);
+        assert_eq!(pool.trailing_window_fees(129_600), 700);
+        assert_eq!(pool.total_trading_fees, 1_200);
+
+        // Two full windows later nothing is left in the trailing total.
+        assert_eq!(pool.trailing_window_fees(259_200), 0);
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let mut pool = zeroed_pool();
+        pool.accounted_liquidity = 100_000;
+        pool.circuit_breaker_window_secs = 3_600;
+        pool.reset_pnl_window(0);
+        // Disabled without a drawdown threshold.
+        pool.cumulative_trader_pnl = 50_000;
+        assert_eq!(pool.check_circuit_breaker(10), None);
+
+        pool.circuit_breaker_drawdown_bps = 1_000;
+        pool.cumulative_trader_pnl = 0;
+        pool.reset_pnl_window(0);
+        // A 10% drawdown of the window's NAV trips it; realized and unrealized
+        // trader PnL both count.
+        pool.cumulative_trader_pnl = 6_000;
+        assert_eq!(pool.check_circuit_breaker(10), None);
+        pool.unrealized_trader_pnl = 4_000;
+        assert_eq!(pool.check_circuit_breaker(20), Some(10_000));
+        assert!(pool.withdrawals_paused);
+        // It trips once; the window then rolls over from the new baseline.
+        assert_eq!(pool.check_circuit_breaker(30), None);
+        assert_eq!(pool.check_circuit_breaker(3_600), None);
+        assert_eq!(pool.pnl_window_start, 3_600);
+        assert_eq!(pool.pnl_window_trader_pnl, 10_000);
+        assert_eq!(pool.pnl_window_nav, 96_000);
+    }
+
+    #[test]
+    fn test_pro_rata_min_amount_out() {
+        // Half the shares paid: half the minimum, rounded up.
+        assert_eq!(pro_rata_min_amount_out(1_001, 500, 1_000).unwrap(), 501);
+        assert_eq!(pro_rata_min_amount_out(1_000, 1_000, 1_000).unwrap(), 1_000);
+        assert_eq!(pro_rata_min_amount_out(0, 500, 1_000).unwrap(), 0);
+        assert!(pro_rata_min_amount_out(1_000, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_migrate_keeper_rebate() {
+        let pool = Pubkey::new_unique();
+        let keeper = Pubkey::new_unique();
+        // `KeeperRebate` before `payout_account` was appended.
+        let data = account_data(
+            KeeperRebate::DISCRIMINATOR,
+            &(pool, keeper, 12_345u64, 253u8),
+            8 + 32 + 32 + 8 + 1,
+        );
+
+        let rebate = migrated_keeper_rebate(&data).unwrap();
+        assert_eq!(rebate.pool, pool);
+        assert_eq!(rebate.keeper, keeper);
+        assert_eq!(rebate.execution_rebate, 12_345);
+        assert_eq!(rebate.bump, 253);
+        assert!(rebate.payout_account.is_none());
+
+        let mut migrated = Vec::new();
+        rebate.try_serialize(&mut migrated).unwrap();
+        migrated.resize(8 + KeeperRebate::INIT_SPACE, 0);
+        assert!(migrated_keeper_rebate(&migrated).is_err());
+    }
+
+    #[test]
+    fn test_migrate_withdraw_request() {
+        let owner = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        // `WithdrawRequest` before the queue fields were appended.
+        let data = account_data(
+            WithdrawRequest::DISCRIMINATOR,
+            &(owner, pool, 7u64, 50_000u128, 1_000i64, false, 252u8),
+            8 + 32 + 32 + 8 + 16 + 8 + 1 + 1,
+        );
+
+        let request = migrated_withdraw_request(&data).unwrap();
+        assert_eq!(request.owner, owner);
+        assert_eq!(request.nonce, 7);
+        assert_eq!(request.share_amount, 50_000);
+        assert_eq!(request.bump, 252);
+        assert!(!request.queued);
+        assert_eq!(request.min_amount_out, 0);
+    }
+
+    #[test]
+    fn test_migrate_pool_rejects_other_accounts() {
+        let data = account_data(
+            LpPosition::DISCRIMINATOR,
+            &legacy_pool(),
+            LegacyPool::ACCOUNT_LEN,
+        );
+        assert!(migrated_pool(&data).is_err());
+    }
+
+    #[test]
+    fn test_migrate_legacy_lp_position() {
+        let owner = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        // owner, pool, shares, pending_shares, withdraw_nonce, bump
+        let legacy = (owner, pool, 1_000u128, 250u128, 4u64, 253u8);
+        let data = account_data(
+            LpPosition::DISCRIMINATOR,
+            &legacy,
+            8 + 32 + 32 + 16 + 16 + 8 + 1,
+        );
+
+        let lp = migrated_lp_position(&data).unwrap();
+        assert_eq!(lp.version, LP_POSITION_VERSION);
+        assert_eq!(lp.owner, owner);
+        assert_eq!(lp.pool, pool);
+        assert_eq!(lp.shares, 1_000);
+        assert_eq!(lp.pending_shares, 250);
+        assert_eq!(lp.withdraw_nonce, 4);
+        assert_eq!(lp.bump, 253);
+        assert!(lp.delegate.is_none());
+        assert!(lp.referrer.is_none());
+        assert_eq!(lp.referral_accrued, 0);
+
+        let mut migrated = Vec::new();
+        lp.try_serialize(&mut migrated).unwrap();
+        let reread = LpPosition::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(reread.shares, 1_000);
+        assert_eq!(reread.version, LP_POSITION_VERSION);
+        assert!(migrated_lp_position(&migrated).is_err());
+    }
 }
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolVault;
+
+// A pending break-glass transfer out of one pool vault. At most one exists per
+// pool; it can be cancelled at any time and executed after `eta`.
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyWithdrawal {
+    pub pool: Pubkey,
+    pub vault: PoolVault,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub proposed_at: i64,
+    pub eta: i64,
+    pub bump: u8,
+}
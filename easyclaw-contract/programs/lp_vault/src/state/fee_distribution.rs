@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BPS_DENOM, MAX_FEE_RECIPIENTS},
+    error::ErrorCode,
+    state::FeeRecipient,
+};
+
+// Recipients protocol fees are split across, by bps summing to 100%.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeDistribution {
+    pub pool: Pubkey,
+    #[max_len(MAX_FEE_RECIPIENTS)]
+    pub recipients: Vec<FeeRecipient>,
+    pub bump: u8,
+}
+
+impl FeeDistribution {
+    pub fn validate_recipients(recipients: &[FeeRecipient]) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+            ErrorCode::InvalidFeeRecipients
+        );
+        let mut sum = 0u64;
+        for recipient in recipients {
+            require!(
+                recipient.destination != Pubkey::default() && recipient.bps > 0,
+                ErrorCode::InvalidFeeRecipients
+            );
+            sum += recipient.bps as u64;
+        }
+        require!(sum == BPS_DENOM, ErrorCode::InvalidBps);
+        Ok(())
+    }
+}
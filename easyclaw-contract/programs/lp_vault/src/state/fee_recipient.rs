@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct FeeRecipient {
+    // USDC token account paid by `distribute_protocol_fees`.
+    pub destination: Pubkey,
+    pub bps: u16,
+}
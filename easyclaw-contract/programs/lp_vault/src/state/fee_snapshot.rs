@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Point-in-time share supply and fee totals, one PDA per `index`. Reward
+// programs weight LP participation between consecutive snapshots instead of
+// replaying every deposit and withdrawal.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSnapshot {
+    pub pool: Pubkey,
+    pub index: u64,
+    pub timestamp: i64,
+    pub total_shares: u128,
+    pub share_price: u128,
+    pub cumulative_lp_fees: u64,
+    pub total_trading_fees: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::BPS_DENOM, error::ErrorCode};
+
+// Fee split the pool moves towards as utilization (open interest over
+// accounted liquidity) rises. The split is interpolated linearly from the base
+// bps at zero utilization to these bps at `utilization_bps` and held there
+// beyond it; a zero `utilization_bps` keeps the base split.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct FeeSplitCurve {
+    pub utilization_bps: u16,
+    pub lp_fee_bps: u16,
+    pub insurance_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+}
+
+impl FeeSplitCurve {
+    pub fn is_enabled(&self) -> bool {
+        self.utilization_bps > 0
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let sum = self
+            .lp_fee_bps
+            .checked_add(self.insurance_fee_bps)
+            .and_then(|x| x.checked_add(self.protocol_fee_bps))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        require!(sum == BPS_DENOM as u16, ErrorCode::InvalidBps);
+        Ok(())
+    }
+
+    // Moves `base` towards `target` by the fraction of the curve covered.
+    pub fn interpolate(&self, base: u16, target: u16, utilization_bps: u16) -> u16 {
+        if !self.is_enabled() {
+            return base;
+        }
+        let progress = utilization_bps.min(self.utilization_bps) as i64;
+        let delta = (target as i64 - base as i64) * progress / self.utilization_bps as i64;
+        (base as i64 + delta) as u16
+    }
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Third-party stake in the insurance vault. Stakers earn
+// `insurance_staker_fee_bps` of insurance income and absorb bad debt before
+// the protocol-owned part of the vault.
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceStake {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub shares: u128,
+    // Queued to leave after the cooldown; still exposed to losses until then.
+    pub pending_shares: u128,
+    pub unstake_requested_at: i64,
+    pub bump: u8,
+}
@@ -5,6 +5,11 @@ use anchor_lang::prelude::*;
 pub struct KeeperRebate {
     pub pool: Pubkey,
     pub keeper: Pubkey,
-    pub amount: u64,
+    // Fill execution rebates awaiting claim. Liquidation rewards are paid to
+    // the keeper directly and never accrue here.
+    pub execution_rebate: u64,
     pub bump: u8,
+    // Token account claims are paid to instead of one owned by the keeper,
+    // e.g. a cold wallet or treasury.
+    pub payout_account: Option<Pubkey>,
 }
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{POOL_RESERVED_BYTES, POOL_VERSION},
+    state::{FeeSplitCurve, Pool},
+};
+
+// `PoolConfigParams` as it was before layouts were versioned.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LegacyPoolConfigParams {
+    pub cooldown_secs: i64,
+    pub liquidity_buffer_oi_bps: u16,
+    pub lp_fee_bps: u16,
+    pub insurance_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub execution_rebate_usdc: u64,
+    pub execution_rebate_fee_bps: u16,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    pub treasury: Pubkey,
+    pub pool_deposit_cap: u64,
+    pub position_deposit_cap: u64,
+    pub instant_withdraw_fee_bps: u16,
+    pub min_insurance_oi_bps: u16,
+    pub max_insurance_oi_bps: u16,
+    pub insurance_staker_fee_bps: u16,
+    pub config_delay_secs: i64,
+}
+
+// The unversioned `Pool` layout, read field by field by `migrate_pool`. It
+// kept the queued config inline, so later fields cannot simply be appended to
+// it. Shares the `Pool` discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LegacyPool {
+    pub admin: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub pool_index: u16,
+    pub order_engine_program: Pubkey,
+    pub engine_authority: Pubkey,
+    pub liquidity_vault: Pubkey,
+    pub insurance_vault: Pubkey,
+    pub protocol_fee_vault: Pubkey,
+    pub cooldown_secs: i64,
+    pub liquidity_buffer_oi_bps: u16,
+    pub lp_fee_bps: u16,
+    pub insurance_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub execution_rebate_usdc: u64,
+    pub execution_rebate_fee_bps: u16,
+    pub total_shares: u128,
+    pub accounted_liquidity: u64,
+    pub pending_keeper_rebates: u64,
+    pub unfunded_keeper_rebates: u64,
+    pub total_trading_fees: u64,
+    pub cumulative_lp_fees: u64,
+    pub cumulative_deposits: u64,
+    pub cumulative_withdrawals: u64,
+    pub fee_window_start: i64,
+    pub fee_window_fees: u64,
+    pub prev_fee_window_fees: u64,
+    pub cumulative_trader_pnl: i128,
+    pub total_maker_rebates: u64,
+    pub unrealized_trader_pnl: i64,
+    pub nav_marked_at: i64,
+    pub open_interest: u64,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    pub treasury: Pubkey,
+    pub treasury_shares: u128,
+    pub last_management_fee_ts: i64,
+    pub high_water_mark: u128,
+    pub pool_deposit_cap: u64,
+    pub position_deposit_cap: u64,
+    pub instant_withdraw_fee_bps: u16,
+    pub min_insurance_oi_bps: u16,
+    pub max_insurance_oi_bps: u16,
+    pub insurance_staked: u64,
+    pub insurance_stake_shares: u128,
+    pub insurance_staker_fee_bps: u16,
+    pub total_staker_losses: u64,
+    pub config_delay_secs: i64,
+    pub pending_config: Option<LegacyPoolConfigParams>,
+    pub pending_config_eta: i64,
+    pub withdraw_queue_head: u64,
+    pub withdraw_queue_tail: u64,
+    pub allowlist_enabled: bool,
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
+    pub bump: u8,
+}
+
+impl LegacyPool {
+    // Legacy pools were allocated at the maximum size of this layout.
+    pub const ACCOUNT_LEN: usize = 8 + Self::INIT_SPACE;
+
+    // Carries every legacy field over; fields added since take the values
+    // `initialize_pool` gives them. A queued config is dropped and has to be
+    // proposed again.
+    pub fn into_pool(self) -> Pool {
+        Pool {
+            version: POOL_VERSION,
+            admin: self.admin,
+            usdc_mint: self.usdc_mint,
+            pool_index: self.pool_index,
+            order_engine_program: self.order_engine_program,
+            engine_authority: self.engine_authority,
+            liquidity_vault: self.liquidity_vault,
+            insurance_vault: self.insurance_vault,
+            protocol_fee_vault: self.protocol_fee_vault,
+            cooldown_secs: self.cooldown_secs,
+            liquidity_buffer_oi_bps: self.liquidity_buffer_oi_bps,
+            lp_fee_bps: self.lp_fee_bps,
+            insurance_fee_bps: self.insurance_fee_bps,
+            protocol_fee_bps: self.protocol_fee_bps,
+            execution_rebate_usdc: self.execution_rebate_usdc,
+            execution_rebate_fee_bps: self.execution_rebate_fee_bps,
+            total_shares: self.total_shares,
+            accounted_liquidity: self.accounted_liquidity,
+            pending_keeper_rebates: self.pending_keeper_rebates,
+            unfunded_keeper_rebates: self.unfunded_keeper_rebates,
+            total_trading_fees: self.total_trading_fees,
+            cumulative_lp_fees: self.cumulative_lp_fees,
+            cumulative_deposits: self.cumulative_deposits,
+            cumulative_withdrawals: self.cumulative_withdrawals,
+            fee_window_start: self.fee_window_start,
+            fee_window_fees: self.fee_window_fees,
+            prev_fee_window_fees: self.prev_fee_window_fees,
+            cumulative_trader_pnl: self.cumulative_trader_pnl,
+            total_maker_rebates: self.total_maker_rebates,
+            unrealized_trader_pnl: self.unrealized_trader_pnl,
+            nav_marked_at: self.nav_marked_at,
+            open_interest: self.open_interest,
+            management_fee_bps: self.management_fee_bps,
+            performance_fee_bps: self.performance_fee_bps,
+            treasury: self.treasury,
+            treasury_shares: self.treasury_shares,
+            last_management_fee_ts: self.last_management_fee_ts,
+            high_water_mark: self.high_water_mark,
+            pool_deposit_cap: self.pool_deposit_cap,
+            position_deposit_cap: self.position_deposit_cap,
+            instant_withdraw_fee_bps: self.instant_withdraw_fee_bps,
+            min_insurance_oi_bps: self.min_insurance_oi_bps,
+            max_insurance_oi_bps: self.max_insurance_oi_bps,
+            insurance_staked: self.insurance_staked,
+            insurance_stake_shares: self.insurance_stake_shares,
+            insurance_staker_fee_bps: self.insurance_staker_fee_bps,
+            total_staker_losses: self.total_staker_losses,
+            config_delay_secs: self.config_delay_secs,
+            withdraw_queue_head: self.withdraw_queue_head,
+            withdraw_queue_tail: self.withdraw_queue_tail,
+            allowlist_enabled: self.allowlist_enabled,
+            deposits_paused: self.deposits_paused,
+            withdrawals_paused: self.withdrawals_paused,
+            bump: self.bump,
+            fee_curve: FeeSplitCurve::default(),
+            marked_utilization_bps: 0,
+            max_liquidation_reward: 0,
+            cumulative_bad_debt_covered: 0,
+            fee_snapshot_count: 0,
+            last_fee_snapshot_at: 0,
+            circuit_breaker_drawdown_bps: 0,
+            circuit_breaker_window_secs: 0,
+            pnl_window_start: 0,
+            pnl_window_trader_pnl: 0,
+            pnl_window_nav: 0,
+            referral_fee_bps: 0,
+            pending_referral_rewards: 0,
+            min_insurance_coverage_bps: 0,
+            reserved: [0; POOL_RESERVED_BYTES],
+            lp_fees_per_share: 0,
+            pending_lp_fee_bps: 0,
+            pending_insurance_fee_bps: 0,
+            pending_protocol_fee_bps: 0,
+            pending_fee_split_eta: 0,
+        }
+    }
+}
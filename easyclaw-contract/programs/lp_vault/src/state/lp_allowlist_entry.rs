@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// Marks `user` as an approved depositor for a gated pool; closing it revokes
+// the approval. Existing shares are unaffected.
+#[account]
+#[derive(InitSpace)]
+pub struct LpAllowlistEntry {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+}
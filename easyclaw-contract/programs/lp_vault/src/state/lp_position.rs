@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{BPS_DENOM, LP_POSITION_RESERVED_BYTES, SHARE_PRICE_SCALE},
+    error::ErrorCode,
+    state::Pool,
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct LpPosition {
@@ -9,4 +15,48 @@ pub struct LpPosition {
     pub pending_shares: u128,
     pub withdraw_nonce: u64,
     pub bump: u8,
+    // Layout version, after the unversioned fields so a zero-extended legacy
+    // position reads as version zero.
+    pub version: u8,
+    pub reserved: [u8; LP_POSITION_RESERVED_BYTES],
+    // May claim matured withdrawals on the owner's behalf; funds always go to
+    // the owner.
+    pub delegate: Option<Pubkey>,
+    // Set at creation only. Earns `Pool::referral_fee_bps` of the fee income
+    // on this position's shares.
+    pub referrer: Option<Pubkey>,
+    // `Pool::lp_fees_per_share` when the referral was last settled.
+    pub referral_fee_checkpoint: u128,
+    pub referral_accrued: u64,
+}
+
+impl LpPosition {
+    // Credits the referrer with their cut of the fee income earned since the
+    // last checkpoint. Must run before the position's share count changes.
+    pub fn settle_referral(&mut self, pool: &mut Pool) -> Result<()> {
+        if self.referrer.is_some() && pool.referral_fee_bps > 0 {
+            let fee_income = self
+                .shares
+                .checked_add(self.pending_shares)
+                .and_then(|shares| {
+                    shares.checked_mul(
+                        pool.lp_fees_per_share
+                            .saturating_sub(self.referral_fee_checkpoint),
+                    )
+                })
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+                / SHARE_PRICE_SCALE;
+            let reward = (fee_income * pool.referral_fee_bps as u128 / BPS_DENOM as u128) as u64;
+            self.referral_accrued = self
+                .referral_accrued
+                .checked_add(reward)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            pool.pending_referral_rewards = pool
+                .pending_referral_rewards
+                .checked_add(reward)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        }
+        self.referral_fee_checkpoint = pool.lp_fees_per_share;
+        Ok(())
+    }
 }
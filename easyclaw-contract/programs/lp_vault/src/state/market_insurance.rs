@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Virtual slice of the shared insurance vault attributed to one market: fed by
+// that market's insurance fees and liquidation penalties, and drawn first when
+// the market produces bad debt.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketInsurance {
+    pub pool: Pubkey,
+    pub market_id: u64,
+    pub earmarked: u64,
+    pub total_contributed: u64,
+    pub total_bad_debt: u64,
+    // Bad debt the earmark could not cover and the shared pot absorbed.
+    pub drawn_from_shared: u64,
+    pub bump: u8,
+    // Keeper rebate per fill in this market; falls back to the pool default.
+    pub execution_rebate_override: Option<u64>,
+}
+
+impl MarketInsurance {
+    pub fn execution_rebate(&self, pool_default: u64) -> u64 {
+        self.execution_rebate_override.unwrap_or(pool_default)
+    }
+
+    pub fn contribute(&mut self, amount: u64) -> Result<()> {
+        self.earmarked = self
+            .earmarked
+            .checked_add(amount)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.total_contributed = self
+            .total_contributed
+            .checked_add(amount)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    // Returns the part of `bad_debt` that falls on the shared pot.
+    pub fn absorb_bad_debt(&mut self, bad_debt: u64) -> Result<u64> {
+        let from_earmark = bad_debt.min(self.earmarked);
+        let from_shared = bad_debt - from_earmark;
+        self.earmarked -= from_earmark;
+        self.total_bad_debt = self
+            .total_bad_debt
+            .checked_add(bad_debt)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.drawn_from_shared = self
+            .drawn_from_shared
+            .checked_add(from_shared)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(from_shared)
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Running totals of what one market has earned and cost the pool, so fee and
+// risk parameters can be tuned per market.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketVaultStats {
+    pub pool: Pubkey,
+    pub market_id: u64,
+    pub fill_count: u64,
+    pub cumulative_notional: u128,
+    pub cumulative_fees: u64,
+    pub lp_fees: u64,
+    pub insurance_fees: u64,
+    pub protocol_fees: u64,
+    pub keeper_credits: u64,
+    // Realized trader PnL reported with fills; positive is a loss to the pool.
+    pub trader_pnl: i128,
+    pub liquidation_count: u64,
+    pub liquidation_penalties: u64,
+    pub bad_debt: u64,
+    pub bump: u8,
+}
+
+impl MarketVaultStats {
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_fill(
+        &mut self,
+        notional: u64,
+        fee: u64,
+        lp_fee: u64,
+        insurance_fee: u64,
+        protocol_fee: u64,
+        keeper_credit: u64,
+        pnl_delta: i64,
+    ) -> Result<()> {
+        self.fill_count = self.fill_count.saturating_add(1);
+        self.cumulative_notional = self
+            .cumulative_notional
+            .checked_add(notional as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.cumulative_fees = self.cumulative_fees.saturating_add(fee);
+        self.lp_fees = self.lp_fees.saturating_add(lp_fee);
+        self.insurance_fees = self.insurance_fees.saturating_add(insurance_fee);
+        self.protocol_fees = self.protocol_fees.saturating_add(protocol_fee);
+        self.keeper_credits = self.keeper_credits.saturating_add(keeper_credit);
+        self.trader_pnl = self
+            .trader_pnl
+            .checked_add(pnl_delta as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    pub fn record_liquidation(
+        &mut self,
+        penalty: u64,
+        bad_debt: u64,
+        pnl_delta: i64,
+    ) -> Result<()> {
+        self.liquidation_count = self.liquidation_count.saturating_add(1);
+        self.liquidation_penalties = self.liquidation_penalties.saturating_add(penalty);
+        self.bad_debt = self.bad_debt.saturating_add(bad_debt);
+        self.trader_pnl = self
+            .trader_pnl
+            .checked_add(pnl_delta as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+}
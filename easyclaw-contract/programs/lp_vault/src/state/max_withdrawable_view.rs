@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Returned by `view_max_withdrawable` for the withdraw requests passed in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MaxWithdrawableView {
+    // Requests past their cooldown and not waiting in the queue.
+    pub matured_shares: u128,
+    pub matured_value: u64,
+    // Requests still cooling down, and when the first of them matures.
+    pub cooling_shares: u128,
+    pub next_claimable_at: i64,
+    // What the pool can pay out without breaching the liquidity buffer.
+    pub withdrawable_liquidity: u64,
+    // Matured value capped by `withdrawable_liquidity`.
+    pub claimable_now: u64,
+}
@@ -1,11 +1,43 @@
+pub mod emergency_withdrawal;
+pub mod fee_distribution;
+pub mod fee_recipient;
+pub mod fee_snapshot;
+pub mod fee_split_curve;
+pub mod insurance_stake;
 pub mod keeper_rebate;
+pub mod legacy_pool;
+pub mod lp_allowlist_entry;
 pub mod lp_position;
+pub mod market_insurance;
+pub mod market_vault_stats;
+pub mod max_withdrawable_view;
+pub mod pending_pool_config;
 pub mod pool;
 pub mod pool_config_params;
+pub mod pool_metrics;
+pub mod pool_vault;
+pub mod rebalance_direction;
+pub mod share_price_view;
 pub mod withdraw_request;
 
+pub use emergency_withdrawal::*;
+pub use fee_distribution::*;
+pub use fee_recipient::*;
+pub use fee_snapshot::*;
+pub use fee_split_curve::*;
+pub use insurance_stake::*;
 pub use keeper_rebate::*;
+pub use legacy_pool::*;
+pub use lp_allowlist_entry::*;
 pub use lp_position::*;
+pub use market_insurance::*;
+pub use market_vault_stats::*;
+pub use max_withdrawable_view::*;
+pub use pending_pool_config::*;
 pub use pool::*;
 pub use pool_config_params::*;
+pub use pool_metrics::*;
+pub use pool_vault::*;
+pub use rebalance_direction::*;
+pub use share_price_view::*;
 pub use withdraw_request::*;
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolConfigParams;
+
+// A `configure_pool` proposal waiting out the pool's config delay. Kept out of
+// `Pool` so growing `PoolConfigParams` never shifts the pool layout. At most
+// one exists per pool; executing or cancelling it closes the account.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingPoolConfig {
+    pub pool: Pubkey,
+    pub params: PoolConfigParams,
+    pub proposed_at: i64,
+    pub eta: i64,
+    pub bump: u8,
+}
@@ -1,24 +1,575 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{
+        BPS_DENOM, FEE_WINDOW_SECS, MAX_NAV_AGE_SECS, POOL_RESERVED_BYTES, SECONDS_PER_YEAR,
+        SHARE_PRICE_SCALE,
+    },
+    error::ErrorCode,
+    helpers::mul_bps,
+    state::{FeeSplitCurve, PoolConfigParams},
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Pool {
     pub admin: Pubkey,
     pub usdc_mint: Pubkey,
+    // Distinguishes pools sharing a collateral mint; part of the PDA seeds.
+    pub pool_index: u16,
     pub order_engine_program: Pubkey,
     pub engine_authority: Pubkey,
     pub liquidity_vault: Pubkey,
     pub insurance_vault: Pubkey,
     pub protocol_fee_vault: Pubkey,
     pub cooldown_secs: i64,
-    pub min_liquidity_buffer_bps: u16,
+    // Liquidity that must remain after withdrawals, in bps of the open
+    // interest reported at the last NAV mark. May exceed 100%.
+    pub liquidity_buffer_oi_bps: u16,
+    // Base fee split, owned by the registry: only `sync_fee_split` changes it.
     pub lp_fee_bps: u16,
     pub insurance_fee_bps: u16,
     pub protocol_fee_bps: u16,
     pub execution_rebate_usdc: u64,
+    // When non-zero the keeper rebate is this share of the fill fee, capped at
+    // the flat rebate.
+    pub execution_rebate_fee_bps: u16,
     pub total_shares: u128,
+    pub accounted_liquidity: u64,
     pub pending_keeper_rebates: u64,
+    // Rebate credit dropped because the protocol fee vault could not fund it.
+    pub unfunded_keeper_rebates: u64,
     pub total_trading_fees: u64,
+    // LP share of every fill fee. It is moved into the liquidity vault and
+    // added to accounted liquidity in the same fill, so it compounds straight
+    // into the share price.
+    pub cumulative_lp_fees: u64,
+    // Gross LP flows, treasury fee redemptions included, snapshotted into
+    // `PoolMetrics` by the metrics crank.
+    pub cumulative_deposits: u64,
+    pub cumulative_withdrawals: u64,
+    pub fee_window_start: i64,
+    pub fee_window_fees: u64,
+    pub prev_fee_window_fees: u64,
     pub cumulative_trader_pnl: i128,
+    // Paid to makers from the liquidity vault; bounded by the LP fee share.
+    pub total_maker_rebates: u64,
+    // Engine-reported mark of open positions against the pool; positive means
+    // traders are in profit and the pool owes it.
+    pub unrealized_trader_pnl: i64,
+    pub nav_marked_at: i64,
+    // Engine open interest at the last NAV mark.
+    pub open_interest: u64,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    pub treasury: Pubkey,
+    // Fee shares owed to the treasury; counted in `total_shares`.
+    pub treasury_shares: u128,
+    pub last_management_fee_ts: i64,
+    // Highest share price (SHARE_PRICE_SCALE) the performance fee was charged at.
+    pub high_water_mark: u128,
+    // Zero means uncapped.
+    pub pool_deposit_cap: u64,
+    pub position_deposit_cap: u64,
+    pub instant_withdraw_fee_bps: u16,
+    // Bounds, as bps of open interest, that admin rebalancing must leave the
+    // insurance vault within.
+    pub min_insurance_oi_bps: u16,
+    pub max_insurance_oi_bps: u16,
+    // Staker-owned part of the insurance vault and the shares that claim it.
+    pub insurance_staked: u64,
+    pub insurance_stake_shares: u128,
+    pub insurance_staker_fee_bps: u16,
+    pub total_staker_losses: u64,
+    // Timelock for `configure_pool`; proposals live in `PendingPoolConfig`.
+    pub config_delay_secs: i64,
+    // FIFO tickets for buffer-blocked withdrawals: the next request to pay and
+    // the position the next queued request gets.
+    pub withdraw_queue_head: u64,
+    pub withdraw_queue_tail: u64,
+    // Gated pools only take deposits from users with an `LpAllowlistEntry`.
+    pub allowlist_enabled: bool,
+    // Incident switches, set independently by the admin.
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
     pub bump: u8,
+    // Layout version, after the unversioned fields so it reads as zero on
+    // accounts created before it existed.
+    pub version: u8,
+    // Split the fee shares move towards as utilization rises.
+    pub fee_curve: FeeSplitCurve,
+    // Utilization sampled at the last NAV mark. The fee curve reads this
+    // rather than live liquidity so order_engine and `apply_trade_fill` split
+    // a fee identically within one transaction.
+    pub marked_utilization_bps: u16,
+    pub max_liquidation_reward: u64,
+    // Bad debt paid out of the insurance vault, stakers' share included.
+    // Together with the LP flows this reconciles vault balances from state.
+    pub cumulative_bad_debt_covered: u64,
+    // Index of the next `FeeSnapshot` and when the last one was taken.
+    pub fee_snapshot_count: u64,
+    pub last_fee_snapshot_at: i64,
+    pub circuit_breaker_drawdown_bps: u16,
+    pub circuit_breaker_window_secs: i64,
+    // Trader PnL (realized plus marked) and NAV when the breaker window began.
+    pub pnl_window_start: i64,
+    pub pnl_window_trader_pnl: i128,
+    pub pnl_window_nav: u64,
+    pub referral_fee_bps: u16,
+    // Referral rewards settled onto positions but not yet claimed; reserved in
+    // the protocol fee vault alongside keeper rebates.
+    pub pending_referral_rewards: u64,
+    pub min_insurance_coverage_bps: u16,
+    pub reserved: [u8; POOL_RESERVED_BYTES],
+    // LP fee income per share since inception, scaled by `SHARE_PRICE_SCALE`.
+    // Referral rewards are settled against this.
+    pub lp_fees_per_share: u128,
+    // Registry fee split waiting out `config_delay_secs` before it replaces
+    // the base bps; a zero eta means none is queued. See `sync_fee_split`.
+    pub pending_lp_fee_bps: u16,
+    pub pending_insurance_fee_bps: u16,
+    pub pending_protocol_fee_bps: u16,
+    pub pending_fee_split_eta: i64,
+}
+
+impl Pool {
+    pub fn apply_config(&mut self, params: &PoolConfigParams, now: i64) -> Result<()> {
+        self.cooldown_secs = params.cooldown_secs;
+        self.liquidity_buffer_oi_bps = params.liquidity_buffer_oi_bps;
+        self.fee_curve = params.fee_curve;
+        self.execution_rebate_usdc = params.execution_rebate_usdc;
+        self.execution_rebate_fee_bps = params.execution_rebate_fee_bps;
+        // Accrue at the old rates before the new ones take effect.
+        self.accrue_treasury_fees(now)?;
+        self.management_fee_bps = params.management_fee_bps;
+        self.performance_fee_bps = params.performance_fee_bps;
+        self.treasury = params.treasury;
+        self.pool_deposit_cap = params.pool_deposit_cap;
+        self.position_deposit_cap = params.position_deposit_cap;
+        self.instant_withdraw_fee_bps = params.instant_withdraw_fee_bps;
+        self.min_insurance_oi_bps = params.min_insurance_oi_bps;
+        self.max_insurance_oi_bps = params.max_insurance_oi_bps;
+        self.insurance_staker_fee_bps = params.insurance_staker_fee_bps;
+        self.config_delay_secs = params.config_delay_secs;
+        self.max_liquidation_reward = params.max_liquidation_reward;
+        self.circuit_breaker_drawdown_bps = params.circuit_breaker_drawdown_bps;
+        self.circuit_breaker_window_secs = params.circuit_breaker_window_secs;
+        self.referral_fee_bps = params.referral_fee_bps;
+        self.min_insurance_coverage_bps = params.min_insurance_coverage_bps;
+        self.reset_pnl_window(now);
+        Ok(())
+    }
+
+    // Open interest over accounted liquidity, saturating at u16::MAX.
+    pub fn utilization_bps(&self) -> u16 {
+        if self.accounted_liquidity == 0 {
+            return if self.open_interest == 0 { 0 } else { u16::MAX };
+        }
+        let utilization =
+            (self.open_interest as u128) * (BPS_DENOM as u128) / self.accounted_liquidity as u128;
+        utilization.min(u16::MAX as u128) as u16
+    }
+
+    // Splits a trading fee into its LP, insurance and protocol parts at the
+    // marked utilization; the protocol takes the rounding remainder.
+    pub fn split_fee(&self, fee: u64) -> Result<(u64, u64, u64)> {
+        let utilization = self.marked_utilization_bps;
+        let lp_bps =
+            self.fee_curve
+                .interpolate(self.lp_fee_bps, self.fee_curve.lp_fee_bps, utilization);
+        let insurance_bps = self
+            .fee_curve
+            .interpolate(
+                self.insurance_fee_bps,
+                self.fee_curve.insurance_fee_bps,
+                utilization,
+            )
+            .min(BPS_DENOM as u16 - lp_bps);
+        let lp_fee = mul_bps(fee, lp_bps as u64)?;
+        let insurance_fee = mul_bps(fee, insurance_bps as u64)?;
+        let protocol_fee = fee
+            .checked_sub(lp_fee)
+            .and_then(|x| x.checked_sub(insurance_fee))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok((lp_fee, insurance_fee, protocol_fee))
+    }
+
+    // Open interest that can still be added before `insurance_balance` falls
+    // below the coverage ratio of the marked open interest. Read by
+    // order_engine before a fill grows open interest.
+    pub fn insurance_oi_headroom(&self, insurance_balance: u64) -> u64 {
+        if self.min_insurance_coverage_bps == 0 {
+            return u64::MAX;
+        }
+        let covered_oi = (insurance_balance as u128) * (BPS_DENOM as u128)
+            / self.min_insurance_coverage_bps as u128;
+        (covered_oi.min(u64::MAX as u128) as u64).saturating_sub(self.open_interest)
+    }
+
+    // Protocol fee vault balance owed to keepers and referrers.
+    pub fn reserved_protocol_fees(&self) -> u64 {
+        self.pending_keeper_rebates
+            .saturating_add(self.pending_referral_rewards)
+    }
+
+    pub fn accrue_lp_fees_per_share(&mut self, lp_fee: u64) -> Result<()> {
+        if self.total_shares == 0 {
+            return Ok(());
+        }
+        let per_share = (lp_fee as u128)
+            .checked_mul(SHARE_PRICE_SCALE)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / self.total_shares;
+        self.lp_fees_per_share = self
+            .lp_fees_per_share
+            .checked_add(per_share)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    pub fn execution_rebate(&self, fee: u64, flat_rebate: u64) -> u64 {
+        if self.execution_rebate_fee_bps == 0 {
+            return flat_rebate;
+        }
+        let proportional =
+            ((fee as u128) * (self.execution_rebate_fee_bps as u128) / BPS_DENOM as u128) as u64;
+        proportional.min(flat_rebate)
+    }
+
+    pub fn base_fee_split(&self) -> (u16, u16, u16) {
+        (
+            self.lp_fee_bps,
+            self.insurance_fee_bps,
+            self.protocol_fee_bps,
+        )
+    }
+
+    pub fn pending_fee_split(&self) -> (u16, u16, u16) {
+        (
+            self.pending_lp_fee_bps,
+            self.pending_insurance_fee_bps,
+            self.pending_protocol_fee_bps,
+        )
+    }
+
+    // Whether `sync_fee_split` with this registry split would do anything:
+    // queue it, apply it once matured, or drop a queue the registry reverted.
+    pub fn fee_split_sync_due(&self, split: (u16, u16, u16), now: i64) -> bool {
+        if self.base_fee_split() == split {
+            return self.pending_fee_split_eta != 0;
+        }
+        self.pending_fee_split_eta == 0
+            || self.pending_fee_split() != split
+            || now >= self.pending_fee_split_eta
+    }
+
+    // The part of a liquidation's keeper portion paid to the keeper; anything
+    // above `max_liquidation_reward` goes to protocol fees.
+    pub fn liquidation_reward(&self, keeper_portion: u64) -> u64 {
+        if self.max_liquidation_reward == 0 {
+            keeper_portion
+        } else {
+            keeper_portion.min(self.max_liquidation_reward)
+        }
+    }
+
+    pub fn reset_pnl_window(&mut self, now: i64) {
+        self.pnl_window_start = now;
+        self.pnl_window_trader_pnl =
+            self.cumulative_trader_pnl + self.unrealized_trader_pnl as i128;
+        self.pnl_window_nav = self.net_asset_value();
+    }
+
+    // Pauses withdrawals when trader PnL against the pool has grown past the
+    // drawdown threshold since the window began, then starts a new window once
+    // the current one has run out. Returns the PnL gain if the breaker tripped.
+    pub fn check_circuit_breaker(&mut self, now: i64) -> Option<i128> {
+        if self.circuit_breaker_drawdown_bps == 0 {
+            return None;
+        }
+        let trader_pnl = self.cumulative_trader_pnl + self.unrealized_trader_pnl as i128;
+        let gain = trader_pnl - self.pnl_window_trader_pnl;
+        let threshold = (self.pnl_window_nav as i128) * (self.circuit_breaker_drawdown_bps as i128)
+            / BPS_DENOM as i128;
+        let tripped = !self.withdrawals_paused && self.pnl_window_nav > 0 && gain >= threshold;
+        if tripped {
+            self.withdrawals_paused = true;
+        }
+        if now
+            >= self
+                .pnl_window_start
+                .saturating_add(self.circuit_breaker_window_secs)
+        {
+            self.reset_pnl_window(now);
+        }
+        tripped.then_some(gain)
+    }
+
+    // Fees are bucketed into fixed windows; the previous bucket is kept so a
+    // trailing-window total can be approximated at any point in time.
+    pub fn record_trading_fee(&mut self, now: i64, fee: u64) -> Result<()> {
+        self.roll_fee_window(now);
+        self.fee_window_fees = self
+            .fee_window_fees
+            .checked_add(fee)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.total_trading_fees = self
+            .total_trading_fees
+            .checked_add(fee)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    pub fn trailing_window_fees(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.fee_window_start);
+        if elapsed >= FEE_WINDOW_SECS.saturating_mul(2) {
+            return 0;
+        }
+        if elapsed >= FEE_WINDOW_SECS {
+            let remaining = FEE_WINDOW_SECS.saturating_mul(2) - elapsed;
+            return weight_fees(self.fee_window_fees, remaining);
+        }
+        let remaining = FEE_WINDOW_SECS - elapsed;
+        self.fee_window_fees
+            .saturating_add(weight_fees(self.prev_fee_window_fees, remaining))
+    }
+
+    // Maker rebates are funded from LP fee income, so the pool never pays out
+    // more than the LP share of the trading fees it has collected.
+    pub fn maker_rebate_headroom(&self) -> u64 {
+        self.cumulative_lp_fees
+            .saturating_sub(self.total_maker_rebates)
+    }
+
+    // Liquidity net of what the pool owes open positions at the last mark.
+    // Deposits and withdrawals are priced against this, not the vault balance.
+    pub fn net_asset_value(&self) -> u64 {
+        let nav = (self.accounted_liquidity as i128) - (self.unrealized_trader_pnl as i128);
+        nav.clamp(0, u64::MAX as i128) as u64
+    }
+
+    // Shares must not be priced off an old mark. The first deposit is minted
+    // at par and does not depend on the mark.
+    pub fn require_fresh_nav(&self, now: i64) -> Result<()> {
+        require!(
+            self.total_shares == 0 || now.saturating_sub(self.nav_marked_at) <= MAX_NAV_AGE_SECS,
+            ErrorCode::StaleNav
+        );
+        Ok(())
+    }
+
+    // Brings both treasury fees up to date; called before any share is priced.
+    pub fn accrue_treasury_fees(&mut self, now: i64) -> Result<()> {
+        self.accrue_management_fee(now)?;
+        self.accrue_performance_fee()
+    }
+
+    // Mints the management fee for the time since the last accrual as new
+    // treasury shares, diluting LPs pro rata. Treasury shares are not charged.
+    fn accrue_management_fee(&mut self, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_management_fee_ts).max(0);
+        self.last_management_fee_ts = now;
+        let lp_shares = self.total_shares.saturating_sub(self.treasury_shares);
+        if self.management_fee_bps == 0 || elapsed == 0 || lp_shares == 0 {
+            return Ok(());
+        }
+
+        let fee_shares = lp_shares
+            .checked_mul(self.management_fee_bps as u128)
+            .and_then(|x| x.checked_mul(elapsed as u128))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / (BPS_DENOM as u128 * SECONDS_PER_YEAR as u128);
+        self.treasury_shares = self
+            .treasury_shares
+            .checked_add(fee_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.total_shares = self
+            .total_shares
+            .checked_add(fee_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    // When the share price is above the high-water mark, mints treasury shares
+    // worth `performance_fee_bps` of the LP gain over it and raises the mark to
+    // the post-fee price. Losses leave the mark in place, so a drawdown has to
+    // be recovered before the fee is charged again.
+    fn accrue_performance_fee(&mut self) -> Result<()> {
+        if self.total_shares == 0 {
+            return Ok(());
+        }
+        let nav = self.net_asset_value() as u128;
+        let share_price = self.share_price()?;
+        if self.high_water_mark == 0 {
+            self.high_water_mark = share_price;
+            return Ok(());
+        }
+        if share_price <= self.high_water_mark || self.performance_fee_bps == 0 {
+            return Ok(());
+        }
+
+        let lp_shares = self.total_shares.saturating_sub(self.treasury_shares);
+        let fee_value = (share_price - self.high_water_mark)
+            .checked_mul(lp_shares)
+            .and_then(|x| x.checked_mul(self.performance_fee_bps as u128))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / (SHARE_PRICE_SCALE * BPS_DENOM as u128);
+        // Shares worth `fee_value` after they are minted: s * nav / (total + s).
+        let fee_shares = if fee_value > 0 && fee_value < nav {
+            fee_value
+                .checked_mul(self.total_shares)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+                / (nav - fee_value)
+        } else {
+            0
+        };
+        self.treasury_shares = self
+            .treasury_shares
+            .checked_add(fee_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.total_shares = self
+            .total_shares
+            .checked_add(fee_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        self.high_water_mark = self.share_price()?;
+        Ok(())
+    }
+
+    // Credits stakers their cut of insurance income; the rest stays with the
+    // protocol-owned part of the vault. Returns the amount credited.
+    pub fn credit_insurance_stakers(&mut self, insurance_income: u64) -> Result<u64> {
+        if self.insurance_stake_shares == 0 {
+            return Ok(0);
+        }
+        let cut = ((insurance_income as u128) * (self.insurance_staker_fee_bps as u128)
+            / BPS_DENOM as u128) as u64;
+        self.insurance_staked = self
+            .insurance_staked
+            .checked_add(cut)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(cut)
+    }
+
+    // Stakers take first loss on bad debt. Returns the part they absorbed.
+    pub fn charge_insurance_stakers(&mut self, bad_debt: u64) -> Result<u64> {
+        let loss = bad_debt.min(self.insurance_staked);
+        self.insurance_staked -= loss;
+        self.total_staker_losses = self
+            .total_staker_losses
+            .checked_add(loss)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(loss)
+    }
+
+    pub fn insurance_stake_shares_for(&self, amount: u64) -> Result<u128> {
+        if self.insurance_stake_shares == 0 {
+            return Ok(amount as u128);
+        }
+        // Stakers wiped out by bad debt leave shares with no backing.
+        require!(self.insurance_staked > 0, ErrorCode::InvalidPoolState);
+        Ok((amount as u128)
+            .checked_mul(self.insurance_stake_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / self.insurance_staked as u128)
+    }
+
+    // Part of the insurance vault not owed to stakers.
+    pub fn unstaked_insurance(&self, insurance_vault_amount: u64) -> u64 {
+        insurance_vault_amount.saturating_sub(self.insurance_staked)
+    }
+
+    pub fn insurance_stake_value(&self, shares: u128) -> Result<u64> {
+        let value = shares
+            .checked_mul(self.insurance_staked as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            .checked_div(self.insurance_stake_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        u64::try_from(value).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    // NAV per share (SHARE_PRICE_SCALE); an empty pool prices at 1.0.
+    pub fn share_price(&self) -> Result<u128> {
+        if self.total_shares == 0 {
+            return Ok(SHARE_PRICE_SCALE);
+        }
+        Ok((self.net_asset_value() as u128)
+            .checked_mul(SHARE_PRICE_SCALE)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / self.total_shares)
+    }
+
+    // Value of `shares` at the current NAV.
+    pub fn shares_to_amount(&self, shares: u128) -> Result<u64> {
+        let amount = shares
+            .checked_mul(self.net_asset_value() as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            .checked_div(self.total_shares)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        u64::try_from(amount).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    // Liquidity left after paying out `amount`; fails if that would breach the
+    // minimum buffer.
+    pub fn liquidity_after_withdrawal(&self, amount: u64) -> Result<u64> {
+        let post_liquidity = self
+            .accounted_liquidity
+            .checked_sub(amount)
+            .ok_or_else(|| error!(ErrorCode::LiquidityBufferViolation))?;
+        require!(
+            post_liquidity >= self.min_buffer_amount(),
+            ErrorCode::LiquidityBufferViolation
+        );
+        Ok(post_liquidity)
+    }
+
+    // Gives a buffer-blocked claim the next place in the withdrawal queue.
+    pub fn enqueue_withdrawal(&mut self) -> Result<u64> {
+        let position = self.withdraw_queue_tail;
+        self.withdraw_queue_tail = position
+            .checked_add(1)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(position)
+    }
+
+    // Requests leave the queue strictly in order, paid or dropped.
+    pub fn pop_withdrawal(&mut self, position: u64) -> Result<()> {
+        require!(
+            position == self.withdraw_queue_head,
+            ErrorCode::NotQueueHead
+        );
+        self.withdraw_queue_head = position
+            .checked_add(1)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    // Liquidity withdrawals can take right now without breaching the buffer.
+    pub fn withdrawable_liquidity(&self) -> u64 {
+        self.accounted_liquidity
+            .saturating_sub(self.min_buffer_amount())
+    }
+
+    // Liquidity is only held back while the pool backs open positions.
+    fn min_buffer_amount(&self) -> u64 {
+        ((self.open_interest as u128) * (self.liquidity_buffer_oi_bps as u128) / BPS_DENOM as u128)
+            .min(u64::MAX as u128) as u64
+    }
+
+    fn roll_fee_window(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.fee_window_start);
+        if elapsed < FEE_WINDOW_SECS {
+            return;
+        }
+        self.prev_fee_window_fees = if elapsed < FEE_WINDOW_SECS.saturating_mul(2) {
+            self.fee_window_fees
+        } else {
+            0
+        };
+        self.fee_window_fees = 0;
+        self.fee_window_start = now - elapsed % FEE_WINDOW_SECS;
+    }
+}
+
+fn weight_fees(fees: u64, remaining_secs: i64) -> u64 {
+    ((fees as u128) * (remaining_secs.max(0) as u128) / FEE_WINDOW_SECS as u128) as u64
 }
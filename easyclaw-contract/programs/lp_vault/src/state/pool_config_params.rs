@@ -1,31 +1,108 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::BPS_DENOM, error::ErrorCode};
+use crate::{
+    constants::{
+        BPS_DENOM, MAX_CONFIG_DELAY_SECS, MAX_INSTANT_WITHDRAW_FEE_BPS, MAX_LP_REFERRAL_FEE_BPS,
+        MAX_MANAGEMENT_FEE_BPS, MAX_PERFORMANCE_FEE_BPS,
+    },
+    error::ErrorCode,
+    state::FeeSplitCurve,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
 pub struct PoolConfigParams {
     pub cooldown_secs: i64,
-    pub min_liquidity_buffer_bps: u16,
-    pub lp_fee_bps: u16,
-    pub insurance_fee_bps: u16,
-    pub protocol_fee_bps: u16,
+    // Bps of open interest; may exceed 100%.
+    pub liquidity_buffer_oi_bps: u16,
+    // Utilization-driven split. The base split it starts from at zero
+    // utilization is not configured here: it follows the registry fee split
+    // through `sync_fee_split`.
+    pub fee_curve: FeeSplitCurve,
     pub execution_rebate_usdc: u64,
+    // Pays keepers this share of the fill fee, capped at the flat rebate;
+    // zero keeps the flat rebate.
+    pub execution_rebate_fee_bps: u16,
+    // Annualized, charged by minting shares to the treasury.
+    pub management_fee_bps: u16,
+    // Share of LP profit above the share price high-water mark.
+    pub performance_fee_bps: u16,
+    // USDC token account that treasury fee shares are redeemed to.
+    pub treasury: Pubkey,
+    // Caps on pool liquidity and on one position's value after a deposit;
+    // zero disables the cap.
+    pub pool_deposit_cap: u64,
+    pub position_deposit_cap: u64,
+    // Haircut kept by the pool when an LP skips the cooldown; zero disables
+    // instant withdrawals.
+    pub instant_withdraw_fee_bps: u16,
+    // Insurance vault bounds for `rebalance_insurance`, in bps of open interest.
+    pub min_insurance_oi_bps: u16,
+    pub max_insurance_oi_bps: u16,
+    // Share of insurance income paid to insurance stakers.
+    pub insurance_staker_fee_bps: u16,
+    // Delay before the next proposal can be executed; changing it is itself
+    // subject to the current delay.
+    pub config_delay_secs: i64,
+    // Per-liquidation cap on the keeper reward; the excess stays with protocol
+    // fees. Zero leaves it uncapped.
+    pub max_liquidation_reward: u64,
+    // Pauses withdrawals once traders gain this share of the NAV against the
+    // pool within one window; zero disables the breaker.
+    pub circuit_breaker_drawdown_bps: u16,
+    pub circuit_breaker_window_secs: i64,
+    // Share of a referred LP's fee income accrued to their referrer out of
+    // protocol fees.
+    pub referral_fee_bps: u16,
+    // Insurance the vault must hold, in bps of open interest, before
+    // order_engine lets open interest grow; zero disables the check.
+    pub min_insurance_coverage_bps: u16,
 }
 
 impl PoolConfigParams {
     pub fn validate(&self) -> Result<()> {
         require!(self.cooldown_secs >= 0, ErrorCode::InvalidCooldown);
+        self.fee_curve.validate()?;
         require!(
-            self.min_liquidity_buffer_bps <= BPS_DENOM as u16,
+            self.execution_rebate_fee_bps <= BPS_DENOM as u16,
             ErrorCode::InvalidBps
         );
-
-        let sum = self
-            .lp_fee_bps
-            .checked_add(self.insurance_fee_bps)
-            .and_then(|x| x.checked_add(self.protocol_fee_bps))
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-        require!(sum == BPS_DENOM as u16, ErrorCode::InvalidBps);
+        require!(
+            self.management_fee_bps <= MAX_MANAGEMENT_FEE_BPS,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.performance_fee_bps <= MAX_PERFORMANCE_FEE_BPS,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.instant_withdraw_fee_bps <= MAX_INSTANT_WITHDRAW_FEE_BPS,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.min_insurance_oi_bps <= self.max_insurance_oi_bps,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.insurance_staker_fee_bps <= BPS_DENOM as u16,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.referral_fee_bps <= MAX_LP_REFERRAL_FEE_BPS,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            self.circuit_breaker_drawdown_bps == 0 || self.circuit_breaker_window_secs > 0,
+            ErrorCode::InvalidCooldown
+        );
+        require!(
+            (0..=MAX_CONFIG_DELAY_SECS).contains(&self.config_delay_secs),
+            ErrorCode::InvalidCooldown
+        );
+        require!(
+            (self.management_fee_bps == 0 && self.performance_fee_bps == 0)
+                || self.treasury != Pubkey::default(),
+            ErrorCode::InvalidTreasury
+        );
 
         Ok(())
     }
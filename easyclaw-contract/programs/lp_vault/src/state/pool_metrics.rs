@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BPS_DENOM, POOL_METRICS_SAMPLES, SECONDS_PER_YEAR},
+    error::ErrorCode,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SharePriceSample {
+    pub share_price: u128,
+    pub timestamp: i64,
+}
+
+// Snapshot of pool flows plus a ring of share price samples, advanced by the
+// permissionless `record_pool_metrics` crank so yield can be checked on-chain.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolMetrics {
+    pub pool: Pubkey,
+    pub cumulative_deposits: u64,
+    pub cumulative_withdrawals: u64,
+    pub cumulative_lp_fees: u64,
+    pub realized_trader_pnl: i128,
+    pub peak_share_price: u128,
+    pub max_drawdown_bps: u16,
+    pub samples: [SharePriceSample; POOL_METRICS_SAMPLES],
+    pub next_sample: u8,
+    pub sample_count: u8,
+    pub bump: u8,
+}
+
+impl PoolMetrics {
+    pub fn latest_sample(&self) -> Option<SharePriceSample> {
+        if self.sample_count == 0 {
+            return None;
+        }
+        let index = (self.next_sample as usize + POOL_METRICS_SAMPLES - 1) % POOL_METRICS_SAMPLES;
+        Some(self.samples[index])
+    }
+
+    pub fn oldest_sample(&self) -> Option<SharePriceSample> {
+        if self.sample_count == 0 {
+            return None;
+        }
+        let index = if (self.sample_count as usize) < POOL_METRICS_SAMPLES {
+            0
+        } else {
+            self.next_sample as usize
+        };
+        Some(self.samples[index])
+    }
+
+    pub fn record_sample(&mut self, share_price: u128, now: i64) -> Result<()> {
+        self.samples[self.next_sample as usize] = SharePriceSample {
+            share_price,
+            timestamp: now,
+        };
+        self.next_sample = ((self.next_sample as usize + 1) % POOL_METRICS_SAMPLES) as u8;
+        self.sample_count = (self.sample_count as usize + 1).min(POOL_METRICS_SAMPLES) as u8;
+
+        if share_price >= self.peak_share_price {
+            self.peak_share_price = share_price;
+            return Ok(());
+        }
+        let drawdown_bps = (self.peak_share_price - share_price)
+            .checked_mul(BPS_DENOM as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / self.peak_share_price;
+        self.max_drawdown_bps = self.max_drawdown_bps.max(drawdown_bps as u16);
+        Ok(())
+    }
+
+    // Simple (non-compounded) annualized return between the oldest and newest
+    // samples in the ring, in bps. Zero until two samples exist.
+    pub fn trailing_apy_bps(&self) -> Result<i64> {
+        let (Some(oldest), Some(latest)) = (self.oldest_sample(), self.latest_sample()) else {
+            return Ok(0);
+        };
+        let elapsed = latest.timestamp.saturating_sub(oldest.timestamp);
+        if elapsed <= 0 || oldest.share_price == 0 {
+            return Ok(0);
+        }
+        let change = latest.share_price as i128 - oldest.share_price as i128;
+        let apy_bps = change
+            .checked_mul(BPS_DENOM as i128 * SECONDS_PER_YEAR as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / (oldest.share_price as i128 * elapsed as i128);
+        i64::try_from(apy_bps).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PoolVault {
+    Liquidity,
+    Insurance,
+    ProtocolFee,
+}
+
+impl PoolVault {
+    pub fn address(self, pool: &Pool) -> Pubkey {
+        match self {
+            PoolVault::Liquidity => pool.liquidity_vault,
+            PoolVault::Insurance => pool.insurance_vault,
+            PoolVault::ProtocolFee => pool.protocol_fee_vault,
+        }
+    }
+
+    pub fn authority_seed(self) -> &'static [u8] {
+        match self {
+            PoolVault::Liquidity => b"liquidity-auth",
+            PoolVault::Insurance => b"insurance-auth",
+            PoolVault::ProtocolFee => b"protocol-fee-auth",
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RebalanceDirection {
+    InsuranceToLiquidity,
+    LiquidityToInsurance,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// Returned by `view_share_price`. Values match what a claim would pay right now.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SharePriceView {
+    // NAV per share, scaled by SHARE_PRICE_SCALE.
+    pub share_price: u128,
+    pub net_asset_value: u64,
+    pub total_shares: u128,
+    pub shares: u128,
+    pub pending_shares: u128,
+    // Value of `shares + pending_shares` at NAV.
+    pub redeemable_value: u64,
+}
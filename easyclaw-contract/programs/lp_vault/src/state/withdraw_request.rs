@@ -10,4 +10,9 @@ pub struct WithdrawRequest {
     pub requested_at: i64,
     pub claimed: bool,
     pub bump: u8,
+    // Set when a claim hit the liquidity buffer; the request then waits at
+    // `queue_position` for `process_withdraw_queue`.
+    pub queued: bool,
+    pub queue_position: u64,
+    pub min_amount_out: u64,
 }
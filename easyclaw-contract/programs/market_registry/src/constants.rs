@@ -1,2 +1,17 @@
-pub const MAX_KEEPERS: usize = 64;
+pub const MAX_MARKETS: usize = 128;
+// Parameter changes kept per market in its `MarketParamHistory`.
+pub const MAX_PARAM_HISTORY: usize = 32;
+// IMR and MMR may each move at most this far within one rate-limit window, so
+// a compromised admin key cannot mass-liquidate users in one step.
+pub const MAX_MARGIN_CHANGE_BPS: u16 = 500;
+pub const RISK_CHANGE_WINDOW_SECS: i64 = 86_400;
+// Notice given before a new `FeeSplit` can be executed.
+pub const FEE_SPLIT_DELAY_SECS: i64 = 86_400;
 pub const SYMBOL_LEN: usize = 16;
+pub const MAX_OPERATOR_NAME_LEN: usize = 32;
+pub const MAX_CONTACT_URI_LEN: usize = 128;
+// Keepers are expected to heartbeat this often; one that misses this many in a
+// row may be pruned, and when every keeper has, the engine's dead-man switch
+// opens liquidations to anyone.
+pub const KEEPER_HEARTBEAT_INTERVAL_SECS: i64 = 60;
+pub const MAX_MISSED_HEARTBEATS: i64 = 10;
@@ -8,12 +8,6 @@ pub enum ErrorCode {
     InvalidSymbolLength,
     #[msg("Global pause is enabled")]
     GlobalPaused,
-    #[msg("Keeper set is full")]
-    KeeperSetFull,
-    #[msg("Keeper already exists")]
-    KeeperAlreadyExists,
-    #[msg("Keeper not found")]
-    KeeperNotFound,
     #[msg("Invalid keeper set")]
     InvalidKeeperSet,
     #[msg("Invalid fee split")]
@@ -26,6 +20,34 @@ pub enum ErrorCode {
     InvalidFundingParams,
     #[msg("Invalid fee params")]
     InvalidFeeParams,
+    #[msg("Invalid oracle config")]
+    InvalidOracleConfig,
     #[msg("Math overflow")]
     MathOverflow,
+    #[msg("Invalid parameter ramp")]
+    InvalidRamp,
+    #[msg("Market list is full")]
+    MarketListFull,
+    #[msg("Market is already listed")]
+    MarketAlreadyListed,
+    #[msg("Market is not in the market list")]
+    MarketNotListed,
+    #[msg("Risk parameter change exceeds the per-window limit")]
+    RiskChangeTooLarge,
+    #[msg("No fee split change is pending")]
+    NoPendingFeeSplit,
+    #[msg("Fee split change is still timelocked")]
+    FeeSplitTimelockActive,
+    #[msg("Invalid keeper metadata")]
+    InvalidKeeperMetadata,
+    #[msg("Keeper has not missed enough heartbeats to be pruned")]
+    KeeperStillLive,
+    #[msg("Keeper set is already in the current layout")]
+    KeeperSetAlreadyMigrated,
+    #[msg("Migration accounts do not match the keeper set")]
+    InvalidMigrationAccounts,
+    #[msg("Global config is already in the current layout")]
+    GlobalConfigAlreadyMigrated,
+    #[msg("Invalid market id")]
+    InvalidMarketId,
 }
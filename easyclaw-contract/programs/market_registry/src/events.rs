@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    FeeParams, FundingParams, KeeperScopes, MarketStatus, PricingParams, RiskParams,
+};
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub symbol: String,
+    pub pyth_feed: Pubkey,
+    pub risk_params: RiskParams,
+    pub pricing_params: PricingParams,
+    pub funding_params: FundingParams,
+    pub fee_params: FeeParams,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskParamsUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub before: RiskParams,
+    pub after: RiskParams,
+    pub params_version: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PricingParamsUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub before: PricingParams,
+    pub after: PricingParams,
+    pub params_version: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundingParamsUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub before: FundingParams,
+    pub after: FundingParams,
+    pub params_version: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeParamsUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub before: FeeParams,
+    pub after: FeeParams,
+    pub params_version: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketStatusChanged {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub before: MarketStatus,
+    pub after: MarketStatus,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalPauseSet {
+    pub before: bool,
+    pub after: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperAdded {
+    pub keeper: Pubkey,
+    pub scopes: KeeperScopes,
+    pub keeper_count: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// `pruned` is set when a stale keeper was dropped by `prune_keeper`; the
+// authority is then whoever cranked it.
+#[event]
+pub struct KeeperRemoved {
+    pub keeper: Pubkey,
+    pub scopes: KeeperScopes,
+    pub keeper_count: u32,
+    pub pruned: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+// One `migrate_keeper_set` batch; `remaining` reaching zero marks the set as
+// migrated.
+#[event]
+pub struct KeeperSetMigrated {
+    pub migrated: u32,
+    pub remaining: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalConfigMigrated {
+    pub next_market_id: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperScopesUpdated {
+    pub keeper: Pubkey,
+    pub before: KeeperScopes,
+    pub after: KeeperScopes,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
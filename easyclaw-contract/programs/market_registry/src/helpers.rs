@@ -1,4 +1,4 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 
 use crate::{
     constants::SYMBOL_LEN,
@@ -24,6 +24,55 @@ pub fn require_admin(
     Ok(())
 }
 
+// The guardian may only pause; unpausing and everything else needs the
+// multisig.
+pub fn require_pause_authority(
+    authority: &Signer<'_>,
+    global_config: &Account<GlobalConfig>,
+    keeper_set: &Account<KeeperSet>,
+    pausing: bool,
+) -> Result<()> {
+    if pausing && global_config.guardian == Some(authority.key()) {
+        require_keys_eq!(
+            keeper_set.key(),
+            global_config.keeper_set,
+            ErrorCode::InvalidKeeperSet
+        );
+        return Ok(());
+    }
+    require_admin(authority, global_config, keeper_set)
+}
+
+// Resizes an account up to `new_len`, topping up rent from `payer`. Used by
+// layout migrations; new bytes are zeroed by the runtime.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
 pub fn to_fixed_symbol(symbol: &str) -> Result<[u8; SYMBOL_LEN]> {
     let bytes = symbol.as_bytes();
     require!(
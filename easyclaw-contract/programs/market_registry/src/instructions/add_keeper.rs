@@ -1,33 +1,63 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::MAX_KEEPERS,
     error::ErrorCode,
+    events::KeeperAdded,
     helpers::require_admin,
-    state::{GlobalConfig, KeeperSet},
+    state::{
+        GlobalConfig, KeeperAuthorization, KeeperMetadata, KeeperMetadataParams, KeeperScopes,
+        KeeperSet,
+    },
 };
 
-pub fn handler(ctx: Context<AddKeeper>, keeper: Pubkey) -> Result<()> {
+pub fn handler(
+    ctx: Context<AddKeeper>,
+    keeper: Pubkey,
+    metadata: KeeperMetadataParams,
+    scopes: KeeperScopes,
+) -> Result<()> {
     require_admin(
         &ctx.accounts.authority,
         &ctx.accounts.global_config,
         &ctx.accounts.keeper_set,
     )?;
+    metadata.validate()?;
 
     let set = &mut ctx.accounts.keeper_set;
-    require!(set.keepers.len() < MAX_KEEPERS, ErrorCode::KeeperSetFull);
-    require!(
-        !set.keepers.contains(&keeper),
-        ErrorCode::KeeperAlreadyExists
-    );
+    set.keeper_count = set
+        .keeper_count
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    set.keepers.push(keeper);
-    ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
+    let now = Clock::get()?.unix_timestamp;
+    let auth = &mut ctx.accounts.keeper_auth;
+    auth.keeper = keeper;
+    auth.scopes = scopes;
+    auth.added_at = now;
+    auth.bump = ctx.bumps.keeper_auth;
+
+    let meta = &mut ctx.accounts.keeper_metadata;
+    meta.keeper = keeper;
+    meta.registered_at = now;
+    // Counts as the first heartbeat so a new keeper is not prunable at once.
+    meta.last_heartbeat_at = now;
+    meta.bump = ctx.bumps.keeper_metadata;
+    meta.apply(metadata, now);
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(KeeperAdded {
+        keeper,
+        scopes,
+        keeper_count: ctx.accounts.keeper_set.keeper_count,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
 pub struct AddKeeper<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -43,4 +73,22 @@ pub struct AddKeeper<'info> {
         bump = keeper_set.bump,
     )]
     pub keeper_set: Account<'info, KeeperSet>,
+    // Already in use if the keeper is registered.
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"keeper-auth", keeper.as_ref()],
+        bump,
+        space = 8 + KeeperAuthorization::INIT_SPACE,
+    )]
+    pub keeper_auth: Account<'info, KeeperAuthorization>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"keeper-meta", keeper.as_ref()],
+        bump,
+        space = 8 + KeeperMetadata::INIT_SPACE,
+    )]
+    pub keeper_metadata: Account<'info, KeeperMetadata>,
+    pub system_program: Program<'info, System>,
 }
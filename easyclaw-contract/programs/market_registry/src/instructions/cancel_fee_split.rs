@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperSet},
+};
+
+pub fn handler(ctx: Context<CancelFeeSplit>) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+
+    let global = &mut ctx.accounts.global_config;
+    require!(
+        global.pending_fee_split.is_some(),
+        ErrorCode::NoPendingFeeSplit
+    );
+    global.pending_fee_split = None;
+    global.pending_fee_split_eta = 0;
+    global.last_updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelFeeSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+}
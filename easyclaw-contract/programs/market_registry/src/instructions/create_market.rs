@@ -2,16 +2,16 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::ErrorCode,
+    events::MarketCreated,
     helpers::{require_admin, to_fixed_symbol},
     state::{
-        FeeParams, FundingParams, GlobalConfig, KeeperSet, Market, MarketStatus, PricingParams,
-        RiskParams,
+        FeeParams, FundingParams, GlobalConfig, KeeperSet, Market, MarketList, MarketParamHistory,
+        MarketRamps, MarketStatus, OracleConfig, OracleSource, PricingParams, RiskParams,
     },
 };
 
 pub fn handler(
     ctx: Context<CreateMarket>,
-    market_id: u64,
     symbol: String,
     pyth_feed: Pubkey,
     risk_params: RiskParams,
@@ -34,22 +34,62 @@ pub fn handler(
     funding_params.validate()?;
     fee_params.validate()?;
 
+    let global = &mut ctx.accounts.global_config;
+    let market_id = global.next_market_id;
+    global.next_market_id = market_id
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
     let market = &mut ctx.accounts.market;
     market.market_id = market_id;
     market.symbol = to_fixed_symbol(&symbol)?;
     market.pyth_feed = pyth_feed;
+    market.oracle_config = OracleConfig {
+        source: OracleSource::PythPush,
+        switchboard_feed: Pubkey::default(),
+        lazer_feed_id: 0,
+        lazer_signer: Pubkey::default(),
+        median_feeds: [Pubkey::default(); 3],
+        allow_keeper_fallback: false,
+        fallback_signer: Pubkey::default(),
+    };
     market.status = MarketStatus::Active;
     market.risk_params = risk_params;
     market.pricing_params = pricing_params;
     market.funding_params = funding_params;
     market.fee_params = fee_params;
+    market.ramps = MarketRamps::default();
+    market.params_version = 0;
     market.bump = ctx.bumps.market;
+    let now = Clock::get()?.unix_timestamp;
+    market.reset_risk_window(now);
+    ctx.accounts
+        .market_list
+        .insert(market_id, MarketStatus::Active)?;
+
+    let history = &mut ctx.accounts.param_history;
+    history.market_id = market_id;
+    history.total_changes = 0;
+    history.records = Vec::new();
+    history.bump = ctx.bumps.param_history;
+
+    emit!(MarketCreated {
+        market: market.key(),
+        market_id,
+        symbol,
+        pyth_feed,
+        risk_params,
+        pricing_params,
+        funding_params,
+        fee_params,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(market_id: u64)]
 pub struct CreateMarket<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -67,10 +107,24 @@ pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = authority,
-        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds = [b"market".as_ref(), &global_config.next_market_id.to_le_bytes()],
         bump,
         space = 8 + Market::INIT_SPACE,
     )]
     pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"param-history".as_ref(), &global_config.next_market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketParamHistory::INIT_SPACE,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+    #[account(
+        mut,
+        seeds = [b"market-list"],
+        bump = market_list.bump,
+    )]
+    pub market_list: Account<'info, MarketList>,
     pub system_program: Program<'info, System>,
 }
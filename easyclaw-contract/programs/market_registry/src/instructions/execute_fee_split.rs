@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperSet},
+};
+
+// order_engine picks the new split up on its next fill and syncs it into the
+// LP pool.
+pub fn handler(ctx: Context<ExecuteFeeSplit>) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let global = &mut ctx.accounts.global_config;
+    let fee_split = global
+        .pending_fee_split
+        .ok_or_else(|| error!(ErrorCode::NoPendingFeeSplit))?;
+    require!(
+        now >= global.pending_fee_split_eta,
+        ErrorCode::FeeSplitTimelockActive
+    );
+    global.fee_split = fee_split;
+    global.pending_fee_split = None;
+    global.pending_fee_split_eta = 0;
+    global.last_updated_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFeeSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+}
@@ -1,9 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{
-    constants::MAX_KEEPERS,
-    state::{FeeSplit, GlobalConfig, KeeperSet},
-};
+use crate::state::{FeeSplit, GlobalConfig, KeeperSet};
 
 pub fn handler(
     ctx: Context<InitializeGlobal>,
@@ -22,11 +19,15 @@ pub fn handler(
     global.created_at = now;
     global.last_updated_at = now;
     global.bump = ctx.bumps.global_config;
+    global.guardian = None;
+    global.next_market_id = 1;
+    global.pending_fee_split = None;
+    global.pending_fee_split_eta = 0;
 
     let keeper_set = &mut ctx.accounts.keeper_set;
     keeper_set.authority = multisig;
-    keeper_set.keepers = Vec::with_capacity(MAX_KEEPERS);
     keeper_set.bump = ctx.bumps.keeper_set;
+    keeper_set.keeper_count = 0;
 
     Ok(())
 }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperLiveness, KeeperSet},
+};
+
+pub fn handler(ctx: Context<InitializeKeeperLiveness>) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+
+    let liveness = &mut ctx.accounts.keeper_liveness;
+    liveness.last_heartbeat_at = 0;
+    liveness.bump = ctx.bumps.keeper_liveness;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeKeeperLiveness<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"keeper-liveness"],
+        bump,
+        space = 8 + KeeperLiveness::INIT_SPACE,
+    )]
+    pub keeper_liveness: Account<'info, KeeperLiveness>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperSet, Market, MarketList},
+};
+
+// Markets created before the list existed are passed as remaining accounts and
+// listed in the order given.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeMarketList<'info>>,
+) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+
+    let list = &mut ctx.accounts.market_list;
+    list.markets = Vec::new();
+    list.bump = ctx.bumps.market_list;
+    for info in ctx.remaining_accounts {
+        let market = Account::<Market>::try_from(info)?;
+        list.insert(market.market_id, market.status)?;
+    }
+    ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMarketList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"market-list"],
+        bump,
+        space = 8 + MarketList::INIT_SPACE,
+    )]
+    pub market_list: Account<'info, MarketList>,
+    pub system_program: Program<'info, System>,
+}
@@ -2,40 +2,30 @@ use anchor_lang::prelude::*;
 
 use crate::{
     helpers::require_admin,
-    state::{FeeParams, FundingParams, GlobalConfig, KeeperSet, Market, PricingParams, RiskParams},
+    state::{GlobalConfig, KeeperSet, Market, MarketParamHistory},
 };
 
-pub fn handler(
-    ctx: Context<UpdateMarketParams>,
-    risk_params: RiskParams,
-    pricing_params: PricingParams,
-    funding_params: FundingParams,
-    fee_params: FeeParams,
-) -> Result<()> {
+// For markets created before parameter history existed; newer markets get
+// theirs in `create_market`.
+pub fn handler(ctx: Context<InitializeParamHistory>) -> Result<()> {
     require_admin(
         &ctx.accounts.authority,
         &ctx.accounts.global_config,
         &ctx.accounts.keeper_set,
     )?;
 
-    risk_params.validate()?;
-    pricing_params.validate()?;
-    funding_params.validate()?;
-    fee_params.validate()?;
-
-    let market = &mut ctx.accounts.market;
-    market.risk_params = risk_params;
-    market.pricing_params = pricing_params;
-    market.funding_params = funding_params;
-    market.fee_params = fee_params;
-
+    let history = &mut ctx.accounts.param_history;
+    history.market_id = ctx.accounts.market.market_id;
+    history.total_changes = 0;
+    history.records = Vec::new();
+    history.bump = ctx.bumps.param_history;
     ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct UpdateMarketParams<'info> {
+pub struct InitializeParamHistory<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
@@ -50,9 +40,17 @@ pub struct UpdateMarketParams<'info> {
     )]
     pub keeper_set: Account<'info, KeeperSet>,
     #[account(
-        mut,
         seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
         bump = market.bump,
     )]
     pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"param-history".as_ref(), &market.market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketParamHistory::INIT_SPACE,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+    pub system_program: Program<'info, System>,
 }
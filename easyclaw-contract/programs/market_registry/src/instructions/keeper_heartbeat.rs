@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{KeeperAuthorization, KeeperLiveness, KeeperMetadata};
+
+pub fn handler(ctx: Context<KeeperHeartbeat>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.keeper_metadata.last_heartbeat_at = now;
+    ctx.accounts.keeper_liveness.last_heartbeat_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct KeeperHeartbeat<'info> {
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"keeper-liveness"],
+        bump = keeper_liveness.bump,
+    )]
+    pub keeper_liveness: Account<'info, KeeperLiveness>,
+    #[account(
+        seeds = [b"keeper-auth", keeper.key().as_ref()],
+        bump = keeper_auth.bump,
+    )]
+    pub keeper_auth: Account<'info, KeeperAuthorization>,
+    #[account(
+        mut,
+        seeds = [b"keeper-meta", keeper.key().as_ref()],
+        bump = keeper_metadata.bump,
+    )]
+    pub keeper_metadata: Account<'info, KeeperMetadata>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode, events::GlobalConfigMigrated, helpers::grow_account, state::GlobalConfig,
+};
+
+// Grows a global config created before the guardian, market id counter, fee
+// split timelock and later fields were appended. The new bytes read as zero
+// (no guardian, nothing pending); `next_market_id` must be above every market
+// id already in use. Run this before any other upgraded instruction, since
+// they all load the config in the current layout.
+pub fn handler(ctx: Context<MigrateGlobalConfig>, next_market_id: u64) -> Result<()> {
+    require!(next_market_id > 0, ErrorCode::InvalidMarketId);
+    let info = ctx.accounts.global_config.to_account_info();
+    let mut global = migrated_global_config(&info.try_borrow_data()?)?;
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        global.multisig,
+        ErrorCode::Unauthorized
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    global.next_market_id = next_market_id;
+    global.last_updated_at = now;
+
+    grow_account(
+        &info,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        8 + GlobalConfig::INIT_SPACE,
+    )?;
+    global.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(GlobalConfigMigrated {
+        next_market_id,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// The config in `data` zero-extended to the current layout, or
+// `GlobalConfigAlreadyMigrated` once it is already that size.
+pub fn migrated_global_config(data: &[u8]) -> Result<GlobalConfig> {
+    require!(
+        data.len() >= 8 && data[..8] == *GlobalConfig::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(
+        data.len() < 8 + GlobalConfig::INIT_SPACE,
+        ErrorCode::GlobalConfigAlreadyMigrated
+    );
+    let mut padded = data.to_vec();
+    padded.resize(8 + GlobalConfig::INIT_SPACE, 0);
+    GlobalConfig::try_deserialize(&mut &padded[..])
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: the deployed layout does not deserialize as `GlobalConfig`; the
+    /// address is pinned by its seeds and the discriminator checked by
+    /// `migrated_global_config`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"global-config"],
+        bump,
+    )]
+    pub global_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
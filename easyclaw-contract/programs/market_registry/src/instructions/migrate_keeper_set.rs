@@ -0,0 +1,201 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+
+use crate::{
+    error::ErrorCode,
+    events::KeeperSetMigrated,
+    state::{
+        GlobalConfig, KeeperAuthorization, KeeperMetadata, KeeperScopes, KeeperSet, LegacyKeeperSet,
+    },
+};
+
+// Moves keepers from the legacy inline list onto `KeeperAuthorization` PDAs,
+// in batches taken from the end of the list. Remaining accounts are
+// `[keeper_auth, keeper_metadata]` pairs for those keepers, in list order.
+// Legacy keepers predate scopes, so each gets all of them, and one without
+// metadata gets a blank `KeeperMetadata` to fill in with
+// `update_keeper_metadata`. The batch that empties the list rewrites the set in
+// the current layout. Runs after `migrate_global_config`.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateKeeperSet<'info>>) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        ctx.accounts.global_config.multisig,
+        ErrorCode::Unauthorized
+    );
+
+    let info = ctx.accounts.keeper_set.to_account_info();
+    let mut set = read_legacy_keeper_set(&info.try_borrow_data()?)?;
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        set.authority,
+        ErrorCode::Unauthorized
+    );
+
+    let pairs = ctx.remaining_accounts;
+    let batch = pairs.len() / 2;
+    require!(
+        pairs.len().is_multiple_of(2) && batch <= set.keepers.len(),
+        ErrorCode::InvalidMigrationAccounts
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let first = set.keepers.len() - batch;
+    for (i, group) in pairs.chunks(2).enumerate() {
+        let keeper = set.keepers[first + i];
+
+        let (auth_key, auth_bump) =
+            Pubkey::find_program_address(&[b"keeper-auth", keeper.as_ref()], &crate::ID);
+        require_keys_eq!(
+            group[0].key(),
+            auth_key,
+            ErrorCode::InvalidMigrationAccounts
+        );
+        create_keeper_account(
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            &group[0],
+            &[b"keeper-auth", keeper.as_ref(), &[auth_bump]],
+            8 + KeeperAuthorization::INIT_SPACE,
+        )?;
+        let auth = KeeperAuthorization {
+            keeper,
+            scopes: KeeperScopes::ALL,
+            added_at: now,
+            bump: auth_bump,
+        };
+        auth.try_serialize(&mut &mut group[0].try_borrow_mut_data()?[..])?;
+
+        let (meta_key, meta_bump) =
+            Pubkey::find_program_address(&[b"keeper-meta", keeper.as_ref()], &crate::ID);
+        require_keys_eq!(
+            group[1].key(),
+            meta_key,
+            ErrorCode::InvalidMigrationAccounts
+        );
+        if group[1].owner == &crate::ID {
+            Account::<KeeperMetadata>::try_from(&group[1])?;
+            continue;
+        }
+        create_keeper_account(
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            &group[1],
+            &[b"keeper-meta", keeper.as_ref(), &[meta_bump]],
+            8 + KeeperMetadata::INIT_SPACE,
+        )?;
+        let meta = KeeperMetadata {
+            keeper,
+            operator_name: String::new(),
+            contact_uri: String::new(),
+            payout_address: keeper,
+            registered_at: now,
+            updated_at: now,
+            bump: meta_bump,
+            // Counts as a heartbeat so the keeper is not prunable at once.
+            last_heartbeat_at: now,
+        };
+        meta.try_serialize(&mut &mut group[1].try_borrow_mut_data()?[..])?;
+    }
+
+    set.keepers.truncate(first);
+    set.migrated = set
+        .migrated
+        .checked_add(batch as u32)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    emit!(KeeperSetMigrated {
+        migrated: batch as u32,
+        remaining: set.keepers.len() as u32,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    if !set.keepers.is_empty() {
+        let mut data = info.try_borrow_mut_data()?;
+        return set.serialize(&mut &mut data[8..]).map_err(Into::into);
+    }
+
+    let current = KeeperSet {
+        authority: set.authority,
+        bump: set.bump,
+        keeper_count: set.migrated,
+    };
+    let new_len = 8 + KeeperSet::INIT_SPACE;
+    current.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    info.resize(new_len)?;
+    let excess = info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(new_len));
+    **info.try_borrow_mut_lamports()? -= excess;
+    **ctx
+        .accounts
+        .authority
+        .to_account_info()
+        .try_borrow_mut_lamports()? += excess;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    Ok(())
+}
+
+// The legacy set in `data`, or `KeeperSetAlreadyMigrated` once the set is in
+// the current layout.
+pub fn read_legacy_keeper_set(data: &[u8]) -> Result<LegacyKeeperSet> {
+    require!(
+        data.len() >= 8 && data[..8] == *KeeperSet::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(
+        data.len() > 8 + KeeperSet::INIT_SPACE,
+        ErrorCode::KeeperSetAlreadyMigrated
+    );
+    // A full legacy set leaves no tail for the migration count.
+    let mut padded = data[8..].to_vec();
+    padded.extend_from_slice(&[0; 4]);
+    Ok(LegacyKeeperSet::deserialize(&mut &padded[..])?)
+}
+
+fn create_keeper_account<'info>(
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    target: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: target.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )
+}
+
+#[derive(Accounts)]
+pub struct MigrateKeeperSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    /// CHECK: the legacy layout does not deserialize as `KeeperSet`; the
+    /// address is pinned here and the discriminator checked by
+    /// `read_legacy_keeper_set`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        address = global_config.keeper_set @ ErrorCode::InvalidKeeperSet,
+    )]
+    pub keeper_set: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,15 +1,51 @@
 pub mod add_keeper;
+pub mod cancel_fee_split;
 pub mod create_market;
+pub mod execute_fee_split;
 pub mod initialize_global;
+pub mod initialize_keeper_liveness;
+pub mod initialize_market_list;
+pub mod initialize_param_history;
+pub mod keeper_heartbeat;
+pub mod migrate_global_config;
+pub mod migrate_keeper_set;
+pub mod propose_fee_split;
+pub mod prune_keeper;
 pub mod remove_keeper;
+pub mod schedule_param_ramp;
 pub mod set_global_pause;
+pub mod set_guardian;
+pub mod set_keeper_scopes;
+pub mod set_market_oracle;
 pub mod set_market_status;
-pub mod update_market_params;
+pub mod update_fee_params;
+pub mod update_funding_params;
+pub mod update_keeper_metadata;
+pub mod update_pricing_params;
+pub mod update_risk_params;
 
 pub use add_keeper::*;
+pub use cancel_fee_split::*;
 pub use create_market::*;
+pub use execute_fee_split::*;
 pub use initialize_global::*;
+pub use initialize_keeper_liveness::*;
+pub use initialize_market_list::*;
+pub use initialize_param_history::*;
+pub use keeper_heartbeat::*;
+pub use migrate_global_config::*;
+pub use migrate_keeper_set::*;
+pub use propose_fee_split::*;
+pub use prune_keeper::*;
 pub use remove_keeper::*;
+pub use schedule_param_ramp::*;
 pub use set_global_pause::*;
+pub use set_guardian::*;
+pub use set_keeper_scopes::*;
+pub use set_market_oracle::*;
 pub use set_market_status::*;
-pub use update_market_params::*;
+pub use update_fee_params::*;
+pub use update_funding_params::*;
+pub use update_keeper_metadata::*;
+pub use update_pricing_params::*;
+pub use update_risk_params::*;
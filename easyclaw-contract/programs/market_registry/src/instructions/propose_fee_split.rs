@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::FEE_SPLIT_DELAY_SECS,
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{FeeSplit, GlobalConfig, KeeperSet},
+};
+
+// Replaces any pending proposal; the new split takes effect once
+// `execute_fee_split` runs after the delay.
+pub fn handler(ctx: Context<ProposeFeeSplit>, fee_split: FeeSplit) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    fee_split.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let global = &mut ctx.accounts.global_config;
+    global.pending_fee_split = Some(fee_split);
+    global.pending_fee_split_eta = now
+        .checked_add(FEE_SPLIT_DELAY_SECS)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    global.last_updated_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeFeeSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{KEEPER_HEARTBEAT_INTERVAL_SECS, MAX_MISSED_HEARTBEATS},
+    error::ErrorCode,
+    events::KeeperRemoved,
+    state::{GlobalConfig, KeeperAuthorization, KeeperMetadata, KeeperSet},
+};
+
+// Permissionless: drops a keeper that has missed too many heartbeats. The
+// rent goes back to the multisig, which paid for it.
+pub fn handler(ctx: Context<PruneKeeper>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let deadline = ctx
+        .accounts
+        .keeper_metadata
+        .last_heartbeat_at
+        .saturating_add(KEEPER_HEARTBEAT_INTERVAL_SECS.saturating_mul(MAX_MISSED_HEARTBEATS));
+    require!(now > deadline, ErrorCode::KeeperStillLive);
+
+    let set = &mut ctx.accounts.keeper_set;
+    set.keeper_count = set
+        .keeper_count
+        .checked_sub(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(KeeperRemoved {
+        keeper: ctx.accounts.keeper_auth.keeper,
+        scopes: ctx.accounts.keeper_auth.scopes,
+        keeper_count: ctx.accounts.keeper_set.keeper_count,
+        pruned: true,
+        authority: ctx.accounts.caller.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PruneKeeper<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        address = global_config.keeper_set @ ErrorCode::InvalidKeeperSet,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        close = multisig,
+        seeds = [b"keeper-auth", keeper_metadata.keeper.as_ref()],
+        bump = keeper_auth.bump,
+    )]
+    pub keeper_auth: Account<'info, KeeperAuthorization>,
+    #[account(
+        mut,
+        close = multisig,
+        seeds = [b"keeper-meta", keeper_metadata.keeper.as_ref()],
+        bump = keeper_metadata.bump,
+    )]
+    pub keeper_metadata: Account<'info, KeeperMetadata>,
+    /// CHECK: receives the metadata rent; must be the registry multisig.
+    #[account(mut, address = global_config.multisig @ ErrorCode::Unauthorized)]
+    pub multisig: UncheckedAccount<'info>,
+}
@@ -2,11 +2,12 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::ErrorCode,
+    events::KeeperRemoved,
     helpers::require_admin,
-    state::{GlobalConfig, KeeperSet},
+    state::{GlobalConfig, KeeperAuthorization, KeeperMetadata, KeeperSet},
 };
 
-pub fn handler(ctx: Context<RemoveKeeper>, keeper: Pubkey) -> Result<()> {
+pub fn handler(ctx: Context<RemoveKeeper>, _keeper: Pubkey) -> Result<()> {
     require_admin(
         &ctx.accounts.authority,
         &ctx.accounts.global_config,
@@ -14,19 +15,27 @@ pub fn handler(ctx: Context<RemoveKeeper>, keeper: Pubkey) -> Result<()> {
     )?;
 
     let set = &mut ctx.accounts.keeper_set;
-    let idx = set
-        .keepers
-        .iter()
-        .position(|k| *k == keeper)
-        .ok_or_else(|| error!(ErrorCode::KeeperNotFound))?;
+    set.keeper_count = set
+        .keeper_count
+        .checked_sub(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_config.last_updated_at = now;
 
-    set.keepers.swap_remove(idx);
-    ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
+    emit!(KeeperRemoved {
+        keeper: ctx.accounts.keeper_auth.keeper,
+        scopes: ctx.accounts.keeper_auth.scopes,
+        keeper_count: ctx.accounts.keeper_set.keeper_count,
+        pruned: false,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
 pub struct RemoveKeeper<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -42,4 +51,20 @@ pub struct RemoveKeeper<'info> {
         bump = keeper_set.bump,
     )]
     pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"keeper-auth", keeper.as_ref()],
+        bump = keeper_auth.bump,
+    )]
+    pub keeper_auth: Account<'info, KeeperAuthorization>,
+    // Every keeper has metadata: `add_keeper` creates it and
+    // `migrate_keeper_set` backfills it for keepers registered before it.
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"keeper-meta", keeper.as_ref()],
+        bump = keeper_metadata.bump,
+    )]
+    pub keeper_metadata: Account<'info, KeeperMetadata>,
 }
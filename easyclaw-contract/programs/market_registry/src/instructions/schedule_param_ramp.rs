@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{
+        BpsRamp, GlobalConfig, KeeperSet, Market, MarketParamHistory, ParamGroup, RampedParam,
+    },
+};
+
+pub fn handler(
+    ctx: Context<ScheduleParamRamp>,
+    param: RampedParam,
+    target_bps: u16,
+    duration_secs: i64,
+) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    require!(duration_secs > 0, ErrorCode::InvalidRamp);
+
+    let now = Clock::get()?.unix_timestamp;
+    let end_ts = now
+        .checked_add(duration_secs)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let market = &mut ctx.accounts.market;
+    let before = (market.risk_params, market.fee_params, market.ramps);
+    let mut risk_params = market.risk_params;
+    let mut fee_params = market.fee_params;
+    // The ramp starts from the value in effect now, so rescheduling mid-ramp
+    // does not jump.
+    let start_bps = match param {
+        RampedParam::ImrBps => {
+            risk_params.imr_bps = target_bps;
+            market.imr_bps_at(now)
+        }
+        RampedParam::MmrBps => {
+            risk_params.mmr_bps = target_bps;
+            market.mmr_bps_at(now)
+        }
+        RampedParam::TakerFeeBps => {
+            fee_params.taker_fee_bps = target_bps;
+            market.taker_fee_bps_at(now)
+        }
+    };
+    risk_params.validate()?;
+    fee_params.validate()?;
+    // A ramp ends at its target, so the target is held to the same limit as a
+    // direct update.
+    market.check_margin_change(now, risk_params.imr_bps, risk_params.mmr_bps)?;
+
+    let ramp = BpsRamp {
+        start_bps,
+        end_bps: target_bps,
+        start_ts: now,
+        end_ts,
+    };
+    match param {
+        RampedParam::ImrBps => market.ramps.imr_bps = ramp,
+        RampedParam::MmrBps => market.ramps.mmr_bps = ramp,
+        RampedParam::TakerFeeBps => market.ramps.taker_fee_bps = ramp,
+    }
+    market.risk_params = risk_params;
+    market.fee_params = fee_params;
+    market.params_version = market
+        .params_version
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    ctx.accounts.param_history.record(
+        ParamGroup::Ramp,
+        market.params_version,
+        ctx.accounts.authority.key(),
+        now,
+        &before,
+        &(market.risk_params, market.fee_params, market.ramps),
+    )?;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ScheduleParamRamp<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"param-history".as_ref(), &market.market_id.to_le_bytes()],
+        bump = param_history.bump,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+}
@@ -1,19 +1,31 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    helpers::require_admin,
+    events::GlobalPauseSet,
+    helpers::require_pause_authority,
     state::{GlobalConfig, KeeperSet},
 };
 
 pub fn handler(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
-    require_admin(
+    require_pause_authority(
         &ctx.accounts.authority,
         &ctx.accounts.global_config,
         &ctx.accounts.keeper_set,
+        paused,
     )?;
 
-    ctx.accounts.global_config.global_pause = paused;
-    ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
+    let now = Clock::get()?.unix_timestamp;
+    let global = &mut ctx.accounts.global_config;
+    let before = global.global_pause;
+    global.global_pause = paused;
+    global.last_updated_at = now;
+
+    emit!(GlobalPauseSet {
+        before,
+        after: paused,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::KeeperScopesUpdated,
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperAuthorization, KeeperScopes, KeeperSet},
+};
+
+pub fn handler(ctx: Context<SetKeeperScopes>, _keeper: Pubkey, scopes: KeeperScopes) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let auth = &mut ctx.accounts.keeper_auth;
+    let before = auth.scopes;
+    auth.scopes = scopes;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(KeeperScopesUpdated {
+        keeper: auth.keeper,
+        before,
+        after: scopes,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
+pub struct SetKeeperScopes<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"keeper-auth", keeper.as_ref()],
+        bump = keeper_auth.bump,
+    )]
+    pub keeper_auth: Account<'info, KeeperAuthorization>,
+}
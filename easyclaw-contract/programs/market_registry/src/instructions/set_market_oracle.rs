@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperSet, Market, MarketParamHistory, OracleConfig, ParamGroup},
+};
+
+pub fn handler(ctx: Context<SetMarketOracle>, oracle_config: OracleConfig) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    oracle_config.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let market = &mut ctx.accounts.market;
+    let before = market.oracle_config;
+    market.oracle_config = oracle_config;
+    market.params_version = market
+        .params_version
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.param_history.record(
+        ParamGroup::Oracle,
+        market.params_version,
+        ctx.accounts.authority.key(),
+        now,
+        &before,
+        &oracle_config,
+    )?;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"param-history".as_ref(), &market.market_id.to_le_bytes()],
+        bump = param_history.bump,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+}
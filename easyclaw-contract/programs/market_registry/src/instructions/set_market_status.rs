@@ -1,19 +1,41 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    helpers::require_admin,
-    state::{GlobalConfig, KeeperSet, Market, MarketStatus},
+    error::ErrorCode,
+    events::MarketStatusChanged,
+    helpers::require_pause_authority,
+    state::{GlobalConfig, KeeperSet, Market, MarketList, MarketStatus},
 };
 
 pub fn handler(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
-    require_admin(
+    require_pause_authority(
         &ctx.accounts.authority,
         &ctx.accounts.global_config,
         &ctx.accounts.keeper_set,
+        status == MarketStatus::Paused,
     )?;
 
-    ctx.accounts.market.status = status;
-    ctx.accounts.global_config.last_updated_at = Clock::get()?.unix_timestamp;
+    let market = &mut ctx.accounts.market;
+    let before = market.status;
+    market.status = status;
+    ctx.accounts
+        .market_list
+        .set_status(market.market_id, status)?;
+    market.params_version = market
+        .params_version
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(MarketStatusChanged {
+        market: market.key(),
+        market_id: market.market_id,
+        before,
+        after: status,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -39,4 +61,10 @@ pub struct SetMarketStatus<'info> {
         bump = market.bump,
     )]
     pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market-list"],
+        bump = market_list.bump,
+    )]
+    pub market_list: Account<'info, MarketList>,
 }
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    events::FundingParamsUpdated,
+    helpers::require_admin,
+    state::{FundingParams, GlobalConfig, KeeperSet, Market, MarketParamHistory, ParamGroup},
+};
+
+pub fn handler(ctx: Context<UpdateFundingParams>, funding_params: FundingParams) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    funding_params.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let market = &mut ctx.accounts.market;
+    let before = market.funding_params;
+    market.funding_params = funding_params;
+    market.params_version = market
+        .params_version
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    ctx.accounts.param_history.record(
+        ParamGroup::Funding,
+        market.params_version,
+        ctx.accounts.authority.key(),
+        now,
+        &before,
+        &funding_params,
+    )?;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(FundingParamsUpdated {
+        market: market.key(),
+        market_id: market.market_id,
+        before,
+        after: funding_params,
+        params_version: market.params_version,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFundingParams<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"param-history".as_ref(), &market.market_id.to_le_bytes()],
+        bump = param_history.bump,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+}
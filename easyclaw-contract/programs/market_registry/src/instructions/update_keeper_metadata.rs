@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{GlobalConfig, KeeperMetadata, KeeperMetadataParams, KeeperSet},
+};
+
+pub fn handler(ctx: Context<UpdateKeeperMetadata>, params: KeeperMetadataParams) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    params.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.keeper_metadata.apply(params, now);
+    ctx.accounts.global_config.last_updated_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateKeeperMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"keeper-meta", keeper_metadata.keeper.as_ref()],
+        bump = keeper_metadata.bump,
+    )]
+    pub keeper_metadata: Account<'info, KeeperMetadata>,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    events::RiskParamsUpdated,
+    helpers::require_admin,
+    state::{BpsRamp, GlobalConfig, KeeperSet, Market, MarketParamHistory, ParamGroup, RiskParams},
+};
+
+pub fn handler(ctx: Context<UpdateRiskParams>, risk_params: RiskParams) -> Result<()> {
+    require_admin(
+        &ctx.accounts.authority,
+        &ctx.accounts.global_config,
+        &ctx.accounts.keeper_set,
+    )?;
+    risk_params.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let market = &mut ctx.accounts.market;
+    let before = market.risk_params;
+    market.check_margin_change(now, risk_params.imr_bps, risk_params.mmr_bps)?;
+    market.risk_params = risk_params;
+    // A direct update supersedes any margin ramp in flight.
+    market.ramps.imr_bps = BpsRamp::default();
+    market.ramps.mmr_bps = BpsRamp::default();
+    market.params_version = market
+        .params_version
+        .checked_add(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    ctx.accounts.param_history.record(
+        ParamGroup::Risk,
+        market.params_version,
+        ctx.accounts.authority.key(),
+        now,
+        &before,
+        &risk_params,
+    )?;
+    ctx.accounts.global_config.last_updated_at = now;
+
+    emit!(RiskParamsUpdated {
+        market: market.key(),
+        market_id: market.market_id,
+        before,
+        after: risk_params,
+        params_version: market.params_version,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRiskParams<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"keeper-set"],
+        bump = keeper_set.bump,
+    )]
+    pub keeper_set: Account<'info, KeeperSet>,
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"param-history".as_ref(), &market.market_id.to_le_bytes()],
+        bump = param_history.bump,
+    )]
+    pub param_history: Account<'info, MarketParamHistory>,
+}
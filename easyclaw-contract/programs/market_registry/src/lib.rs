@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod helpers;
 pub mod instructions;
 pub mod state;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -26,9 +28,22 @@ pub mod market_registry {
         instructions::initialize_global::handler(ctx, multisig, fee_split, pause_flags)
     }
 
+    pub fn initialize_market_list<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeMarketList<'info>>,
+    ) -> Result<()> {
+        instructions::initialize_market_list::handler(ctx)
+    }
+
+    pub fn initialize_keeper_liveness(ctx: Context<InitializeKeeperLiveness>) -> Result<()> {
+        instructions::initialize_keeper_liveness::handler(ctx)
+    }
+
+    pub fn initialize_param_history(ctx: Context<InitializeParamHistory>) -> Result<()> {
+        instructions::initialize_param_history::handler(ctx)
+    }
+
     pub fn create_market(
         ctx: Context<CreateMarket>,
-        market_id: u64,
         symbol: String,
         pyth_feed: Pubkey,
         risk_params: RiskParams,
@@ -38,7 +53,6 @@ pub mod market_registry {
     ) -> Result<()> {
         instructions::create_market::handler(
             ctx,
-            market_id,
             symbol,
             pyth_feed,
             risk_params,
@@ -48,35 +62,251 @@ pub mod market_registry {
         )
     }
 
-    pub fn update_market_params(
-        ctx: Context<UpdateMarketParams>,
+    pub fn update_risk_params(
+        ctx: Context<UpdateRiskParams>,
         risk_params: RiskParams,
+    ) -> Result<()> {
+        instructions::update_risk_params::handler(ctx, risk_params)
+    }
+
+    pub fn update_pricing_params(
+        ctx: Context<UpdatePricingParams>,
         pricing_params: PricingParams,
+    ) -> Result<()> {
+        instructions::update_pricing_params::handler(ctx, pricing_params)
+    }
+
+    pub fn update_funding_params(
+        ctx: Context<UpdateFundingParams>,
         funding_params: FundingParams,
-        fee_params: FeeParams,
     ) -> Result<()> {
-        instructions::update_market_params::handler(
-            ctx,
-            risk_params,
-            pricing_params,
-            funding_params,
-            fee_params,
-        )
+        instructions::update_funding_params::handler(ctx, funding_params)
+    }
+
+    pub fn update_fee_params(ctx: Context<UpdateFeeParams>, fee_params: FeeParams) -> Result<()> {
+        instructions::update_fee_params::handler(ctx, fee_params)
+    }
+
+    pub fn schedule_param_ramp(
+        ctx: Context<ScheduleParamRamp>,
+        param: RampedParam,
+        target_bps: u16,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::schedule_param_ramp::handler(ctx, param, target_bps, duration_secs)
     }
 
     pub fn set_market_status(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
         instructions::set_market_status::handler(ctx, status)
     }
 
+    pub fn set_market_oracle(
+        ctx: Context<SetMarketOracle>,
+        oracle_config: OracleConfig,
+    ) -> Result<()> {
+        instructions::set_market_oracle::handler(ctx, oracle_config)
+    }
+
     pub fn set_global_pause(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
         instructions::set_global_pause::handler(ctx, paused)
     }
 
-    pub fn add_keeper(ctx: Context<AddKeeper>, keeper: Pubkey) -> Result<()> {
-        instructions::add_keeper::handler(ctx, keeper)
+    pub fn propose_fee_split(ctx: Context<ProposeFeeSplit>, fee_split: FeeSplit) -> Result<()> {
+        instructions::propose_fee_split::handler(ctx, fee_split)
+    }
+
+    pub fn execute_fee_split(ctx: Context<ExecuteFeeSplit>) -> Result<()> {
+        instructions::execute_fee_split::handler(ctx)
+    }
+
+    pub fn cancel_fee_split(ctx: Context<CancelFeeSplit>) -> Result<()> {
+        instructions::cancel_fee_split::handler(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn add_keeper(
+        ctx: Context<AddKeeper>,
+        keeper: Pubkey,
+        metadata: KeeperMetadataParams,
+        scopes: KeeperScopes,
+    ) -> Result<()> {
+        instructions::add_keeper::handler(ctx, keeper, metadata, scopes)
+    }
+
+    pub fn set_keeper_scopes(
+        ctx: Context<SetKeeperScopes>,
+        keeper: Pubkey,
+        scopes: KeeperScopes,
+    ) -> Result<()> {
+        instructions::set_keeper_scopes::handler(ctx, keeper, scopes)
+    }
+
+    pub fn keeper_heartbeat(ctx: Context<KeeperHeartbeat>) -> Result<()> {
+        instructions::keeper_heartbeat::handler(ctx)
+    }
+
+    pub fn prune_keeper(ctx: Context<PruneKeeper>) -> Result<()> {
+        instructions::prune_keeper::handler(ctx)
+    }
+
+    pub fn update_keeper_metadata(
+        ctx: Context<UpdateKeeperMetadata>,
+        metadata: KeeperMetadataParams,
+    ) -> Result<()> {
+        instructions::update_keeper_metadata::handler(ctx, metadata)
     }
 
     pub fn remove_keeper(ctx: Context<RemoveKeeper>, keeper: Pubkey) -> Result<()> {
         instructions::remove_keeper::handler(ctx, keeper)
     }
+
+    pub fn migrate_keeper_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateKeeperSet<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_keeper_set::handler(ctx)
+    }
+
+    pub fn migrate_global_config(
+        ctx: Context<MigrateGlobalConfig>,
+        next_market_id: u64,
+    ) -> Result<()> {
+        instructions::migrate_global_config::handler(ctx, next_market_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `KeeperSet` as deployed: authority, up to 64 keepers and the bump.
+    const LEGACY_KEEPER_SET_LEN: usize = 8 + 32 + 4 + 64 * 32 + 1;
+
+    fn legacy_keeper_set_data(keepers: usize) -> (Pubkey, Vec<u8>) {
+        let authority = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..keepers).map(|_| Pubkey::new_unique()).collect();
+        let mut data = KeeperSet::DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&(authority, keys, 254u8)).unwrap());
+        data.resize(LEGACY_KEEPER_SET_LEN, 0);
+        (authority, data)
+    }
+
+    #[test]
+    fn test_keeper_scopes() {
+        let auth = KeeperAuthorization {
+            keeper: Pubkey::new_unique(),
+            scopes: KeeperScopes {
+                execute: true,
+                liquidate: false,
+                cancel_by_executor: false,
+                crank_funding: true,
+            },
+            added_at: 0,
+            bump: 255,
+        };
+        assert!(auth.allows(KeeperScope::Execute));
+        assert!(!auth.allows(KeeperScope::Liquidate));
+        assert!(!auth.allows(KeeperScope::CancelByExecutor));
+        assert!(auth.allows(KeeperScope::CrankFunding));
+
+        let none = KeeperScopes {
+            execute: false,
+            liquidate: false,
+            cancel_by_executor: false,
+            crank_funding: false,
+        };
+        for scope in [
+            KeeperScope::Execute,
+            KeeperScope::Liquidate,
+            KeeperScope::CancelByExecutor,
+            KeeperScope::CrankFunding,
+        ] {
+            assert!(!none.allows(scope));
+        }
+    }
+
+    #[test]
+    fn test_read_legacy_keeper_set() {
+        let (authority, data) = legacy_keeper_set_data(3);
+        let set = read_legacy_keeper_set(&data).unwrap();
+        assert_eq!(set.authority, authority);
+        assert_eq!(set.keepers.len(), 3);
+        assert_eq!(set.bump, 254);
+        assert_eq!(set.migrated, 0);
+
+        // A full set has no spare tail but still reads.
+        let (_, data) = legacy_keeper_set_data(64);
+        assert_eq!(data.len(), LEGACY_KEEPER_SET_LEN);
+        let set = read_legacy_keeper_set(&data).unwrap();
+        assert_eq!(set.keepers.len(), 64);
+        assert_eq!(set.bump, 254);
+        assert_eq!(set.migrated, 0);
+    }
+
+    #[test]
+    fn test_legacy_keeper_set_batches_round_trip() {
+        let (_, mut data) = legacy_keeper_set_data(5);
+        let mut set = read_legacy_keeper_set(&data).unwrap();
+        set.keepers.truncate(3);
+        set.migrated = 2;
+        set.serialize(&mut &mut data[8..]).unwrap();
+
+        let reread = read_legacy_keeper_set(&data).unwrap();
+        assert_eq!(reread.keepers.len(), 3);
+        assert_eq!(reread.migrated, 2);
+
+        // Migrating one keeper out of a full set frees room for the count.
+        let (_, mut data) = legacy_keeper_set_data(64);
+        let mut set = read_legacy_keeper_set(&data).unwrap();
+        set.keepers.truncate(63);
+        set.migrated = 1;
+        set.serialize(&mut &mut data[8..]).unwrap();
+        assert_eq!(data.len(), LEGACY_KEEPER_SET_LEN);
+        assert_eq!(read_legacy_keeper_set(&data).unwrap().migrated, 1);
+    }
+
+    #[test]
+    fn test_migrate_global_config() {
+        let multisig = Pubkey::new_unique();
+        let keeper_set = Pubkey::new_unique();
+        let fee_split = FeeSplit {
+            lp_bps: 7_000,
+            insurance_bps: 2_000,
+            protocol_bps: 1_000,
+        };
+        // `GlobalConfig` as deployed, before any field was appended.
+        let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+        data.extend(
+            borsh::to_vec(&(multisig, true, fee_split, keeper_set, 100i64, 200i64, 253u8)).unwrap(),
+        );
+
+        let global = migrated_global_config(&data).unwrap();
+        assert_eq!(global.multisig, multisig);
+        assert!(global.global_pause);
+        assert_eq!(global.fee_split.lp_bps, 7_000);
+        assert_eq!(global.keeper_set, keeper_set);
+        assert_eq!(global.last_updated_at, 200);
+        assert_eq!(global.bump, 253);
+        assert!(global.guardian.is_none());
+        assert!(global.pending_fee_split.is_none());
+
+        let mut migrated = Vec::new();
+        global.try_serialize(&mut migrated).unwrap();
+        migrated.resize(8 + GlobalConfig::INIT_SPACE, 0);
+        assert!(migrated_global_config(&migrated).is_err());
+    }
+
+    #[test]
+    fn test_migrated_keeper_set_is_rejected() {
+        let set = KeeperSet {
+            authority: Pubkey::new_unique(),
+            bump: 254,
+            keeper_count: 5,
+        };
+        let mut data = Vec::new();
+        set.try_serialize(&mut data).unwrap();
+        assert!(read_legacy_keeper_set(&data).is_err());
+    }
 }
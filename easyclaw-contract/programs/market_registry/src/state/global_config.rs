@@ -7,9 +7,18 @@ use crate::state::FeeSplit;
 pub struct GlobalConfig {
     pub multisig: Pubkey,
     pub global_pause: bool,
+    // Base split of trading fees between LPs, insurance and protocol;
+    // order_engine keeps the LP pool in sync with it.
     pub fee_split: FeeSplit,
     pub keeper_set: Pubkey,
     pub created_at: i64,
     pub last_updated_at: i64,
     pub bump: u8,
+    // Hot key that may pause the protocol or a market but change nothing else.
+    pub guardian: Option<Pubkey>,
+    // Id the next `create_market` assigns; ids start at 1 and never repeat.
+    pub next_market_id: u64,
+    // Proposed replacement for `fee_split` and when it may be executed.
+    pub pending_fee_split: Option<FeeSplit>,
+    pub pending_fee_split_eta: i64,
 }
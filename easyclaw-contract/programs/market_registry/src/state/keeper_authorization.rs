@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{KeeperScope, KeeperScopes};
+
+// Proof that `keeper` is registered, at `[b"keeper-auth", keeper]`. The engine
+// loads only the executor's own PDA instead of scanning the whole set.
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperAuthorization {
+    pub keeper: Pubkey,
+    pub scopes: KeeperScopes,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl KeeperAuthorization {
+    pub fn allows(&self, scope: KeeperScope) -> bool {
+        self.scopes.allows(scope)
+    }
+}
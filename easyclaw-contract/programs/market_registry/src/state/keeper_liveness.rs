@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{KEEPER_HEARTBEAT_INTERVAL_SECS, MAX_MISSED_HEARTBEATS};
+
+// Latest heartbeat from any keeper. Kept out of `GlobalConfig` so heartbeats
+// do not write-lock the config every fill reads.
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperLiveness {
+    // Zero until the first heartbeat.
+    pub last_heartbeat_at: i64,
+    pub bump: u8,
+}
+
+impl KeeperLiveness {
+    // Dead-man switch: every keeper has gone quiet for longer than one may
+    // before being pruned. Never trips before the first heartbeat.
+    pub fn keepers_unresponsive(&self, now: i64) -> bool {
+        self.last_heartbeat_at > 0
+            && now
+                > self.last_heartbeat_at.saturating_add(
+                    KEEPER_HEARTBEAT_INTERVAL_SECS.saturating_mul(MAX_MISSED_HEARTBEATS),
+                )
+    }
+}
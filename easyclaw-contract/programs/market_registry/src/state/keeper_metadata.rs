@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_CONTACT_URI_LEN, MAX_OPERATOR_NAME_LEN},
+    error::ErrorCode,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct KeeperMetadataParams {
+    pub operator_name: String,
+    pub contact_uri: String,
+    // Where the operator wants keeper income sent.
+    pub payout_address: Pubkey,
+}
+
+impl KeeperMetadataParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.operator_name.is_empty() && self.operator_name.len() <= MAX_OPERATOR_NAME_LEN,
+            ErrorCode::InvalidKeeperMetadata
+        );
+        require!(
+            self.contact_uri.len() <= MAX_CONTACT_URI_LEN,
+            ErrorCode::InvalidKeeperMetadata
+        );
+        Ok(())
+    }
+}
+
+// Who runs a keeper, for monitoring and payout tooling.
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperMetadata {
+    pub keeper: Pubkey,
+    #[max_len(MAX_OPERATOR_NAME_LEN)]
+    pub operator_name: String,
+    #[max_len(MAX_CONTACT_URI_LEN)]
+    pub contact_uri: String,
+    pub payout_address: Pubkey,
+    pub registered_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub last_heartbeat_at: i64,
+}
+
+impl KeeperMetadata {
+    pub fn apply(&mut self, params: KeeperMetadataParams, now: i64) {
+        self.operator_name = params.operator_name;
+        self.contact_uri = params.contact_uri;
+        self.payout_address = params.payout_address;
+        self.updated_at = now;
+    }
+}
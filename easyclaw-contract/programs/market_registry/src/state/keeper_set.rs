@@ -1,12 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::MAX_KEEPERS;
-
+// Keepers themselves live in per-keeper `KeeperAuthorization` PDAs; this only
+// anchors the admin authority and tracks how many are registered.
 #[account]
 #[derive(InitSpace)]
 pub struct KeeperSet {
     pub authority: Pubkey,
-    #[max_len(MAX_KEEPERS)]
-    pub keepers: Vec<Pubkey>,
     pub bump: u8,
+    pub keeper_count: u32,
 }
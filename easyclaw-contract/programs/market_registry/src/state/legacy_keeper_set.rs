@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// `KeeperSet` as deployed before keepers moved to PDAs, read by
+// `migrate_keeper_set`. Shares the `KeeperSet` discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyKeeperSet {
+    pub authority: Pubkey,
+    pub keepers: Vec<Pubkey>,
+    pub bump: u8,
+    // Not part of the legacy layout: keepers already moved to PDAs by earlier
+    // migration batches, kept in the tail each batch frees. Reads as zero on a
+    // set that has not been touched yet.
+    pub migrated: u32,
+}
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::SYMBOL_LEN,
-    state::{FeeParams, FundingParams, MarketStatus, PricingParams, RiskParams},
+    constants::{MAX_MARGIN_CHANGE_BPS, RISK_CHANGE_WINDOW_SECS, SYMBOL_LEN},
+    error::ErrorCode,
+    state::{
+        FeeParams, FundingParams, MarketRamps, MarketStatus, OracleConfig, PricingParams,
+        RiskParams,
+    },
 };
 
 #[account]
@@ -11,10 +15,67 @@ pub struct Market {
     pub market_id: u64,
     pub symbol: [u8; SYMBOL_LEN],
     pub pyth_feed: Pubkey,
+    pub oracle_config: OracleConfig,
     pub status: MarketStatus,
     pub risk_params: RiskParams,
     pub pricing_params: PricingParams,
     pub funding_params: FundingParams,
     pub fee_params: FeeParams,
+    pub ramps: MarketRamps,
+    pub params_version: u64,
     pub bump: u8,
+    // Margin requirements in effect when the current rate-limit window began.
+    pub risk_window_start: i64,
+    pub risk_window_imr_bps: u16,
+    pub risk_window_mmr_bps: u16,
+}
+
+impl Market {
+    pub fn imr_bps_at(&self, now: i64) -> u16 {
+        self.ramps
+            .imr_bps
+            .value_at(now)
+            .unwrap_or(self.risk_params.imr_bps)
+    }
+
+    pub fn mmr_bps_at(&self, now: i64) -> u16 {
+        self.ramps
+            .mmr_bps
+            .value_at(now)
+            .unwrap_or(self.risk_params.mmr_bps)
+    }
+
+    pub fn taker_fee_bps_at(&self, now: i64) -> u16 {
+        self.ramps
+            .taker_fee_bps
+            .value_at(now)
+            .unwrap_or(self.fee_params.taker_fee_bps)
+    }
+
+    pub fn reset_risk_window(&mut self, now: i64) {
+        self.risk_window_start = now;
+        self.risk_window_imr_bps = self.imr_bps_at(now);
+        self.risk_window_mmr_bps = self.mmr_bps_at(now);
+    }
+
+    // Rejects margin targets too far from the values the window started with;
+    // a new window starts once the current one has run out.
+    pub fn check_margin_change(&mut self, now: i64, imr_bps: u16, mmr_bps: u16) -> Result<()> {
+        if now
+            >= self
+                .risk_window_start
+                .saturating_add(RISK_CHANGE_WINDOW_SECS)
+        {
+            self.reset_risk_window(now);
+        }
+        require!(
+            imr_bps.abs_diff(self.risk_window_imr_bps) <= MAX_MARGIN_CHANGE_BPS,
+            ErrorCode::RiskChangeTooLarge
+        );
+        require!(
+            mmr_bps.abs_diff(self.risk_window_mmr_bps) <= MAX_MARGIN_CHANGE_BPS,
+            ErrorCode::RiskChangeTooLarge
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_MARKETS, error::ErrorCode, state::MarketStatus};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct MarketListEntry {
+    pub market_id: u64,
+    pub status: MarketStatus,
+}
+
+// Every created market and its status, in creation order, so integrators and
+// the engine can enumerate markets without scanning program accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketList {
+    #[max_len(MAX_MARKETS)]
+    pub markets: Vec<MarketListEntry>,
+    pub bump: u8,
+}
+
+impl MarketList {
+    pub fn insert(&mut self, market_id: u64, status: MarketStatus) -> Result<()> {
+        require!(self.markets.len() < MAX_MARKETS, ErrorCode::MarketListFull);
+        require!(
+            !self
+                .markets
+                .iter()
+                .any(|entry| entry.market_id == market_id),
+            ErrorCode::MarketAlreadyListed
+        );
+        self.markets.push(MarketListEntry { market_id, status });
+        Ok(())
+    }
+
+    pub fn set_status(&mut self, market_id: u64, status: MarketStatus) -> Result<()> {
+        let entry = self
+            .markets
+            .iter_mut()
+            .find(|entry| entry.market_id == market_id)
+            .ok_or_else(|| error!(ErrorCode::MarketNotListed))?;
+        entry.status = status;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::{prelude::*, solana_program::hash::hash};
+
+use crate::{constants::MAX_PARAM_HISTORY, error::ErrorCode};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum ParamGroup {
+    Risk,
+    Pricing,
+    Funding,
+    Fee,
+    // Risk and fee params together with the ramps, as `schedule_param_ramp`
+    // touches all three.
+    Ramp,
+    Oracle,
+}
+
+// Hashes are over the borsh encoding of the group, so a reader holding the
+// values can check them against the record.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ParamChangeRecord {
+    pub group: ParamGroup,
+    pub params_version: u64,
+    pub changed_by: Pubkey,
+    pub changed_at: i64,
+    pub old_hash: [u8; 32],
+    pub new_hash: [u8; 32],
+}
+
+// Most recent parameter changes for one market, at
+// `[b"param-history", market_id]`. Once full, the oldest record is overwritten.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketParamHistory {
+    pub market_id: u64,
+    // Records ever appended; the next one goes in slot `total_changes % MAX_PARAM_HISTORY`.
+    pub total_changes: u64,
+    #[max_len(MAX_PARAM_HISTORY)]
+    pub records: Vec<ParamChangeRecord>,
+    pub bump: u8,
+}
+
+impl MarketParamHistory {
+    pub fn record<T: AnchorSerialize>(
+        &mut self,
+        group: ParamGroup,
+        params_version: u64,
+        changed_by: Pubkey,
+        changed_at: i64,
+        old: &T,
+        new: &T,
+    ) -> Result<()> {
+        let record = ParamChangeRecord {
+            group,
+            params_version,
+            changed_by,
+            changed_at,
+            old_hash: params_hash(old)?,
+            new_hash: params_hash(new)?,
+        };
+        let slot = (self.total_changes % MAX_PARAM_HISTORY as u64) as usize;
+        if slot < self.records.len() {
+            self.records[slot] = record;
+        } else {
+            self.records.push(record);
+        }
+        self.total_changes = self
+            .total_changes
+            .checked_add(1)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+}
+
+fn params_hash<T: AnchorSerialize>(value: &T) -> Result<[u8; 32]> {
+    let bytes = borsh::to_vec(value)?;
+    Ok(hash(&bytes).to_bytes())
+}
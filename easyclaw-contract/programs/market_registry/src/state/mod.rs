@@ -1,9 +1,21 @@
 pub mod global_config;
+pub mod keeper_authorization;
+pub mod keeper_liveness;
+pub mod keeper_metadata;
 pub mod keeper_set;
+pub mod legacy_keeper_set;
 pub mod market;
+pub mod market_list;
+pub mod market_param_history;
 pub mod types;
 
 pub use global_config::*;
+pub use keeper_authorization::*;
+pub use keeper_liveness::*;
+pub use keeper_metadata::*;
 pub use keeper_set::*;
+pub use legacy_keeper_set::*;
 pub use market::*;
+pub use market_list::*;
+pub use market_param_history::*;
 pub use types::*;
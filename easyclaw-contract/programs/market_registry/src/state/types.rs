@@ -7,6 +7,150 @@ pub enum MarketStatus {
     Active,
     Paused,
     Halted,
+    // Only reduce-only orders may be placed or filled.
+    ReduceOnly,
+    // No new orders; resting reduce-only orders still fill so positions can
+    // wind down.
+    CloseOnly,
+    // Trading and liquidations stop while positions are settled.
+    Settlement,
+}
+
+impl MarketStatus {
+    pub fn allows_placement(&self, reduce_only: bool) -> bool {
+        match self {
+            MarketStatus::Active => true,
+            MarketStatus::ReduceOnly => reduce_only,
+            _ => false,
+        }
+    }
+
+    pub fn allows_execution(&self, reduce_only: bool) -> bool {
+        match self {
+            MarketStatus::Active => true,
+            MarketStatus::ReduceOnly | MarketStatus::CloseOnly => reduce_only,
+            _ => false,
+        }
+    }
+
+    pub fn allows_liquidation(&self) -> bool {
+        matches!(
+            self,
+            MarketStatus::Active | MarketStatus::ReduceOnly | MarketStatus::CloseOnly
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum OracleStalePolicy {
+    RejectFills,
+    ReduceOnly,
+    WidenMargin,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum OracleSource {
+    PythPush,
+    SwitchboardOnDemand,
+    PythLazer,
+    Median,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct OracleConfig {
+    pub source: OracleSource,
+    pub switchboard_feed: Pubkey,
+    pub lazer_feed_id: u32,
+    pub lazer_signer: Pubkey,
+    // Pyth push or Switchboard accounts; unused slots are `Pubkey::default()`.
+    pub median_feeds: [Pubkey; 3],
+    // Accept keeper-supplied prices when the instructions sysvar is passed as
+    // the oracle account and the preceding ed25519 instruction carries the
+    // price signed by `fallback_signer`. Only settable in `devnet` builds, and
+    // not for Lazer markets whose prices are already signed.
+    pub allow_keeper_fallback: bool,
+    pub fallback_signer: Pubkey,
+}
+
+impl OracleConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.allow_keeper_fallback || cfg!(feature = "devnet"),
+            ErrorCode::InvalidOracleConfig
+        );
+        if self.allow_keeper_fallback {
+            require!(
+                self.fallback_signer != Pubkey::default() && self.source != OracleSource::PythLazer,
+                ErrorCode::InvalidOracleConfig
+            );
+        }
+        match self.source {
+            OracleSource::PythPush => {}
+            OracleSource::SwitchboardOnDemand => require!(
+                self.switchboard_feed != Pubkey::default(),
+                ErrorCode::InvalidOracleConfig
+            ),
+            OracleSource::PythLazer => {
+                require!(self.lazer_feed_id > 0, ErrorCode::InvalidOracleConfig);
+                require!(
+                    self.lazer_signer != Pubkey::default(),
+                    ErrorCode::InvalidOracleConfig
+                );
+            }
+            OracleSource::Median => {
+                let configured = self
+                    .median_feeds
+                    .iter()
+                    .filter(|feed| **feed != Pubkey::default())
+                    .count();
+                require!(configured >= 2, ErrorCode::InvalidOracleConfig);
+                for (i, feed) in self.median_feeds.iter().enumerate() {
+                    require!(
+                        *feed == Pubkey::default() || !self.median_feeds[..i].contains(feed),
+                        ErrorCode::InvalidOracleConfig
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Engine actions a keeper may be restricted to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum KeeperScope {
+    Execute,
+    Liquidate,
+    CancelByExecutor,
+    CrankFunding,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct KeeperScopes {
+    pub execute: bool,
+    pub liquidate: bool,
+    pub cancel_by_executor: bool,
+    pub crank_funding: bool,
+}
+
+impl KeeperScopes {
+    // What every keeper could do before scopes existed; keepers carried over
+    // from the unscoped `KeeperSet` start with it.
+    pub const ALL: Self = Self {
+        execute: true,
+        liquidate: true,
+        cancel_by_executor: true,
+        crank_funding: true,
+    };
+
+    pub fn allows(&self, scope: KeeperScope) -> bool {
+        match scope {
+            KeeperScope::Execute => self.execute,
+            KeeperScope::Liquidate => self.liquidate,
+            KeeperScope::CancelByExecutor => self.cancel_by_executor,
+            KeeperScope::CrankFunding => self.crank_funding,
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
@@ -36,6 +180,17 @@ pub struct RiskParams {
     pub oi_cap: u64,
     pub skew_cap: u64,
     pub max_trade_notional: u64,
+    // Legs worth less than this at oracle price may be swept by keepers with
+    // no penalty; 0 disables sweeping.
+    pub dust_notional: u64,
+    // Longest order TTL accepted for this market, capped by the engine-wide
+    // limit; 0 uses the engine limit.
+    pub max_ttl_secs: i64,
+    // Fill quantity is rounded down to a multiple of this (qty units);
+    // 0 or 1 disables lot rounding.
+    pub qty_lot_size: u64,
+    // Order and fill prices must be multiples of this; 0 disables the check.
+    pub price_tick_size: u64,
 }
 
 impl RiskParams {
@@ -46,6 +201,11 @@ impl RiskParams {
         require!(self.mmr_bps <= 10_000, ErrorCode::InvalidRiskParams);
         require!(self.oi_cap > 0, ErrorCode::InvalidRiskParams);
         require!(self.max_trade_notional > 0, ErrorCode::InvalidRiskParams);
+        require!(
+            self.dust_notional < self.max_trade_notional,
+            ErrorCode::InvalidRiskParams
+        );
+        require!(self.max_ttl_secs >= 0, ErrorCode::InvalidRiskParams);
         Ok(())
     }
 }
@@ -57,6 +217,24 @@ pub struct PricingParams {
     pub max_fill_deviation_bps: u16,
     pub max_oracle_staleness_sec: i64,
     pub max_conf_bps: u16,
+    pub stale_policy: OracleStalePolicy,
+    pub stale_grace_sec: i64,
+    pub stale_margin_add_bps: u16,
+    pub min_order_age_slots: u64,
+    // Max spot vs EMA deviation at which liquidations may run; 0 disables the band.
+    pub liquidation_ema_band_bps: u16,
+    // Max move against the last accepted price within the window before the
+    // market trips into reduce-only; 0 disables the breaker.
+    pub circuit_breaker_bps: u16,
+    pub circuit_breaker_window_sec: i64,
+    // Liquidations use their own, typically wider, oracle limits so they keep
+    // running when volatility breaches the trading band.
+    pub liquidation_max_conf_bps: u16,
+    pub liquidation_max_staleness_sec: i64,
+    // Max spot vs on-chain oracle TWAP deviation at which liquidations may run;
+    // 0 disables the band. The TWAP is advanced by the funding crank.
+    pub liquidation_twap_band_bps: u16,
+    pub twap_window_sec: i64,
 }
 
 impl PricingParams {
@@ -70,6 +248,39 @@ impl PricingParams {
             ErrorCode::InvalidPricingParams
         );
         require!(self.max_conf_bps <= 10_000, ErrorCode::InvalidPricingParams);
+        require!(self.stale_grace_sec >= 0, ErrorCode::InvalidPricingParams);
+        require!(
+            self.stale_margin_add_bps <= 10_000,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.liquidation_ema_band_bps <= 10_000,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.circuit_breaker_bps <= 10_000,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.circuit_breaker_bps == 0 || self.circuit_breaker_window_sec > 0,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.liquidation_max_conf_bps <= 10_000,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.liquidation_max_staleness_sec > 0,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.liquidation_twap_band_bps <= 10_000,
+            ErrorCode::InvalidPricingParams
+        );
+        require!(
+            self.liquidation_twap_band_bps == 0 || self.twap_window_sec > 0,
+            ErrorCode::InvalidPricingParams
+        );
         Ok(())
     }
 }
@@ -93,16 +304,61 @@ impl FundingParams {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum RampedParam {
+    ImrBps,
+    MmrBps,
+    TakerFeeBps,
+}
+
+// Linear move from `start_bps` to `end_bps` over `[start_ts, end_ts)`. Consumers
+// evaluate it lazily; once finished the static param (already `end_bps`) applies.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct BpsRamp {
+    pub start_bps: u16,
+    pub end_bps: u16,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+impl BpsRamp {
+    pub fn value_at(&self, now: i64) -> Option<u16> {
+        if now >= self.end_ts {
+            return None;
+        }
+        if now <= self.start_ts {
+            return Some(self.start_bps);
+        }
+
+        let elapsed = (now - self.start_ts) as i128;
+        let duration = (self.end_ts - self.start_ts) as i128;
+        let delta = self.end_bps as i128 - self.start_bps as i128;
+        Some((self.start_bps as i128 + delta * elapsed / duration) as u16)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct MarketRamps {
+    pub imr_bps: BpsRamp,
+    pub mmr_bps: BpsRamp,
+    pub taker_fee_bps: BpsRamp,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
 pub struct FeeParams {
     pub taker_fee_bps: u16,
-    pub maker_fee_bps: u16,
+    // Negative values are a rebate to resting limit orders, funded by LPs.
+    pub maker_fee_bps: i16,
 }
 
 impl FeeParams {
     pub fn validate(&self) -> Result<()> {
         require!(self.taker_fee_bps <= 1_000, ErrorCode::InvalidFeeParams);
-        require!(self.maker_fee_bps <= 1_000, ErrorCode::InvalidFeeParams);
+        require!(
+            self.maker_fee_bps <= self.taker_fee_bps as i16
+                && self.maker_fee_bps.unsigned_abs() <= self.taker_fee_bps,
+            ErrorCode::InvalidFeeParams
+        );
         Ok(())
     }
 }
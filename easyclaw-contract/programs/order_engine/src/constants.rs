@@ -1,3 +1,8 @@
 pub const BPS_DENOM: u128 = 10_000;
 pub const PRICE_SCALE: u128 = 1_000_000;
 pub const FUNDING_SCALE: i128 = 1_000_000;
+pub const SOCIALIZED_LOSS_SCALE: u128 = 1_000_000_000_000;
+pub const MAX_BATCH_ORDERS: usize = 8;
+pub const MAX_BATCH_LIQUIDATIONS: usize = 8;
+pub const MAX_LADDER_STEPS: usize = 10;
+pub const MAX_TOP_UP_THRESHOLD_BPS: u16 = 50_000;
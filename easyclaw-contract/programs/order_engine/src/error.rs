@@ -76,4 +76,86 @@ pub enum ErrorCode {
     MarketHaltedLocal,
     #[msg("Invalid funding params")]
     InvalidFundingParams,
+    #[msg("Invalid batch")]
+    InvalidBatch,
+    #[msg("Stale oracle only allows reduce-only fills")]
+    StaleOracleReduceOnly,
+    #[msg("Market mirror is newer than the registry market")]
+    StaleMarketMirror,
+    #[msg("Order has not reached the market's minimum age")]
+    OrderTooYoung,
+    #[msg("Invalid signed price message")]
+    InvalidSignedMessage,
+    #[msg("Invalid execution guard")]
+    InvalidExecutionGuard,
+    #[msg("Oracle moved against the order after its execution deadline")]
+    ExecutionDeadlineAdverseMove,
+    #[msg("Fewer than two fresh oracles")]
+    InsufficientFreshOracles,
+    #[msg("Oracle price is too far from its EMA to liquidate")]
+    OracleEmaDeviationTooWide,
+    #[msg("Market does not accept keeper-supplied oracle prices")]
+    KeeperFallbackOracleDisabled,
+    #[msg("USDC mint does not match the LP pool")]
+    LpMintMismatch,
+    #[msg("Keeper set does not match the configuration")]
+    KeeperSetMismatch,
+    #[msg("Registry global config does not match the configuration")]
+    GlobalConfigMismatch,
+    #[msg("LP pool is bound to a different order engine program")]
+    EngineProgramMismatch,
+    #[msg("Engine authority does not match the LP pool")]
+    EngineAuthorityMismatch,
+    #[msg("LP pool does not match the configuration")]
+    LpPoolMismatch,
+    #[msg("Liquidity vault does not match the configuration")]
+    LiquidityVaultMismatch,
+    #[msg("Insurance vault does not match the configuration")]
+    InsuranceVaultMismatch,
+    #[msg("Protocol fee vault does not match the configuration")]
+    ProtocolFeeVaultMismatch,
+    #[msg("Collateral vault does not match the configuration")]
+    CollateralVaultMismatch,
+    #[msg("Circuit breaker tripped; only reduce-only fills are allowed")]
+    CircuitBreakerReduceOnly,
+    #[msg("Invalid order ladder")]
+    InvalidLadder,
+    #[msg("Oracle TWAP has not been cranked within its window")]
+    OracleTwapUnavailable,
+    #[msg("Oracle price is too far from its TWAP to liquidate")]
+    OracleTwapDeviationTooWide,
+    #[msg("Invalid top-up threshold")]
+    InvalidTopUpThreshold,
+    #[msg("Account health is above the top-up threshold")]
+    TopUpNotNeeded,
+    #[msg("Top-up source does not match the recorded allowance")]
+    TopUpSourceMismatch,
+    #[msg("No top-up allowance available")]
+    TopUpAllowanceExhausted,
+    #[msg("Engine collateral is not wrapped SOL")]
+    SolCollateralDisabled,
+    #[msg("Position has no leg below the dust threshold")]
+    PositionNotDust,
+    #[msg("Account is below initial margin; only reduce-only orders are allowed")]
+    AccountUnhealthyReduceOnly,
+    #[msg("Execution must be top-level and not bundled with other engine or vault instructions")]
+    UntrustedInstructionBundle,
+    #[msg("Position leverage exceeded for its allocated margin")]
+    PositionLeverageExceeded,
+    #[msg("Conditional order trigger price not reached")]
+    TriggerNotMet,
+    #[msg("Invalid conditional order trigger")]
+    InvalidTrigger,
+    #[msg("Price is not a multiple of the market tick size")]
+    PriceNotOnTick,
+    #[msg("Fill would leave the account below maintenance margin")]
+    PostTradeBelowMaintenance,
+    #[msg("LP insurance coverage is below the ratio required to grow open interest")]
+    InsuranceCoverageTooLow,
+    #[msg("Market only accepts reduce-only orders")]
+    MarketReduceOnly,
+    #[msg("Keeper is not permitted to perform this action")]
+    KeeperScopeMissing,
+    #[msg("Funding states do not match the registry market list")]
+    MarketListMismatch,
 }
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UserCompensated {
+    pub authority: Pubkey,
+    pub user: Pubkey,
+    pub user_margin: Pubkey,
+    pub amount: u64,
+    pub reason_code: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub market_id: u64,
+    pub reference_price: u64,
+    pub oracle_price: u64,
+    pub deviation_bps: u64,
+    pub timestamp: i64,
+}
+
+// Emitted just before a wiring check fails so mis-deployments can be
+// diagnosed from the transaction logs.
+#[event]
+pub struct ConfigMismatchDetected {
+    pub field: String,
+    pub expected: Pubkey,
+    pub actual: Pubkey,
+}
+
+#[event]
+pub struct CollateralToppedUp {
+    pub keeper: Pubkey,
+    pub user: Pubkey,
+    pub user_margin: Pubkey,
+    pub amount: u64,
+    pub remaining_allowance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DustPositionSwept {
+    pub market_id: u64,
+    pub user_margin: Pubkey,
+    pub long_qty: u64,
+    pub short_qty: u64,
+    pub oracle_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LossSocialized {
+    pub market_id: u64,
+    pub liquidated_leg: u8,
+    pub shortfall: u64,
+    pub loss_index_long: u128,
+    pub loss_index_short: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SocializedLossCollected {
+    pub market_id: u64,
+    pub positions: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpNavMarked {
+    pub markets: u32,
+    pub unrealized_trader_pnl: i64,
+    pub open_interest: u64,
+    pub timestamp: i64,
+}
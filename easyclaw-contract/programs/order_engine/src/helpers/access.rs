@@ -1,35 +1,82 @@
 use anchor_lang::prelude::*;
+use market_registry::KeeperScope;
 
-use crate::{error::ErrorCode, state::EngineConfig};
+use crate::{error::ErrorCode, events::ConfigMismatchDetected, state::EngineConfig};
 
 pub fn require_admin(admin: &Signer<'_>, config: &Account<EngineConfig>) -> Result<()> {
     require_keys_eq!(admin.key(), config.admin, ErrorCode::Unauthorized);
     Ok(())
 }
 
+pub fn require_config_key(
+    field: &str,
+    expected: Pubkey,
+    actual: Pubkey,
+    error: ErrorCode,
+) -> Result<()> {
+    if expected != actual {
+        emit!(ConfigMismatchDetected {
+            field: field.to_string(),
+            expected,
+            actual,
+        });
+        return Err(error!(error));
+    }
+    Ok(())
+}
+
+// `keeper_auth` is the executor's own authorization PDA, omitted when the
+// multisig executes directly.
 pub fn assert_executor_authorized(
     executor: &Signer<'_>,
     global_config: &Account<market_registry::GlobalConfig>,
-    keeper_set: &Account<market_registry::KeeperSet>,
+    keeper_auth: Option<&Account<market_registry::KeeperAuthorization>>,
+    scope: KeeperScope,
 ) -> Result<()> {
     if executor.key() == global_config.multisig {
         return Ok(());
     }
 
-    require!(
-        keeper_set.keepers.contains(&executor.key()),
-        ErrorCode::UnauthorizedExecutor
-    );
-    Ok(())
+    let keeper_auth = keeper_auth.ok_or_else(|| error!(ErrorCode::UnauthorizedExecutor))?;
+    assert_keeper_scope(executor, keeper_auth, scope)
+}
+
+// Dead-man switch: once every keeper has stopped heartbeating, liquidations
+// open to anyone so underwater positions cannot sit unliquidated.
+pub fn assert_liquidator_authorized(
+    executor: &Signer<'_>,
+    keeper_liveness: &Account<market_registry::KeeperLiveness>,
+    keeper_auth: Option<&Account<market_registry::KeeperAuthorization>>,
+    now: i64,
+) -> Result<()> {
+    if keeper_liveness.keepers_unresponsive(now) {
+        return Ok(());
+    }
+
+    let keeper_auth = keeper_auth.ok_or_else(|| error!(ErrorCode::UnauthorizedExecutor))?;
+    assert_keeper_scope(executor, keeper_auth, KeeperScope::Liquidate)
 }
 
+// The registry only creates authorizations at the keeper's own PDA, so a
+// registry-owned account naming the executor proves membership.
 pub fn assert_keeper_only(
     executor: &Signer<'_>,
-    keeper_set: &Account<market_registry::KeeperSet>,
+    keeper_auth: &Account<market_registry::KeeperAuthorization>,
 ) -> Result<()> {
-    require!(
-        keeper_set.keepers.contains(&executor.key()),
+    require_keys_eq!(
+        keeper_auth.keeper,
+        executor.key(),
         ErrorCode::UnauthorizedExecutor
     );
     Ok(())
 }
+
+pub fn assert_keeper_scope(
+    executor: &Signer<'_>,
+    keeper_auth: &Account<market_registry::KeeperAuthorization>,
+    scope: KeeperScope,
+) -> Result<()> {
+    assert_keeper_only(executor, keeper_auth)?;
+    require!(keeper_auth.allows(scope), ErrorCode::KeeperScopeMissing);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::BPS_DENOM, error::ErrorCode, events::CircuitBreakerTripped, helpers::abs_diff,
+    state::MarketFundingState,
+};
+
+// Compares `oracle_price` with the last accepted price and trips the breaker
+// when it moved more than `circuit_breaker_bps` inside the window. Returns
+// `true` when it tripped on this call; the caller must then return `Ok` so the
+// trip persists instead of failing the transaction.
+pub fn observe_oracle_price(
+    funding_state: &mut MarketFundingState,
+    pricing: &market_registry::PricingParams,
+    now: i64,
+    oracle_price: u64,
+) -> Result<bool> {
+    if funding_state.is_breaker_tripped() {
+        return Ok(false);
+    }
+
+    let reference_price = funding_state.last_accepted_price;
+    let within_window = now.saturating_sub(funding_state.last_accepted_price_ts)
+        <= pricing.circuit_breaker_window_sec;
+    if pricing.circuit_breaker_bps > 0 && reference_price > 0 && within_window {
+        let deviation_bps = (abs_diff(oracle_price, reference_price) as u128)
+            .checked_mul(BPS_DENOM)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            .checked_div(reference_price as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        if deviation_bps > pricing.circuit_breaker_bps as u128 {
+            funding_state.breaker_tripped = 1;
+            emit!(CircuitBreakerTripped {
+                market_id: funding_state.market_id,
+                reference_price,
+                oracle_price,
+                deviation_bps: deviation_bps as u64,
+                timestamp: now,
+            });
+            return Ok(true);
+        }
+    }
+
+    funding_state.last_accepted_price = oracle_price;
+    funding_state.last_accepted_price_ts = now;
+    Ok(false)
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::error::ErrorCode;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+// Offsets pointing at this value refer to data inside the ed25519 instruction itself.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Returns the message verified by the ed25519 instruction immediately preceding
+// the current one, after checking it was signed by `expected_signer`.
+pub fn load_verified_ed25519_message(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+) -> Result<Vec<u8>> {
+    require_keys_eq!(
+        instructions_sysvar.key(),
+        anchor_lang::solana_program::sysvar::instructions::ID,
+        ErrorCode::InvalidSignedMessage
+    );
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidSignedMessage);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        ErrorCode::InvalidSignedMessage
+    );
+
+    parse_ed25519_message(&ix.data, expected_signer)
+}
+
+// Reads the single signature entry of an ed25519 program instruction, whose
+// pubkey and message must live in the instruction itself.
+pub(crate) fn parse_ed25519_message(data: &[u8], expected_signer: &Pubkey) -> Result<Vec<u8>> {
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidSignedMessage
+    );
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_ix_index = read_u16(2);
+    let pubkey_offset = read_u16(4) as usize;
+    let pubkey_ix_index = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_len = read_u16(10) as usize;
+    let message_ix_index = read_u16(12);
+    require!(
+        signature_ix_index == CURRENT_INSTRUCTION
+            && pubkey_ix_index == CURRENT_INSTRUCTION
+            && message_ix_index == CURRENT_INSTRUCTION,
+        ErrorCode::InvalidSignedMessage
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or_else(|| error!(ErrorCode::InvalidSignedMessage))?;
+    require!(
+        pubkey == expected_signer.as_ref(),
+        ErrorCode::InvalidSignedMessage
+    );
+
+    let message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or_else(|| error!(ErrorCode::InvalidSignedMessage))?;
+    Ok(message.to_vec())
+}
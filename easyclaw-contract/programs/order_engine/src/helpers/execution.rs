@@ -1,10 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{constants::MAX_BATCH_ORDERS, error::ErrorCode};
+
+// `execute_orders` takes one to `MAX_BATCH_ORDERS` fills, each with exactly
+// three remaining accounts.
+pub fn validate_fill_batch(fills: usize, remaining_accounts: usize) -> Result<()> {
+    require!(
+        fills > 0 && fills <= MAX_BATCH_ORDERS,
+        ErrorCode::InvalidBatch
+    );
+    require!(remaining_accounts == fills * 3, ErrorCode::InvalidBatch);
+    Ok(())
+}
 
 pub fn transfer_from_collateral<'info>(
-    token_program: &Program<'info, Token>,
-    from: &Account<'info, TokenAccount>,
-    to: &Account<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
     authority: &UncheckedAccount<'info>,
     authority_bump: u8,
     amount: u64,
@@ -16,14 +30,287 @@ pub fn transfer_from_collateral<'info>(
     let seeds: &[&[u8]] = &[b"engine-authority", &[authority_bump]];
     let signer = &[seeds];
 
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: from.to_account_info(),
+        mint: mint.to_account_info(),
         to: to.to_account_info(),
         authority: authority.to_account_info(),
     };
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
         amount,
+        mint.decimals,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_fee_split<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    collateral_vault: &InterfaceAccount<'info, TokenAccount>,
+    lp_pool: &Account<'info, lp_vault::Pool>,
+    lp_liquidity_vault: &InterfaceAccount<'info, TokenAccount>,
+    lp_insurance_vault: &InterfaceAccount<'info, TokenAccount>,
+    lp_protocol_fee_vault: &InterfaceAccount<'info, TokenAccount>,
+    engine_authority: &UncheckedAccount<'info>,
+    engine_authority_bump: u8,
+    fee: u64,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+
+    // lp_vault recomputes the same split when the fill is applied.
+    let (lp_fee, insurance_fee, protocol_fee) = lp_pool.split_fee(fee)?;
+
+    transfer_from_collateral(
+        token_program,
+        mint,
+        collateral_vault,
+        lp_liquidity_vault,
+        engine_authority,
+        engine_authority_bump,
+        lp_fee,
+    )?;
+
+    transfer_from_collateral(
+        token_program,
+        mint,
+        collateral_vault,
+        lp_insurance_vault,
+        engine_authority,
+        engine_authority_bump,
+        insurance_fee,
+    )?;
+
+    transfer_from_collateral(
+        token_program,
+        mint,
+        collateral_vault,
+        lp_protocol_fee_vault,
+        engine_authority,
+        engine_authority_bump,
+        protocol_fee,
+    )
+}
+
+// Maker rebates are funded from LP fee income: a fill gets at most what is
+// left of the pool's headroom after rebates already credited in this batch.
+pub fn clamp_maker_rebate(lp_pool: &lp_vault::Pool, already_credited: u64, requested: u64) -> u64 {
+    lp_pool
+        .maker_rebate_headroom()
+        .saturating_sub(already_credited)
+        .min(requested)
+}
+
+pub struct MakerRebateCpi<'a, 'info> {
+    pub lp_vault_program: &'a AccountInfo<'info>,
+    pub engine_authority: &'a AccountInfo<'info>,
+    pub engine_authority_bump: u8,
+    pub lp_pool: &'a AccountInfo<'info>,
+    pub lp_liquidity_auth: &'a AccountInfo<'info>,
+    pub lp_liquidity_vault: &'a AccountInfo<'info>,
+    pub collateral_vault: &'a AccountInfo<'info>,
+    pub usdc_mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+// Moves rebates already credited to makers' margin from the liquidity vault
+// into the collateral vault.
+pub fn cpi_pay_maker_rebate(accounts: &MakerRebateCpi<'_, '_>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let seeds: &[&[u8]] = &[b"engine-authority", &[accounts.engine_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = lp_vault::cpi::accounts::PayMakerRebate {
+        engine_authority: accounts.engine_authority.clone(),
+        pool: accounts.lp_pool.clone(),
+        liquidity_auth: accounts.lp_liquidity_auth.clone(),
+        liquidity_vault: accounts.lp_liquidity_vault.clone(),
+        destination: accounts.collateral_vault.clone(),
+        usdc_mint: accounts.usdc_mint.clone(),
+        token_program: accounts.token_program.clone(),
+    };
+
+    lp_vault::cpi::pay_maker_rebate(
+        CpiContext::new_with_signer(
+            accounts.lp_vault_program.clone(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+pub fn cpi_mark_nav<'info>(
+    lp_vault_program: &AccountInfo<'info>,
+    engine_authority: &AccountInfo<'info>,
+    engine_authority_bump: u8,
+    lp_pool: &AccountInfo<'info>,
+    unrealized_trader_pnl: i64,
+    open_interest: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"engine-authority", &[engine_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = lp_vault::cpi::accounts::MarkNav {
+        engine_authority: engine_authority.clone(),
+        pool: lp_pool.clone(),
+    };
+
+    lp_vault::cpi::mark_nav(
+        CpiContext::new_with_signer(lp_vault_program.clone(), cpi_accounts, signer_seeds),
+        unrealized_trader_pnl,
+        open_interest,
+    )
+}
+
+// The registry fee split is authoritative, but lp_vault owns its pool's split:
+// a change is handed to the pool, which adopts it after its own config delay.
+// Fees are always routed by the pool's current split, so this transfer and
+// `apply_trade_fill` agree.
+pub fn sync_lp_fee_split<'info>(
+    lp_vault_program: &AccountInfo<'info>,
+    engine_authority: &AccountInfo<'info>,
+    engine_authority_bump: u8,
+    lp_pool: &mut Account<'info, lp_vault::Pool>,
+    fee_split: &market_registry::FeeSplit,
+) -> Result<()> {
+    let split = (
+        fee_split.lp_bps,
+        fee_split.insurance_bps,
+        fee_split.protocol_bps,
+    );
+    if !lp_pool.fee_split_sync_due(split, Clock::get()?.unix_timestamp) {
+        return Ok(());
+    }
+
+    let seeds: &[&[u8]] = &[b"engine-authority", &[engine_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = lp_vault::cpi::accounts::SyncFeeSplit {
+        engine_authority: engine_authority.clone(),
+        pool: lp_pool.to_account_info(),
+    };
+
+    lp_vault::cpi::sync_fee_split(
+        CpiContext::new_with_signer(lp_vault_program.clone(), cpi_accounts, signer_seeds),
+        fee_split.lp_bps,
+        fee_split.insurance_bps,
+        fee_split.protocol_bps,
+    )?;
+    lp_pool.reload()
+}
+
+pub struct TradeFillCpi<'a, 'info> {
+    pub lp_vault_program: &'a AccountInfo<'info>,
+    pub engine_authority: &'a AccountInfo<'info>,
+    pub engine_authority_bump: u8,
+    pub lp_pool: &'a AccountInfo<'info>,
+    pub keeper: &'a AccountInfo<'info>,
+    pub keeper_rebate: Option<AccountInfo<'info>>,
+    pub lp_market_insurance: &'a AccountInfo<'info>,
+    pub lp_market_stats: &'a AccountInfo<'info>,
+    pub lp_liquidity_vault: &'a AccountInfo<'info>,
+    pub lp_insurance_vault: &'a AccountInfo<'info>,
+    pub lp_protocol_fee_vault: &'a AccountInfo<'info>,
+    pub lp_protocol_fee_auth: &'a AccountInfo<'info>,
+    pub keeper_token_account: Option<AccountInfo<'info>>,
+    pub usdc_mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn cpi_apply_trade_fill(
+    accounts: &TradeFillCpi<'_, '_>,
+    market_id: u64,
+    user: Pubkey,
+    order_id: u64,
+    notional: u64,
+    fee: u64,
+    realized_pnl: i64,
+    keeper_tip: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"engine-authority", &[accounts.engine_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = lp_vault::cpi::accounts::ApplyTradeFill {
+        engine_authority: accounts.engine_authority.clone(),
+        pool: accounts.lp_pool.clone(),
+        keeper: accounts.keeper.clone(),
+        keeper_rebate: accounts.keeper_rebate.clone(),
+        market_insurance: accounts.lp_market_insurance.clone(),
+        market_stats: accounts.lp_market_stats.clone(),
+        liquidity_vault: accounts.lp_liquidity_vault.clone(),
+        insurance_vault: accounts.lp_insurance_vault.clone(),
+        protocol_fee_vault: accounts.lp_protocol_fee_vault.clone(),
+        protocol_fee_auth: accounts.lp_protocol_fee_auth.clone(),
+        keeper_token_account: accounts.keeper_token_account.clone(),
+        usdc_mint: accounts.usdc_mint.clone(),
+        token_program: accounts.token_program.clone(),
+    };
+
+    lp_vault::cpi::apply_trade_fill(
+        CpiContext::new_with_signer(
+            accounts.lp_vault_program.clone(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        market_id,
+        user,
+        order_id,
+        notional,
+        fee,
+        realized_pnl,
+        keeper_tip,
+    )
+}
+
+pub struct LiquidationCpi<'a, 'info> {
+    pub lp_vault_program: &'a AccountInfo<'info>,
+    pub engine_authority: &'a AccountInfo<'info>,
+    pub engine_authority_bump: u8,
+    pub lp_pool: &'a AccountInfo<'info>,
+    pub lp_market_insurance: &'a AccountInfo<'info>,
+    pub lp_market_stats: &'a AccountInfo<'info>,
+    pub lp_insurance_vault: &'a AccountInfo<'info>,
+    pub keeper: &'a AccountInfo<'info>,
+}
+
+pub fn cpi_apply_liquidation(
+    accounts: &LiquidationCpi<'_, '_>,
+    market_id: u64,
+    user: Pubkey,
+    penalty: u64,
+    bad_debt: u64,
+    pnl_delta: i64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"engine-authority", &[accounts.engine_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = lp_vault::cpi::accounts::ApplyLiquidation {
+        engine_authority: accounts.engine_authority.clone(),
+        pool: accounts.lp_pool.clone(),
+        keeper: accounts.keeper.clone(),
+        market_insurance: accounts.lp_market_insurance.clone(),
+        market_stats: accounts.lp_market_stats.clone(),
+        insurance_vault: accounts.lp_insurance_vault.clone(),
+    };
+
+    lp_vault::cpi::apply_liquidation(
+        CpiContext::new_with_signer(
+            accounts.lp_vault_program.clone(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        market_id,
+        user,
+        penalty,
+        bad_debt,
+        pnl_delta,
     )
 }
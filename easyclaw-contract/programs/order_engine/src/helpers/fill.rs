@@ -0,0 +1,375 @@
+use anchor_lang::prelude::*;
+use market_registry::{Market, OracleStalePolicy};
+
+use crate::{
+    constants::PRICE_SCALE,
+    error::ErrorCode,
+    helpers::{
+        apply_fill_to_position, mul_bps_u64, realized_pnl, reduce_position, release_open_order,
+        require_position_leverage, require_post_trade_maintenance, settle_user_funding,
+        validate_execution_guard, validate_impact_price, validate_oracle, validate_order_price,
+        OracleFreshness,
+    },
+    state::{
+        MarketFundingState, Order, OrderStatus, OrderType, PositionLeg, Side, UserMargin,
+        UserMarketPosition,
+    },
+};
+
+pub struct FillOutcome {
+    pub notional: u64,
+    pub fee: u64,
+    // Requested before clamping to the pool's LP fee income; not yet credited.
+    pub maker_rebate: u64,
+    pub keeper_tip: u64,
+    pub qty: u64,
+    pub side: Side,
+    // Trader PnL realized by a reduce-only fill; zero when opening.
+    pub realized_pnl: i64,
+}
+
+// Returns `None` when the order had already expired and was closed without a fill.
+// The funding index must be updated by the caller before filling.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_order(
+    market: &Account<Market>,
+    funding_state: &mut MarketFundingState,
+    order: &mut Order,
+    margin: &mut Account<UserMargin>,
+    position: &mut UserMarketPosition,
+    clock: &Clock,
+    fill_price: u64,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
+    insurance_oi_headroom: &mut u64,
+) -> Result<Option<FillOutcome>> {
+    require!(fill_price > 0, ErrorCode::InvalidPrice);
+    require!(order.status() == OrderStatus::Open, ErrorCode::OrderNotOpen);
+    require!(
+        order.market_id == market.market_id,
+        ErrorCode::MarketMismatch
+    );
+
+    // Ownership is checked before the reservation is released, so an order can
+    // never be closed against another user's margin.
+    require_keys_eq!(margin.owner, order.user, ErrorCode::MarginOrderMismatch);
+    require_keys_eq!(
+        order.user_margin,
+        margin.key(),
+        ErrorCode::MarginOrderMismatch
+    );
+    require_keys_eq!(
+        position.user_margin,
+        margin.key(),
+        ErrorCode::PositionOwnerMismatch
+    );
+    require!(
+        position.market_id == market.market_id,
+        ErrorCode::MarketMismatch
+    );
+
+    let order_side = order.side();
+    let reduce_only = order.is_reduce_only();
+    let order_margin = order.margin;
+
+    release_open_order(margin, order)?;
+    let now = clock.unix_timestamp;
+    if now > order.expires_at {
+        order.set_status(OrderStatus::Expired);
+        return Ok(None);
+    }
+    // The tip came back with the reservation; it is spent only on a fill.
+    let keeper_tip = order.priority_tip;
+    margin.collateral_balance = margin
+        .collateral_balance
+        .checked_sub(keeper_tip)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    require_min_order_age(order, market.pricing_params.min_order_age_slots, clock.slot)?;
+
+    let freshness = validate_oracle(
+        market,
+        now,
+        fill_price,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let mut imr_bps = market.imr_bps_at(now) as u64;
+    let taker_fee_bps = market.taker_fee_bps_at(now) as u64;
+    if freshness == OracleFreshness::Stale {
+        match market.pricing_params.stale_policy {
+            OracleStalePolicy::ReduceOnly => {
+                require!(reduce_only, ErrorCode::StaleOracleReduceOnly)
+            }
+            OracleStalePolicy::WidenMargin => {
+                imr_bps = imr_bps
+                    .checked_add(market.pricing_params.stale_margin_add_bps as u64)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            }
+            OracleStalePolicy::RejectFills => return err!(ErrorCode::StaleOracle),
+        }
+    }
+    require!(
+        reduce_only || !funding_state.is_breaker_tripped(),
+        ErrorCode::CircuitBreakerReduceOnly
+    );
+
+    let notional = order_margin;
+    require!(notional > 0, ErrorCode::InvalidAmount);
+    require!(
+        notional <= market.risk_params.max_trade_notional,
+        ErrorCode::MaxTradeNotionalExceeded
+    );
+
+    let tick = market.risk_params.price_tick_size;
+    require!(
+        tick == 0 || fill_price.is_multiple_of(tick),
+        ErrorCode::PriceNotOnTick
+    );
+    let (order_qty, notional) =
+        quantize_fill(notional, fill_price, market.risk_params.qty_lot_size)?;
+
+    if let Some(trigger) = order.trigger() {
+        require!(trigger.is_met(oracle_price), ErrorCode::TriggerNotMet);
+    }
+    validate_execution_guard(order, now, oracle_price)?;
+    validate_order_price(order_side, order.order_type(), order.price, fill_price)?;
+
+    settle_user_funding(position, funding_state, margin)?;
+    let mmr_bps = market.mmr_bps_at(now) as u64;
+
+    if reduce_only {
+        let was_healthy = margin.collateral_balance >= mul_bps_u64(margin.total_notional, mmr_bps)?;
+        let close_leg = match order_side {
+            Side::Buy => PositionLeg::Short,
+            Side::Sell => PositionLeg::Long,
+        };
+        let reduced_notional = reduce_position(position, close_leg, order_qty, fill_price, now)?;
+        let realized_pnl = realized_pnl(close_leg, reduced_notional, notional)?;
+
+        margin.total_notional = margin
+            .total_notional
+            .checked_sub(reduced_notional)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+        funding_state.open_interest = funding_state
+            .open_interest
+            .checked_sub(reduced_notional)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+        funding_state.skew = match close_leg {
+            PositionLeg::Long => funding_state
+                .skew
+                .checked_sub(reduced_notional as i128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+            PositionLeg::Short => funding_state
+                .skew
+                .checked_add(reduced_notional as i128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+        };
+        funding_state.remove_open_qty(close_leg, order_qty)?;
+
+        let (fee, maker_rebate) = fill_fee(market, order, clock.slot, taker_fee_bps, notional)?;
+        require!(
+            margin.collateral_balance >= fee,
+            ErrorCode::InsufficientCollateral
+        );
+        margin.collateral_balance = margin
+            .collateral_balance
+            .checked_sub(fee)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        // Accounts already under MMR may still de-risk; healthy ones may not
+        // be pushed under it by the fee.
+        if was_healthy {
+            require_post_trade_maintenance(margin, mmr_bps)?;
+        }
+
+        order.set_status(OrderStatus::Executed);
+
+        return Ok(Some(FillOutcome {
+            notional,
+            fee,
+            maker_rebate,
+            keeper_tip,
+            qty: order_qty,
+            side: order_side,
+            realized_pnl,
+        }));
+    }
+
+    let projected_oi = funding_state
+        .open_interest
+        .checked_add(notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        projected_oi <= market.risk_params.oi_cap,
+        ErrorCode::OiCapExceeded
+    );
+    require!(
+        notional <= *insurance_oi_headroom,
+        ErrorCode::InsuranceCoverageTooLow
+    );
+
+    let projected_skew = match order_side {
+        Side::Buy => funding_state
+            .skew
+            .checked_add(notional as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+        Side::Sell => funding_state
+            .skew
+            .checked_sub(notional as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+    };
+
+    require!(
+        projected_skew.unsigned_abs() <= market.risk_params.skew_cap as u128,
+        ErrorCode::SkewCapExceeded
+    );
+
+    validate_impact_price(
+        order_side,
+        fill_price,
+        oracle_price,
+        projected_skew,
+        projected_oi,
+        &market.pricing_params,
+    )?;
+
+    let (fee, maker_rebate) = fill_fee(market, order, clock.slot, taker_fee_bps, notional)?;
+    require!(
+        margin.collateral_balance >= fee,
+        ErrorCode::InsufficientCollateral
+    );
+
+    margin.collateral_balance = margin
+        .collateral_balance
+        .checked_sub(fee)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let new_total_notional = margin
+        .total_notional
+        .checked_add(notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let imr_required = mul_bps_u64(new_total_notional, imr_bps)?;
+    require!(
+        margin.collateral_balance >= imr_required,
+        ErrorCode::MarginRequirementViolation
+    );
+
+    let leverage_num = new_total_notional
+        .checked_mul(1)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let leverage_den = margin.collateral_balance.max(1);
+    require!(
+        leverage_num <= leverage_den.saturating_mul(market.risk_params.max_leverage as u64),
+        ErrorCode::LeverageExceeded
+    );
+
+    let position_notional = position
+        .long_entry_notional
+        .checked_add(position.short_entry_notional)
+        .and_then(|total| u64::try_from(total).ok())
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require_position_leverage(
+        margin,
+        position_notional,
+        position_notional
+            .checked_add(notional)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+        imr_bps,
+        market.risk_params.max_leverage,
+    )?;
+
+    apply_fill_to_position(position, order_side, order_qty, notional, fill_price, now)?;
+
+    funding_state.open_interest = projected_oi;
+    *insurance_oi_headroom -= notional;
+    funding_state.skew = projected_skew;
+    funding_state.add_open_qty(
+        match order_side {
+            Side::Buy => PositionLeg::Long,
+            Side::Sell => PositionLeg::Short,
+        },
+        order_qty,
+    )?;
+    margin.total_notional = new_total_notional;
+    require_post_trade_maintenance(margin, mmr_bps)?;
+    order.set_status(OrderStatus::Executed);
+
+    Ok(Some(FillOutcome {
+        notional,
+        fee,
+        maker_rebate,
+        keeper_tip,
+        qty: order_qty,
+        side: order_side,
+        realized_pnl: 0,
+    }))
+}
+
+// Rounds the fill quantity down to whole lots and re-derives the notional
+// from it, so the position, fees and OI all reflect exactly what was filled.
+pub fn quantize_fill(notional: u64, fill_price: u64, lot_size: u64) -> Result<(u64, u64)> {
+    let raw_qty = ((notional as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?)
+    .checked_div(fill_price as u128)
+    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let lot_size = lot_size.max(1) as u128;
+    let qty = raw_qty - raw_qty % lot_size;
+    require!(qty > 0, ErrorCode::InvalidAmount);
+
+    let filled_notional = qty
+        .checked_mul(fill_price as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        / PRICE_SCALE;
+    require!(filled_notional > 0, ErrorCode::InvalidAmount);
+    Ok((
+        qty.try_into()
+            .map_err(|_| error!(ErrorCode::MathOverflow))?,
+        filled_notional as u64,
+    ))
+}
+
+// Keeps keepers from filling an order before it has aged `min_order_age_slots`.
+pub fn require_min_order_age(order: &Order, min_order_age_slots: u64, slot: u64) -> Result<()> {
+    let min_execution_slot = order
+        .created_slot
+        .checked_add(min_order_age_slots)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(slot >= min_execution_slot, ErrorCode::OrderTooYoung);
+    Ok(())
+}
+
+// Only a limit order that rested on the book for at least a slot added
+// liquidity; one filled in the slot it was placed was marketable on arrival
+// and pays the taker rate like a market order.
+pub fn earns_maker_fee(order: &Order, slot: u64) -> bool {
+    order.order_type() == OrderType::Limit && slot > order.created_slot
+}
+
+// Resting limit orders pay the maker rate; everything else pays the taker
+// rate. A negative maker rate is returned as a rebate.
+fn fill_fee(
+    market: &Account<Market>,
+    order: &Order,
+    slot: u64,
+    taker_fee_bps: u64,
+    notional: u64,
+) -> Result<(u64, u64)> {
+    if !earns_maker_fee(order, slot) {
+        return Ok((mul_bps_u64(notional, taker_fee_bps)?, 0));
+    }
+    let maker_fee_bps = market.fee_params.maker_fee_bps;
+    if maker_fee_bps >= 0 {
+        Ok((mul_bps_u64(notional, maker_fee_bps as u64)?, 0))
+    } else {
+        Ok((
+            0,
+            mul_bps_u64(notional, maker_fee_bps.unsigned_abs() as u64)?,
+        ))
+    }
+}
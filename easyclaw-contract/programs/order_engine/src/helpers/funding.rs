@@ -1,13 +1,35 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::{BPS_DENOM, FUNDING_SCALE},
+    constants::{BPS_DENOM, FUNDING_SCALE, PRICE_SCALE},
     error::ErrorCode,
+    helpers::settle_socialized_loss,
     state::{MarketFundingState, UserMargin, UserMarketPosition},
 };
 
+// A NAV mark must see every listed market exactly once, in increasing market
+// id order, so no market's exposure can be left out or counted twice.
+pub fn assert_covers_market_list(
+    market_ids: &[u64],
+    market_list: &market_registry::MarketList,
+) -> Result<()> {
+    let mut listed: Vec<u64> = market_list
+        .markets
+        .iter()
+        .map(|entry| entry.market_id)
+        .collect();
+    listed.sort_unstable();
+    require!(
+        market_ids == listed.as_slice(),
+        ErrorCode::MarketListMismatch
+    );
+    Ok(())
+}
+
+// The index accrues collateral per unit of position qty: the skew premium is
+// valued at `index_price` (the EMA) so a short spot wick does not move funding.
 pub fn update_funding_index(
-    funding_state: &mut Account<MarketFundingState>,
+    funding_state: &mut MarketFundingState,
     now: i64,
     params: &market_registry::FundingParams,
     oi_cap: u64,
@@ -53,7 +75,12 @@ pub fn update_funding_index(
         .checked_mul(FUNDING_SCALE)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let delta = interval_scaled.max(-max_scaled).min(max_scaled);
+    let premium_delta = interval_scaled.max(-max_scaled).min(max_scaled);
+    let delta = premium_delta
+        .checked_mul(funding_state.index_price as i128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(BPS_DENOM as i128 * PRICE_SCALE as i128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
     funding_state.funding_index = funding_state
         .funding_index
@@ -65,10 +92,12 @@ pub fn update_funding_index(
 }
 
 pub fn settle_user_funding(
-    position: &mut Account<UserMarketPosition>,
-    funding_state: &Account<MarketFundingState>,
+    position: &mut UserMarketPosition,
+    funding_state: &mut MarketFundingState,
     margin: &mut Account<UserMargin>,
 ) -> Result<()> {
+    settle_socialized_loss(position, funding_state, margin)?;
+
     let delta_long = funding_state
         .funding_index
         .checked_sub(position.last_funding_index_long)
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
+
+use crate::error::ErrorCode;
+
+// Execution must be a top-level instruction (not a CPI from another program),
+// and the only order_engine/lp_vault instructions allowed alongside it are
+// other fills. This keeps anyone from wrapping a fill with instructions that
+// move margin, orders or pool state in the same atomic transaction.
+pub fn assert_execution_not_bundled(instructions_sysvar: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        instructions_sysvar.key(),
+        anchor_lang::solana_program::sysvar::instructions::ID,
+        ErrorCode::UntrustedInstructionBundle
+    );
+
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let current = load_instruction_at_checked(current_index, instructions_sysvar)?;
+    require_keys_eq!(
+        current.program_id,
+        crate::ID,
+        ErrorCode::UntrustedInstructionBundle
+    );
+
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if ix.program_id == lp_vault::ID {
+            return err!(ErrorCode::UntrustedInstructionBundle);
+        }
+        if ix.program_id == crate::ID {
+            let is_fill = ix
+                .data
+                .starts_with(crate::instruction::ExecuteOrder::DISCRIMINATOR)
+                || ix
+                    .data
+                    .starts_with(crate::instruction::ExecuteOrders::DISCRIMINATOR);
+            require!(is_fill, ErrorCode::UntrustedInstructionBundle);
+        }
+    }
+
+    Ok(())
+}
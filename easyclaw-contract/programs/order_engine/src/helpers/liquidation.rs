@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use market_registry::Market;
+
+use crate::{
+    constants::PRICE_SCALE,
+    error::ErrorCode,
+    helpers::{mul_bps_u64, realized_pnl, reduce_position, settle_user_funding},
+    state::{MarketFundingState, PositionLeg, UserMargin, UserMarketPosition},
+};
+
+pub struct LiquidationOutcome {
+    pub penalty: u64,
+    pub keeper_portion: u64,
+    pub insurance_portion: u64,
+    pub bad_debt: u64,
+    pub realized_pnl: i64,
+}
+
+// The funding index must be updated by the caller before liquidating.
+#[allow(clippy::too_many_arguments)]
+pub fn liquidate_position(
+    market: &Account<Market>,
+    funding_state: &mut MarketFundingState,
+    margin: &mut Account<UserMargin>,
+    position: &mut UserMarketPosition,
+    leg: PositionLeg,
+    close_qty: u64,
+    liquidation_penalty_bps: u16,
+    oracle_price: u64,
+    now: i64,
+) -> Result<LiquidationOutcome> {
+    require!(close_qty > 0, ErrorCode::InvalidAmount);
+    require!(
+        position.market_id == market.market_id,
+        ErrorCode::MarketMismatch
+    );
+    require_keys_eq!(
+        position.user_margin,
+        margin.key(),
+        ErrorCode::PositionOwnerMismatch
+    );
+
+    settle_user_funding(position, funding_state, margin)?;
+
+    let mmr_required = mul_bps_u64(margin.total_notional, market.mmr_bps_at(now) as u64)?;
+    require!(
+        margin.collateral_balance < mmr_required,
+        ErrorCode::NotLiquidatable
+    );
+
+    let reduced_notional = reduce_position(position, leg, close_qty, oracle_price, now)?;
+    require!(reduced_notional > 0, ErrorCode::InvalidAmount);
+    let close_notional: u64 = (close_qty as u128)
+        .checked_mul(oracle_price as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(PRICE_SCALE)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .try_into()
+        .map_err(|_| error!(ErrorCode::MathOverflow))?;
+    let realized_pnl = realized_pnl(leg, reduced_notional, close_notional)?;
+
+    margin.total_notional = margin
+        .total_notional
+        .checked_sub(reduced_notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    funding_state.open_interest = funding_state
+        .open_interest
+        .checked_sub(reduced_notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    funding_state.skew = match leg {
+        PositionLeg::Long => funding_state
+            .skew
+            .checked_sub(reduced_notional as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+        PositionLeg::Short => funding_state
+            .skew
+            .checked_add(reduced_notional as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+    };
+
+    funding_state.remove_open_qty(leg, close_qty)?;
+
+    let penalty = mul_bps_u64(reduced_notional, liquidation_penalty_bps as u64)?;
+    let keeper_portion = mul_bps_u64(penalty, 1_000)?;
+    let insurance_portion = penalty
+        .checked_sub(keeper_portion)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let mut bad_debt = 0u64;
+    if margin.collateral_balance >= penalty {
+        margin.collateral_balance = margin
+            .collateral_balance
+            .checked_sub(penalty)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    } else {
+        bad_debt = penalty
+            .checked_sub(margin.collateral_balance)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        margin.collateral_balance = 0;
+    }
+
+    Ok(LiquidationOutcome {
+        penalty,
+        keeper_portion,
+        insurance_portion,
+        bad_debt,
+        realized_pnl,
+    })
+}
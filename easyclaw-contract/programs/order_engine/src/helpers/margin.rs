@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    helpers::mul_bps_u64,
+    state::{Order, UserMargin},
+};
+
+// Returns an order's reservation to the margin account and drops it from the
+// open-order exposure. Called whenever an order leaves the `Open` state.
+pub fn release_open_order(margin: &mut UserMargin, order: &Order) -> Result<()> {
+    margin.collateral_balance = margin
+        .collateral_balance
+        .checked_add(order.reserved_collateral)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    if !order.is_reduce_only() {
+        margin.open_order_notional = margin
+            .open_order_notional
+            .checked_sub(order.margin)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    }
+    Ok(())
+}
+
+// Margin required assuming every open risk-increasing order fills.
+pub fn pessimistic_margin_required(
+    total_notional: u64,
+    open_order_notional: u64,
+    imr_bps: u64,
+) -> Result<u64> {
+    let worst_case_notional = total_notional
+        .checked_add(open_order_notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    mul_bps_u64(worst_case_notional, imr_bps)
+}
+
+// Between MMR and IMR an account is reduce-only: it may not add risk or
+// withdraw until collateral covers the initial margin on its open notional.
+pub fn require_initial_margin_health(margin: &UserMargin, imr_bps: u64) -> Result<()> {
+    require!(
+        margin.collateral_balance >= mul_bps_u64(margin.total_notional, imr_bps)?,
+        ErrorCode::AccountUnhealthyReduceOnly
+    );
+    Ok(())
+}
+
+// A market's position may only be levered against the collateral left after
+// the initial margin of the account's exposure in every other market, so one
+// market cannot quietly use up headroom the others depend on.
+pub fn require_position_leverage(
+    margin: &UserMargin,
+    position_notional_before: u64,
+    position_notional_after: u64,
+    imr_bps: u64,
+    max_leverage: u16,
+) -> Result<()> {
+    let other_notional = margin
+        .total_notional
+        .checked_sub(position_notional_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let allocated_margin = margin
+        .collateral_balance
+        .saturating_sub(mul_bps_u64(other_notional, imr_bps)?);
+    require!(
+        position_notional_after as u128 <= allocated_margin as u128 * max_leverage as u128,
+        ErrorCode::PositionLeverageExceeded
+    );
+    Ok(())
+}
+
+// A fill must never leave an account that `liquidate` would accept at once.
+pub fn require_post_trade_maintenance(margin: &UserMargin, mmr_bps: u64) -> Result<()> {
+    require!(
+        margin.collateral_balance >= mul_bps_u64(margin.total_notional, mmr_bps)?,
+        ErrorCode::PostTradeBelowMaintenance
+    );
+    Ok(())
+}
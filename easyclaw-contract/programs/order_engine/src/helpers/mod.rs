@@ -1,15 +1,31 @@
 pub mod access;
+pub mod circuit_breaker;
+pub mod ed25519;
 pub mod execution;
+pub mod fill;
 pub mod funding;
+pub mod introspection;
+pub mod liquidation;
+pub mod margin;
 pub mod math;
 pub mod oracle;
 pub mod position;
 pub mod reservation;
+pub mod socialized_loss;
+pub mod twap;
 
 pub use access::*;
+pub use circuit_breaker::*;
+pub use ed25519::*;
 pub use execution::*;
+pub use fill::*;
 pub use funding::*;
+pub use introspection::*;
+pub use liquidation::*;
+pub use margin::*;
 pub use math::*;
 pub use oracle::*;
 pub use position::*;
 pub use reservation::*;
+pub use socialized_loss::*;
+pub use twap::*;
@@ -1,43 +1,312 @@
 use anchor_lang::prelude::*;
+use market_registry::OracleSource;
 
 use crate::{
     constants::{BPS_DENOM, PRICE_SCALE},
     error::ErrorCode,
-    helpers::math::abs_diff,
-    state::{OrderType, Side},
+    helpers::{load_verified_ed25519_message, math::abs_diff},
+    state::{Order, OrderType, Side},
 };
 
 const PYTH_PUSH_ORACLE_PROGRAM_ID: Pubkey = pubkey!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
 const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
 
-pub fn read_oracle_price_update(
+const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+pub(crate) const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+// Byte offsets into `PullFeedAccountData` (including the 8-byte discriminator).
+pub(crate) const SB_LAST_UPDATE_TIMESTAMP_OFFSET: usize = 2216;
+pub(crate) const SB_RESULT_VALUE_OFFSET: usize = 2264;
+pub(crate) const SB_RESULT_STD_DEV_OFFSET: usize = 2280;
+pub(crate) const SB_RESULT_NUM_SAMPLES_OFFSET: usize = 2360;
+const SWITCHBOARD_DECIMALS: i32 = 18;
+
+const FALLBACK_MESSAGE_LEN: usize = 32;
+
+pub(crate) const LAZER_PAYLOAD_MAGIC: u32 = 2_479_346_549;
+pub(crate) const LAZER_PROPERTY_PRICE: u8 = 0;
+pub(crate) const LAZER_PROPERTY_BEST_BID: u8 = 1;
+pub(crate) const LAZER_PROPERTY_BEST_ASK: u8 = 2;
+pub(crate) const LAZER_PROPERTY_PUBLISHER_COUNT: u8 = 3;
+pub(crate) const LAZER_PROPERTY_EXPONENT: u8 = 4;
+pub(crate) const LAZER_PROPERTY_CONFIDENCE: u8 = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OracleUse {
+    Execution,
+    Liquidation,
+}
+
+impl OracleUse {
+    fn max_staleness_sec(self, pricing: &market_registry::PricingParams) -> i64 {
+        match self {
+            OracleUse::Execution => pricing.max_oracle_staleness_sec,
+            OracleUse::Liquidation => pricing.liquidation_max_staleness_sec,
+        }
+    }
+
+    // Execution applies the market's stale policy and grace window; liquidation
+    // has a single hard limit.
+    fn check_freshness(
+        self,
+        pricing: &market_registry::PricingParams,
+        now: i64,
+        publish_time: i64,
+    ) -> Result<()> {
+        match self {
+            OracleUse::Execution => oracle_freshness(pricing, now, publish_time).map(|_| ()),
+            OracleUse::Liquidation => {
+                let age = now
+                    .checked_sub(publish_time)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                require!(age >= 0, ErrorCode::InvalidOracle);
+                require!(
+                    age <= pricing.liquidation_max_staleness_sec,
+                    ErrorCode::StaleOracle
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OracleFreshness {
+    Fresh,
+    // Past `max_oracle_staleness_sec` but inside the market's grace window.
+    Stale,
+}
+
+pub fn oracle_freshness(
+    pricing: &market_registry::PricingParams,
+    now: i64,
+    publish_time: i64,
+) -> Result<OracleFreshness> {
+    let age = now
+        .checked_sub(publish_time)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(age >= 0, ErrorCode::InvalidOracle);
+    if age <= pricing.max_oracle_staleness_sec {
+        return Ok(OracleFreshness::Fresh);
+    }
+
+    let grace_limit = pricing
+        .max_oracle_staleness_sec
+        .checked_add(pricing.stale_grace_sec)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        pricing.stale_policy != market_registry::OracleStalePolicy::RejectFills
+            && age <= grace_limit,
+        ErrorCode::StaleOracle
+    );
+    Ok(OracleFreshness::Stale)
+}
+
+// Outside Pyth Lazer markets, passing the instructions sysvar in the price
+// account slot selects the keeper's signed fallback quote.
+fn uses_signed_fallback(
     market: &Account<market_registry::Market>,
     price_update: &UncheckedAccount,
+) -> bool {
+    price_update.key() == anchor_lang::solana_program::sysvar::instructions::ID
+        && market.oracle_config.source != OracleSource::PythLazer
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn read_oracle_price_update<'info>(
+    market: &Account<market_registry::Market>,
+    price_update: &UncheckedAccount<'info>,
+    secondary_price_updates: &[&UncheckedAccount<'info>],
     clock: &Clock,
+    usage: OracleUse,
     fallback_oracle_price: u64,
     fallback_oracle_conf: u64,
     fallback_oracle_publish_time: i64,
 ) -> Result<(u64, u64, i64)> {
-    if price_update.key() == anchor_lang::solana_program::system_program::ID {
-        require!(fallback_oracle_price > 0, ErrorCode::InvalidOracle);
-        let publish_time = if fallback_oracle_publish_time <= 0 {
-            clock.unix_timestamp
-        } else {
-            fallback_oracle_publish_time
-        };
-        let age = clock
-            .unix_timestamp
-            .checked_sub(publish_time)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-        require!(age >= 0, ErrorCode::InvalidOracle);
+    if uses_signed_fallback(market, price_update) {
         require!(
-            age <= market.pricing_params.max_oracle_staleness_sec,
-            ErrorCode::StaleOracle
+            market.oracle_config.allow_keeper_fallback,
+            ErrorCode::KeeperFallbackOracleDisabled
         );
+        require!(
+            fallback_oracle_price > 0 && fallback_oracle_publish_time > 0,
+            ErrorCode::InvalidOracle
+        );
+        verify_signed_fallback_price(
+            market,
+            price_update,
+            fallback_oracle_price,
+            fallback_oracle_conf,
+            fallback_oracle_publish_time,
+        )?;
+        usage.check_freshness(
+            &market.pricing_params,
+            clock.unix_timestamp,
+            fallback_oracle_publish_time,
+        )?;
+
+        return Ok((
+            fallback_oracle_price,
+            fallback_oracle_conf,
+            fallback_oracle_publish_time,
+        ));
+    }
+
+    let (oracle_price, oracle_conf, publish_time) = match market.oracle_config.source {
+        OracleSource::PythPush => decode_pyth_push_price(market, price_update)?,
+        OracleSource::SwitchboardOnDemand => {
+            require_keys_eq!(
+                price_update.key(),
+                market.oracle_config.switchboard_feed,
+                ErrorCode::InvalidOracle
+            );
+            decode_switchboard_on_demand_price(price_update)?
+        }
+        OracleSource::PythLazer => decode_pyth_lazer_price(market, price_update)?,
+        OracleSource::Median => {
+            let mut feeds = Vec::with_capacity(1 + secondary_price_updates.len());
+            feeds.push(price_update);
+            feeds.extend_from_slice(secondary_price_updates);
+            read_median_price(market, &feeds, clock, usage)?
+        }
+    };
+    usage.check_freshness(&market.pricing_params, clock.unix_timestamp, publish_time)?;
+
+    Ok((oracle_price, oracle_conf, publish_time))
+}
+
+// Uses the median of the fresh configured feeds among `feeds`; at least two must
+// be fresh. Feeds that fail to decode or are stale are skipped.
+fn read_median_price(
+    market: &Account<market_registry::Market>,
+    feeds: &[&UncheckedAccount],
+    clock: &Clock,
+    usage: OracleUse,
+) -> Result<(u64, u64, i64)> {
+    let max_age = usage.max_staleness_sec(&market.pricing_params);
+    let configured = &market.oracle_config.median_feeds;
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(feeds.len());
+    let mut fresh: Vec<(u64, u64, i64)> = Vec::with_capacity(feeds.len());
+    for feed in feeds {
+        let key = feed.key();
+        require!(
+            key != Pubkey::default() && configured.contains(&key),
+            ErrorCode::InvalidOracle
+        );
+        require!(!seen.contains(&key), ErrorCode::InvalidOracle);
+        seen.push(key);
+
+        let decoded = if *feed.owner == PYTH_PUSH_ORACLE_PROGRAM_ID {
+            decode_pyth_push_price(market, feed)
+        } else if *feed.owner == SWITCHBOARD_ON_DEMAND_PROGRAM_ID {
+            decode_switchboard_on_demand_price(feed)
+        } else {
+            err!(ErrorCode::InvalidOracle)
+        };
+        let Ok(quote) = decoded else {
+            continue;
+        };
+        let age = clock.unix_timestamp.saturating_sub(quote.2);
+        if (0..=max_age).contains(&age) {
+            fresh.push(quote);
+        }
+    }
+    median_quote(fresh)
+}
+
+// Median of the fresh quotes. With two, the mean price and the wider
+// confidence are used. The publish time is always the oldest one.
+pub(crate) fn median_quote(mut fresh: Vec<(u64, u64, i64)>) -> Result<(u64, u64, i64)> {
+    require!(fresh.len() >= 2, ErrorCode::InsufficientFreshOracles);
+
+    fresh.sort_by_key(|quote| quote.0);
+    let publish_time = fresh.iter().map(|quote| quote.2).min().unwrap_or_default();
+    if fresh.len() == 2 {
+        let price = ((fresh[0].0 as u128 + fresh[1].0 as u128) / 2) as u64;
+        let conf = fresh[0].1.max(fresh[1].1);
+        return Ok((price, conf, publish_time));
+    }
+    let median = fresh[fresh.len() / 2];
+    Ok((median.0, median.1, publish_time))
+}
+
+// Returns the Pyth EMA price for Pyth push markets and the spot price for
+// sources without an EMA. `None` when the keeper used the fallback path, since
+// that price is not verified on-chain.
+pub fn read_index_price(
+    market: &Account<market_registry::Market>,
+    price_update: &UncheckedAccount,
+    oracle_price: u64,
+) -> Result<Option<u64>> {
+    if uses_signed_fallback(market, price_update) {
+        return Ok(None);
+    }
+    if market.oracle_config.source != OracleSource::PythPush {
+        return Ok(Some(oracle_price));
+    }
+
+    let message = decode_pyth_push_message(market, price_update)?;
+    let ema_price = scale_signed_price_to_engine(message.ema_price, message.exponent)?;
+    Ok(Some(ema_price))
+}
+
+pub fn validate_liquidation_confidence(
+    pricing: &market_registry::PricingParams,
+    oracle_price: u64,
+    oracle_conf: u64,
+) -> Result<()> {
+    let conf_bps = (oracle_conf as u128)
+        .checked_mul(BPS_DENOM)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(oracle_price as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        conf_bps <= pricing.liquidation_max_conf_bps as u128,
+        ErrorCode::OracleConfidenceTooWide
+    );
+    Ok(())
+}
 
-        return Ok((fallback_oracle_price, fallback_oracle_conf, publish_time));
+// Rejects liquidations while spot has wicked away from the EMA index price by
+// more than the market's band.
+pub fn validate_liquidation_band(
+    pricing: &market_registry::PricingParams,
+    oracle_price: u64,
+    index_price: Option<u64>,
+) -> Result<()> {
+    let Some(ema_price) = index_price else {
+        return Ok(());
+    };
+    if pricing.liquidation_ema_band_bps == 0 {
+        return Ok(());
     }
 
+    let deviation_bps = (abs_diff(oracle_price, ema_price) as u128)
+        .checked_mul(BPS_DENOM)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(ema_price as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        deviation_bps <= pricing.liquidation_ema_band_bps as u128,
+        ErrorCode::OracleEmaDeviationTooWide
+    );
+    Ok(())
+}
+
+fn decode_pyth_push_price(
+    market: &Account<market_registry::Market>,
+    price_update: &UncheckedAccount,
+) -> Result<(u64, u64, i64)> {
+    let message = decode_pyth_push_message(market, price_update)?;
+    let oracle_price = scale_signed_price_to_engine(message.price, message.exponent)?;
+    let oracle_conf = scale_confidence_to_engine(message.conf, message.exponent)?;
+    Ok((oracle_price, oracle_conf, message.publish_time))
+}
+
+fn decode_pyth_push_message(
+    market: &Account<market_registry::Market>,
+    price_update: &UncheckedAccount,
+) -> Result<PriceFeedMessageWire> {
     require_keys_eq!(
         *price_update.owner,
         PYTH_PUSH_ORACLE_PROGRAM_ID,
@@ -70,28 +339,206 @@ pub fn read_oracle_price_update(
         ErrorCode::InvalidOracle
     );
 
-    let publish_time = price_update.price_message.publish_time;
-    let age = clock
-        .unix_timestamp
-        .checked_sub(publish_time)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    require!(age >= 0, ErrorCode::InvalidOracle);
+    Ok(price_update.price_message)
+}
+
+fn decode_switchboard_on_demand_price(feed: &UncheckedAccount) -> Result<(u64, u64, i64)> {
+    require_keys_eq!(
+        *feed.owner,
+        SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+        ErrorCode::InvalidOracle
+    );
+
+    let data = feed
+        .try_borrow_data()
+        .map_err(|_| error!(ErrorCode::InvalidOracle))?;
+    decode_switchboard_pull_feed(&data)
+}
+
+pub(crate) fn decode_switchboard_pull_feed(data: &[u8]) -> Result<(u64, u64, i64)> {
     require!(
-        age <= market.pricing_params.max_oracle_staleness_sec,
-        ErrorCode::StaleOracle
+        data.len() > SB_RESULT_NUM_SAMPLES_OFFSET,
+        ErrorCode::InvalidOracle
     );
+    require!(
+        data[..8] == PULL_FEED_DISCRIMINATOR,
+        ErrorCode::InvalidOracle
+    );
+    require!(
+        data[SB_RESULT_NUM_SAMPLES_OFFSET] > 0,
+        ErrorCode::InvalidOracle
+    );
+
+    let publish_time = read_i64_le(data, SB_LAST_UPDATE_TIMESTAMP_OFFSET)?;
 
-    let oracle_price = scale_signed_price_to_engine(
-        price_update.price_message.price,
-        price_update.price_message.exponent,
+    let value = read_i128_le(data, SB_RESULT_VALUE_OFFSET)?;
+    let std_dev = read_i128_le(data, SB_RESULT_STD_DEV_OFFSET)?;
+    require!(value > 0 && std_dev >= 0, ErrorCode::InvalidPrice);
+
+    let oracle_price = scale_unsigned_value(value as u128, -SWITCHBOARD_DECIMALS)?;
+    require!(oracle_price > 0, ErrorCode::InvalidPrice);
+    let oracle_conf = scale_unsigned_value_ceil(std_dev as u128, -SWITCHBOARD_DECIMALS)?;
+    Ok((
+        u64::try_from(oracle_price).map_err(|_| error!(ErrorCode::MathOverflow))?,
+        u64::try_from(oracle_conf).map_err(|_| error!(ErrorCode::MathOverflow))?,
+        publish_time,
+    ))
+}
+
+// The keeper-supplied fallback quote must match the message signed by the
+// market's `fallback_signer` in the preceding ed25519 instruction: market id,
+// price, confidence and publish time as little-endian 8-byte words.
+fn verify_signed_fallback_price(
+    market: &Account<market_registry::Market>,
+    instructions_sysvar: &UncheckedAccount,
+    price: u64,
+    conf: u64,
+    publish_time: i64,
+) -> Result<()> {
+    let message = load_verified_ed25519_message(
+        &instructions_sysvar.to_account_info(),
+        &market.oracle_config.fallback_signer,
     )?;
-    let oracle_conf = scale_confidence_to_engine(
-        price_update.price_message.conf,
-        price_update.price_message.exponent,
+    require!(
+        message == fallback_message(market.market_id, price, conf, publish_time),
+        ErrorCode::InvalidSignedMessage
+    );
+    Ok(())
+}
+
+pub(crate) fn fallback_message(
+    market_id: u64,
+    price: u64,
+    conf: u64,
+    publish_time: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(FALLBACK_MESSAGE_LEN);
+    message.extend_from_slice(&market_id.to_le_bytes());
+    message.extend_from_slice(&price.to_le_bytes());
+    message.extend_from_slice(&conf.to_le_bytes());
+    message.extend_from_slice(&publish_time.to_le_bytes());
+    message
+}
+
+// For Pyth Lazer markets the price account slot carries the instructions
+// sysvar; the signed update is verified by the preceding ed25519 instruction.
+fn decode_pyth_lazer_price(
+    market: &Account<market_registry::Market>,
+    instructions_sysvar: &UncheckedAccount,
+) -> Result<(u64, u64, i64)> {
+    let message = load_verified_ed25519_message(
+        &instructions_sysvar.to_account_info(),
+        &market.oracle_config.lazer_signer,
     )?;
+    let update = parse_lazer_payload(&message, market.oracle_config.lazer_feed_id)?;
+
+    let publish_time = i64::try_from(update.timestamp_us / 1_000_000)
+        .map_err(|_| error!(ErrorCode::InvalidOracle))?;
+    let oracle_price = scale_signed_price_to_engine(update.price, update.exponent)?;
+    let oracle_conf = scale_confidence_to_engine(update.confidence, update.exponent)?;
     Ok((oracle_price, oracle_conf, publish_time))
 }
 
+pub(crate) struct LazerFeedUpdate {
+    pub timestamp_us: u64,
+    pub price: i64,
+    pub exponent: i32,
+    pub confidence: u64,
+}
+
+pub(crate) fn parse_lazer_payload(payload: &[u8], feed_id: u32) -> Result<LazerFeedUpdate> {
+    let mut reader = LazerReader { data: payload };
+    require!(
+        reader.u32()? == LAZER_PAYLOAD_MAGIC,
+        ErrorCode::InvalidOracle
+    );
+    let timestamp_us = reader.u64()?;
+    let _channel = reader.u8()?;
+    let feed_count = reader.u8()?;
+
+    for _ in 0..feed_count {
+        let id = reader.u32()?;
+        let property_count = reader.u8()?;
+        let mut price = None;
+        let mut exponent = None;
+        let mut confidence = 0u64;
+        for _ in 0..property_count {
+            match reader.u8()? {
+                LAZER_PROPERTY_PRICE => price = Some(reader.u64()? as i64),
+                LAZER_PROPERTY_BEST_BID | LAZER_PROPERTY_BEST_ASK => {
+                    reader.u64()?;
+                }
+                LAZER_PROPERTY_PUBLISHER_COUNT => {
+                    reader.u16()?;
+                }
+                LAZER_PROPERTY_EXPONENT => exponent = Some(reader.u16()? as i16 as i32),
+                LAZER_PROPERTY_CONFIDENCE => confidence = reader.u64()?,
+                _ => return err!(ErrorCode::InvalidOracle),
+            }
+        }
+        if id == feed_id {
+            return Ok(LazerFeedUpdate {
+                timestamp_us,
+                price: price.ok_or_else(|| error!(ErrorCode::InvalidOracle))?,
+                exponent: exponent.ok_or_else(|| error!(ErrorCode::InvalidOracle))?,
+                confidence,
+            });
+        }
+    }
+
+    err!(ErrorCode::InvalidOracle)
+}
+
+struct LazerReader<'a> {
+    data: &'a [u8],
+}
+
+impl LazerReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        require!(self.data.len() >= N, ErrorCode::InvalidOracle);
+        let (head, rest) = self.data.split_at(N);
+        self.data = rest;
+        head.try_into()
+            .map_err(|_| error!(ErrorCode::InvalidOracle))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+fn read_i64_le(data: &[u8], offset: usize) -> Result<i64> {
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or_else(|| error!(ErrorCode::InvalidOracle))?;
+    let bytes: [u8; 8] = bytes
+        .try_into()
+        .map_err(|_| error!(ErrorCode::InvalidOracle))?;
+    Ok(i64::from_le_bytes(bytes))
+}
+
+fn read_i128_le(data: &[u8], offset: usize) -> Result<i128> {
+    let bytes = data
+        .get(offset..offset + 16)
+        .ok_or_else(|| error!(ErrorCode::InvalidOracle))?;
+    let bytes: [u8; 16] = bytes
+        .try_into()
+        .map_err(|_| error!(ErrorCode::InvalidOracle))?;
+    Ok(i128::from_le_bytes(bytes))
+}
+
 pub fn validate_oracle(
     market: &Account<market_registry::Market>,
     now: i64,
@@ -99,15 +546,8 @@ pub fn validate_oracle(
     oracle_price: u64,
     oracle_conf: u64,
     oracle_publish_time: i64,
-) -> Result<()> {
-    let age = now
-        .checked_sub(oracle_publish_time)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    require!(age >= 0, ErrorCode::InvalidOracle);
-    require!(
-        age <= market.pricing_params.max_oracle_staleness_sec,
-        ErrorCode::StaleOracle
-    );
+) -> Result<OracleFreshness> {
+    let freshness = oracle_freshness(&market.pricing_params, now, oracle_publish_time)?;
 
     let conf_bps = ((oracle_conf as u128)
         .checked_mul(BPS_DENOM)
@@ -131,6 +571,29 @@ pub fn validate_oracle(
         ErrorCode::FillPriceDeviationTooLarge
     );
 
+    Ok(freshness)
+}
+
+pub fn validate_execution_guard(order: &Order, now: i64, oracle_price: u64) -> Result<()> {
+    if order.execution_deadline == 0 || now <= order.execution_deadline {
+        return Ok(());
+    }
+
+    let reference_price = order.reference_price;
+    let adverse_move = match order.side() {
+        Side::Buy => oracle_price.saturating_sub(reference_price),
+        Side::Sell => reference_price.saturating_sub(oracle_price),
+    };
+    let adverse_move_bps = (adverse_move as u128)
+        .checked_mul(BPS_DENOM)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(reference_price as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        adverse_move_bps <= order.max_adverse_move_bps as u128,
+        ErrorCode::ExecutionDeadlineAdverseMove
+    );
+
     Ok(())
 }
 
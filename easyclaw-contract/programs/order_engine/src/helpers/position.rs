@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    constants::PRICE_SCALE,
     error::ErrorCode,
     state::{PositionLeg, Side, UserMarketPosition},
 };
 
 pub fn apply_fill_to_position(
-    position: &mut Account<UserMarketPosition>,
+    position: &mut UserMarketPosition,
     side: Side,
     qty: u64,
     notional: u64,
+    fill_price: u64,
+    now: i64,
 ) -> Result<()> {
     match side {
         Side::Buy => {
@@ -33,15 +36,19 @@ pub fn apply_fill_to_position(
                 .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
         }
     }
-    Ok(())
+    refresh_position_prices(position, fill_price, now)
 }
 
+// Entry notional is reduced pro rata, so the average entry price of the
+// remaining qty is unchanged apart from rounding.
 pub fn reduce_position(
-    position: &mut Account<UserMarketPosition>,
+    position: &mut UserMarketPosition,
     leg: PositionLeg,
     close_qty: u64,
+    fill_price: u64,
+    now: i64,
 ) -> Result<u64> {
-    match leg {
+    let reduced_notional = match leg {
         PositionLeg::Long => {
             require!(position.long_qty >= close_qty, ErrorCode::InvalidCloseQty);
             let reduced_notional = ((position.long_entry_notional)
@@ -60,7 +67,7 @@ pub fn reduce_position(
                 .checked_sub(reduced_notional as u128)
                 .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-            Ok(reduced_notional)
+            reduced_notional
         }
         PositionLeg::Short => {
             require!(position.short_qty >= close_qty, ErrorCode::InvalidCloseQty);
@@ -80,7 +87,46 @@ pub fn reduce_position(
                 .checked_sub(reduced_notional as u128)
                 .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-            Ok(reduced_notional)
+            reduced_notional
         }
+    };
+    refresh_position_prices(position, fill_price, now)?;
+    Ok(reduced_notional)
+}
+
+// Trader PnL realized by closing `reduced_notional` of entry value on `leg`
+// for `close_notional` at the fill price; positive is a trader gain.
+pub fn realized_pnl(leg: PositionLeg, reduced_notional: u64, close_notional: u64) -> Result<i64> {
+    let pnl = match leg {
+        PositionLeg::Long => close_notional as i128 - reduced_notional as i128,
+        PositionLeg::Short => reduced_notional as i128 - close_notional as i128,
+    };
+    i64::try_from(pnl).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+fn average_entry_price(entry_notional: u128, qty: u64) -> Result<u64> {
+    if qty == 0 {
+        return Ok(0);
     }
+    entry_notional
+        .checked_mul(PRICE_SCALE)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(qty as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .try_into()
+        .map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+fn refresh_position_prices(
+    position: &mut UserMarketPosition,
+    fill_price: u64,
+    now: i64,
+) -> Result<()> {
+    position.long_avg_entry_price =
+        average_entry_price(position.long_entry_notional, position.long_qty)?;
+    position.short_avg_entry_price =
+        average_entry_price(position.short_entry_notional, position.short_qty)?;
+    position.last_fill_price = fill_price;
+    position.last_update_ts = now;
+    Ok(())
 }
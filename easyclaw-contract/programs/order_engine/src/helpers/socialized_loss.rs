@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::SOCIALIZED_LOSS_SCALE,
+    error::ErrorCode,
+    state::{MarketFundingState, PositionLeg, UserMargin, UserMarketPosition},
+};
+
+// Spreads `shortfall` over the side opposite `liquidated_leg` pro-rata to
+// entry notional. Returns false when that side has no open interest, in which
+// case there is nobody to charge and the caller must halt instead.
+pub fn socialize_shortfall(
+    funding_state: &mut MarketFundingState,
+    liquidated_leg: PositionLeg,
+    shortfall: u64,
+) -> Result<bool> {
+    if shortfall == 0 {
+        return Ok(true);
+    }
+
+    // open_interest = long + short and skew = long - short, both in entry notional.
+    let open_interest = funding_state.open_interest as i128;
+    let long_notional = open_interest
+        .checked_add(funding_state.skew)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        / 2;
+    let short_notional = open_interest
+        .checked_sub(long_notional)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let winning_notional = match liquidated_leg {
+        PositionLeg::Long => short_notional,
+        PositionLeg::Short => long_notional,
+    };
+    if winning_notional <= 0 {
+        return Ok(false);
+    }
+
+    // Round up so the haircuts cover at least the full shortfall.
+    let winning_notional = winning_notional as u128;
+    let delta = (shortfall as u128)
+        .checked_mul(SOCIALIZED_LOSS_SCALE)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .div_ceil(winning_notional);
+    let index = match liquidated_leg {
+        PositionLeg::Long => &mut funding_state.socialized_loss_index_short,
+        PositionLeg::Short => &mut funding_state.socialized_loss_index_long,
+    };
+    *index = index
+        .checked_add(delta)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    Ok(true)
+}
+
+// Charges the position's share of any loss socialized since it last settled.
+// Legs that were empty only pick up the current index, so positions opened
+// after a shortfall never pay for it. Returns the amount taken from margin.
+pub fn settle_socialized_loss(
+    position: &mut UserMarketPosition,
+    funding_state: &mut MarketFundingState,
+    margin: &mut UserMargin,
+) -> Result<u64> {
+    let mut owed = 0u128;
+    for (entry_notional, index, last_index) in [
+        (
+            position.long_entry_notional,
+            funding_state.socialized_loss_index_long,
+            &mut position.last_socialized_loss_index_long,
+        ),
+        (
+            position.short_entry_notional,
+            funding_state.socialized_loss_index_short,
+            &mut position.last_socialized_loss_index_short,
+        ),
+    ] {
+        let delta = index
+            .checked_sub(*last_index)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        owed = owed
+            .checked_add(
+                entry_notional
+                    .checked_mul(delta)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+                    / SOCIALIZED_LOSS_SCALE,
+            )
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        *last_index = index;
+    }
+
+    let charged = (owed.min(margin.collateral_balance as u128)) as u64;
+    if charged == 0 {
+        return Ok(0);
+    }
+    margin.collateral_balance -= charged;
+    position.socialized_loss_paid = position
+        .socialized_loss_paid
+        .checked_add(charged)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    funding_state.socialized_loss_collected = funding_state
+        .socialized_loss_collected
+        .checked_add(charged)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    Ok(charged)
+}
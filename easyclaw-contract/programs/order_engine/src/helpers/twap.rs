@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::BPS_DENOM, error::ErrorCode, helpers::abs_diff, state::MarketFundingState};
+
+// Moves the TWAP towards `oracle_price` in proportion to the time elapsed over
+// `window_sec`; after a full window without updates it restarts from spot.
+pub fn accrue_oracle_twap(
+    funding_state: &mut MarketFundingState,
+    window_sec: i64,
+    now: i64,
+    oracle_price: u64,
+) -> Result<()> {
+    let elapsed = now.saturating_sub(funding_state.oracle_twap_ts);
+    if funding_state.oracle_twap == 0 || window_sec <= 0 || elapsed >= window_sec {
+        funding_state.oracle_twap = oracle_price;
+        funding_state.oracle_twap_ts = now;
+        return Ok(());
+    }
+    if elapsed <= 0 {
+        return Ok(());
+    }
+
+    let twap = funding_state.oracle_twap as i128;
+    let step = (oracle_price as i128 - twap)
+        .checked_mul(elapsed as i128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(window_sec as i128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    funding_state.oracle_twap =
+        u64::try_from(twap + step).map_err(|_| error!(ErrorCode::MathOverflow))?;
+    funding_state.oracle_twap_ts = now;
+    Ok(())
+}
+
+// Liquidations only run while spot sits within `liquidation_twap_band_bps` of a
+// TWAP cranked inside the last window, so a single-print wick cannot trigger them.
+pub fn validate_liquidation_twap(
+    pricing: &market_registry::PricingParams,
+    funding_state: &MarketFundingState,
+    now: i64,
+    oracle_price: u64,
+) -> Result<()> {
+    if pricing.liquidation_twap_band_bps == 0 {
+        return Ok(());
+    }
+    let twap = funding_state.oracle_twap;
+    require!(
+        twap > 0 && now.saturating_sub(funding_state.oracle_twap_ts) <= pricing.twap_window_sec,
+        ErrorCode::OracleTwapUnavailable
+    );
+
+    let deviation_bps = (abs_diff(oracle_price, twap) as u128)
+        .checked_mul(BPS_DENOM)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        .checked_div(twap as u128)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    require!(
+        deviation_bps <= pricing.liquidation_twap_band_bps as u128,
+        ErrorCode::OracleTwapDeviationTooWide
+    );
+    Ok(())
+}
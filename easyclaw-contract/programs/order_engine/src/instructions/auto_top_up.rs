@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    error::ErrorCode,
+    events::CollateralToppedUp,
+    helpers::{assert_keeper_only, mul_bps_u64},
+    state::{EngineConfig, EngineStats, UserMargin},
+};
+
+// Pulls just enough from the user's delegated token account to lift
+// collateral back to the warning threshold, bounded by the remaining allowance.
+pub fn handler(ctx: Context<AutoTopUp>, _market_id: u64) -> Result<()> {
+    assert_keeper_only(&ctx.accounts.executor, &ctx.accounts.keeper_auth)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let margin = &ctx.accounts.user_margin;
+    let source = &ctx.accounts.top_up_source;
+    require!(
+        margin.top_up_allowance > 0,
+        ErrorCode::TopUpAllowanceExhausted
+    );
+    require_keys_eq!(
+        source.key(),
+        margin.top_up_source,
+        ErrorCode::TopUpSourceMismatch
+    );
+
+    let mmr_required = mul_bps_u64(
+        margin.total_notional,
+        ctx.accounts.market.mmr_bps_at(now) as u64,
+    )?;
+    let warning_level = mul_bps_u64(mmr_required, margin.top_up_threshold_bps as u64)?;
+    require!(
+        margin.collateral_balance < warning_level,
+        ErrorCode::TopUpNotNeeded
+    );
+
+    let delegated = if source
+        .delegate
+        .contains(&ctx.accounts.engine_authority.key())
+    {
+        source.delegated_amount
+    } else {
+        0
+    };
+    let amount = (warning_level - margin.collateral_balance)
+        .min(margin.top_up_allowance)
+        .min(delegated)
+        .min(source.amount);
+    require!(amount > 0, ErrorCode::TopUpAllowanceExhausted);
+
+    let engine_authority_bump = ctx.bumps.engine_authority;
+    let signer_seed_group: &[&[u8]] = &[b"engine-authority", &[engine_authority_bump]];
+    let signer_seeds = &[signer_seed_group];
+    let vault_before = ctx.accounts.collateral_vault.amount;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.top_up_source.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.collateral_vault.to_account_info(),
+                authority: ctx.accounts.engine_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+    // The allowance is spent on what left the source; the margin is credited
+    // with what arrived after any Token-2022 transfer fee.
+    ctx.accounts.collateral_vault.reload()?;
+    let received = ctx
+        .accounts
+        .collateral_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let margin = &mut ctx.accounts.user_margin;
+    margin.collateral_balance = margin
+        .collateral_balance
+        .checked_add(received)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    margin.top_up_allowance = margin
+        .top_up_allowance
+        .checked_sub(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.engine_stats.credit_collateral(received);
+
+    emit!(CollateralToppedUp {
+        keeper: ctx.accounts.executor.key(),
+        user: margin.owner,
+        user_margin: margin.key(),
+        amount: received,
+        remaining_allowance: margin.top_up_allowance,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct AutoTopUp<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    pub keeper_auth: Box<Account<'info, market_registry::KeeperAuthorization>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, market_registry::Market>>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user_margin.owner.as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Box<Account<'info, UserMargin>>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = top_up_source.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
+        constraint = top_up_source.owner == user_margin.owner @ ErrorCode::Unauthorized,
+    )]
+    pub top_up_source: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
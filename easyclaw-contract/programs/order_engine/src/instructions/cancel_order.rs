@@ -1,25 +1,18 @@
 use anchor_lang::prelude::*;
-use market_registry::program::MarketRegistry;
 
 use crate::{
     error::ErrorCode,
-    helpers::estimate_order_reservation,
-    state::{Order, OrderStatus, UserMargin},
+    helpers::release_open_order,
+    state::{EngineStats, Order, OrderStatus, UserMargin},
 };
 
 pub fn handler(ctx: Context<CancelOrder>) -> Result<()> {
-    let order = &mut ctx.accounts.order;
-    require!(order.status == OrderStatus::Open, ErrorCode::OrderNotOpen);
+    let mut order = ctx.accounts.order.load_mut()?;
+    require!(order.status() == OrderStatus::Open, ErrorCode::OrderNotOpen);
 
-    let reserved_collateral =
-        estimate_order_reservation(order.reduce_only, order.margin, &ctx.accounts.market)?;
-    ctx.accounts.user_margin.collateral_balance = ctx
-        .accounts
-        .user_margin
-        .collateral_balance
-        .checked_add(reserved_collateral)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    order.status = OrderStatus::Cancelled;
+    release_open_order(&mut ctx.accounts.user_margin, &order)?;
+    order.set_status(OrderStatus::Cancelled);
+    ctx.accounts.engine_stats.order_closed();
 
     Ok(())
 }
@@ -27,13 +20,6 @@ pub fn handler(ctx: Context<CancelOrder>) -> Result<()> {
 #[derive(Accounts)]
 pub struct CancelOrder<'info> {
     pub user: Signer<'info>,
-    pub market_registry_program: Program<'info, MarketRegistry>,
-    #[account(
-        seeds = [b"market".as_ref(), &order.market_id.to_le_bytes()],
-        seeds::program = market_registry_program.key(),
-        bump = market.bump,
-    )]
-    pub market: Account<'info, market_registry::Market>,
     #[account(
         mut,
         seeds = [b"user-margin", user.key().as_ref()],
@@ -43,8 +29,14 @@ pub struct CancelOrder<'info> {
     pub user_margin: Account<'info, UserMargin>,
     #[account(
         mut,
-        constraint = order.user == user.key() @ ErrorCode::Unauthorized,
-        constraint = order.user_margin == user_margin.key() @ ErrorCode::MarginOrderMismatch,
+        constraint = order.load()?.user == user.key() @ ErrorCode::Unauthorized,
+        constraint = order.load()?.user_margin == user_margin.key() @ ErrorCode::MarginOrderMismatch,
+    )]
+    pub order: AccountLoader<'info, Order>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
     )]
-    pub order: Account<'info, Order>,
+    pub engine_stats: Account<'info, EngineStats>,
 }
@@ -1,33 +1,27 @@
 use anchor_lang::prelude::*;
-use market_registry::program::MarketRegistry;
+use market_registry::KeeperScope;
 
 use crate::{
     error::ErrorCode,
-    helpers::{assert_executor_authorized, estimate_order_reservation},
-    state::{EngineConfig, Order, OrderStatus, UserMargin},
+    helpers::{assert_executor_authorized, release_open_order},
+    state::{EngineConfig, EngineStats, Order, OrderStatus, UserMargin},
 };
 
 pub fn handler(ctx: Context<CancelOrderByExecutor>) -> Result<()> {
     assert_executor_authorized(
         &ctx.accounts.executor,
         &ctx.accounts.global_config,
-        &ctx.accounts.keeper_set,
+        ctx.accounts.keeper_auth.as_deref(),
+        KeeperScope::CancelByExecutor,
     )?;
 
-    let order = &mut ctx.accounts.order;
-    require!(order.status == OrderStatus::Open, ErrorCode::OrderNotOpen);
+    let mut order = ctx.accounts.order.load_mut()?;
+    require!(order.status() == OrderStatus::Open, ErrorCode::OrderNotOpen);
 
-    let reserved_collateral =
-        estimate_order_reservation(order.reduce_only, order.margin, &ctx.accounts.market)?;
+    release_open_order(&mut ctx.accounts.user_margin, &order)?;
 
-    ctx.accounts.user_margin.collateral_balance = ctx
-        .accounts
-        .user_margin
-        .collateral_balance
-        .checked_add(reserved_collateral)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-    order.status = OrderStatus::Cancelled;
+    order.set_status(OrderStatus::Cancelled);
+    ctx.accounts.engine_stats.order_closed();
 
     Ok(())
 }
@@ -40,27 +34,25 @@ pub struct CancelOrderByExecutor<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Account<'info, EngineConfig>,
-    pub market_registry_program: Program<'info, MarketRegistry>,
-    #[account(address = engine_config.registry_global_config)]
-    pub global_config: Account<'info, market_registry::GlobalConfig>,
-    #[account(address = engine_config.keeper_set)]
-    pub keeper_set: Account<'info, market_registry::KeeperSet>,
     #[account(
-        seeds = [b"market".as_ref(), &order.market_id.to_le_bytes()],
-        seeds::program = market_registry_program.key(),
-        bump = market.bump,
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
     )]
-    pub market: Account<'info, market_registry::Market>,
+    pub engine_stats: Account<'info, EngineStats>,
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
+    pub global_config: Account<'info, market_registry::GlobalConfig>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
     #[account(
         mut,
-        seeds = [b"user-margin", order.user.as_ref()],
+        seeds = [b"user-margin", order.load()?.user.as_ref()],
         bump = user_margin.bump,
-        constraint = user_margin.owner == order.user @ ErrorCode::MarginOrderMismatch,
+        constraint = user_margin.owner == order.load()?.user @ ErrorCode::MarginOrderMismatch,
     )]
     pub user_margin: Account<'info, UserMargin>,
     #[account(
         mut,
-        constraint = order.user_margin == user_margin.key() @ ErrorCode::MarginOrderMismatch,
+        constraint = order.load()?.user_margin == user_margin.key() @ ErrorCode::MarginOrderMismatch,
     )]
-    pub order: Account<'info, Order>,
+    pub order: AccountLoader<'info, Order>,
 }
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use lp_vault::program::LpVault;
+
+use crate::{
+    error::ErrorCode,
+    events::UserCompensated,
+    helpers::require_admin,
+    state::{EngineConfig, EngineStats, UserMargin},
+};
+
+pub fn handler(ctx: Context<CompensateUser>, amount: u64, reason_code: u16) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let vault_before = ctx.accounts.collateral_vault.amount;
+    let seeds: &[&[u8]] = &[b"engine-authority", &[ctx.bumps.engine_authority]];
+    let signer_seeds = &[seeds];
+    let cpi_accounts = lp_vault::cpi::accounts::PayInsurance {
+        engine_authority: ctx.accounts.engine_authority.to_account_info(),
+        pool: ctx.accounts.lp_pool.to_account_info(),
+        insurance_auth: ctx.accounts.lp_insurance_auth.to_account_info(),
+        insurance_vault: ctx.accounts.lp_insurance_vault.to_account_info(),
+        destination: ctx.accounts.collateral_vault.to_account_info(),
+        usdc_mint: ctx.accounts.usdc_mint.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    lp_vault::cpi::pay_insurance(
+        CpiContext::new_with_signer(
+            ctx.accounts.lp_vault_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    // Credit what arrived after any Token-2022 transfer fee.
+    ctx.accounts.collateral_vault.reload()?;
+    let amount = ctx
+        .accounts
+        .collateral_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    let margin = &mut ctx.accounts.user_margin;
+    margin.collateral_balance = margin
+        .collateral_balance
+        .checked_add(amount)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.engine_stats.credit_collateral(amount);
+
+    emit!(UserCompensated {
+        authority: ctx.accounts.admin.key(),
+        user: margin.owner,
+        user_margin: margin.key(),
+        amount,
+        reason_code,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CompensateUser<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user_margin.owner.as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Box<Account<'info, UserMargin>>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub lp_vault_program: Program<'info, LpVault>,
+    #[account(address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
+    pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
+    /// CHECK: insurance auth PDA, validated by lp_vault.
+    pub lp_insurance_auth: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
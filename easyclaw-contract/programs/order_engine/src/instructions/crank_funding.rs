@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use market_registry::{program::MarketRegistry, KeeperScope};
+
+use crate::{
+    error::ErrorCode,
+    helpers::{
+        accrue_oracle_twap, assert_keeper_scope, read_index_price, read_oracle_price_update,
+        update_funding_index, OracleUse,
+    },
+    state::{EngineConfig, MarketFundingState},
+};
+
+pub fn handler(
+    ctx: Context<CrankFunding>,
+    market_id: u64,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let market = &ctx.accounts.market;
+
+    assert_keeper_scope(
+        &ctx.accounts.executor,
+        &ctx.accounts.keeper_auth,
+        KeeperScope::CrankFunding,
+    )?;
+    require!(market.market_id == market_id, ErrorCode::MarketMismatch);
+
+    let secondary_oracles: Vec<&UncheckedAccount> = [
+        ctx.accounts.secondary_oracle.as_ref(),
+        ctx.accounts.tertiary_oracle.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let (oracle_price, _, _) = read_oracle_price_update(
+        market,
+        &ctx.accounts.oracle_price_update,
+        &secondary_oracles,
+        &clock,
+        OracleUse::Execution,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, oracle_price)?;
+
+    let mut funding_state = ctx.accounts.market_funding_state.load_mut()?;
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &market.funding_params,
+        market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+    accrue_oracle_twap(
+        &mut funding_state,
+        market.pricing_params.twap_window_sec,
+        now,
+        oracle_price,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CrankFunding<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    pub keeper_auth: Box<Account<'info, market_registry::KeeperAuthorization>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, market_registry::Market>>,
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
+    pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+}
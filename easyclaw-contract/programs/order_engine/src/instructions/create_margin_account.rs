@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{EngineConfig, UserMargin};
+use crate::state::{EngineConfig, EngineStats, UserMargin};
 
 pub fn handler(ctx: Context<CreateMarginAccount>) -> Result<()> {
     let margin = &mut ctx.accounts.user_margin;
@@ -8,7 +8,12 @@ pub fn handler(ctx: Context<CreateMarginAccount>) -> Result<()> {
     margin.collateral_balance = 0;
     margin.next_order_nonce = 0;
     margin.total_notional = 0;
+    margin.open_order_notional = 0;
     margin.bump = ctx.bumps.user_margin;
+    margin.top_up_source = Pubkey::default();
+    margin.top_up_allowance = 0;
+    margin.top_up_threshold_bps = 0;
+    ctx.accounts.engine_stats.record_user();
 
     Ok(())
 }
@@ -22,6 +27,12 @@ pub struct CreateMarginAccount<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
     #[account(
         init,
         payer = user,
@@ -12,7 +12,7 @@ pub fn handler(ctx: Context<CreateUserMarketPosition>, market_id: u64) -> Result
         ErrorCode::Unauthorized
     );
 
-    let pos = &mut ctx.accounts.user_market_position;
+    let mut pos = ctx.accounts.user_market_position.load_init()?;
     pos.user_margin = ctx.accounts.user_margin.key();
     pos.market_id = market_id;
     pos.long_qty = 0;
@@ -21,6 +21,13 @@ pub fn handler(ctx: Context<CreateUserMarketPosition>, market_id: u64) -> Result
     pos.short_entry_notional = 0;
     pos.last_funding_index_long = 0;
     pos.last_funding_index_short = 0;
+    pos.last_socialized_loss_index_long = 0;
+    pos.last_socialized_loss_index_short = 0;
+    pos.socialized_loss_paid = 0;
+    pos.long_avg_entry_price = 0;
+    pos.short_avg_entry_price = 0;
+    pos.last_fill_price = 0;
+    pos.last_update_ts = 0;
     pos.bump = ctx.bumps.user_market_position;
 
     Ok(())
@@ -47,8 +54,8 @@ pub struct CreateUserMarketPosition<'info> {
         payer = user,
         seeds = [b"user-market-pos", user_margin.key().as_ref(), &market_id.to_le_bytes()],
         bump,
-        space = 8 + UserMarketPosition::INIT_SPACE,
+        space = 8 + std::mem::size_of::<UserMarketPosition>(),
     )]
-    pub user_market_position: Account<'info, UserMarketPosition>,
+    pub user_market_position: AccountLoader<'info, UserMarketPosition>,
     pub system_program: Program<'info, System>,
 }
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
     error::ErrorCode,
-    state::{EngineConfig, UserMargin},
+    state::{EngineConfig, EngineStats, UserMargin},
 };
 
 pub fn handler(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
@@ -14,7 +14,21 @@ pub fn handler(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
         ErrorCode::Unauthorized
     );
 
-    token::transfer(ctx.accounts.deposit_ctx(), amount)?;
+    let vault_before = ctx.accounts.collateral_vault.amount;
+    token_interface::transfer_checked(
+        ctx.accounts.deposit_ctx(),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+    // Token-2022 transfer fees are withheld from the vault side, so only what
+    // actually arrived is credited.
+    ctx.accounts.collateral_vault.reload()?;
+    let amount = ctx
+        .accounts
+        .collateral_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
     ctx.accounts.user_margin.collateral_balance = ctx
         .accounts
@@ -22,6 +36,7 @@ pub fn handler(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
         .collateral_balance
         .checked_add(amount)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.engine_stats.credit_collateral(amount);
 
     Ok(())
 }
@@ -35,6 +50,12 @@ pub struct DepositCollateral<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
     #[account(
         mut,
         seeds = [b"user-margin", user.key().as_ref()],
@@ -46,16 +67,19 @@ pub struct DepositCollateral<'info> {
         constraint = user_token_account.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
         constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut, address = engine_config.collateral_vault)]
-    pub collateral_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> DepositCollateral<'info> {
-    fn deposit_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
+    fn deposit_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
             from: self.user_token_account.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
             to: self.collateral_vault.to_account_info(),
             authority: self.user.to_account_info(),
         };
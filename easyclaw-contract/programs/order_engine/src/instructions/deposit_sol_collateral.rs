@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer as SystemTransfer},
+};
+use anchor_spl::token::{self, spl_token::native_mint, SyncNative, Token, TokenAccount};
+
+use crate::{
+    error::ErrorCode,
+    state::{EngineConfig, EngineStats, UserMargin},
+};
+
+// Only for engines whose collateral mint is wSOL: lamports go straight into the
+// native collateral vault and `sync_native` turns them into token balance.
+pub fn handler(ctx: Context<DepositSolCollateral>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, ErrorCode::InvalidAmount);
+    require_keys_eq!(
+        ctx.accounts.engine_config.usdc_mint,
+        native_mint::ID,
+        ErrorCode::SolCollateralDisabled
+    );
+    require_keys_eq!(
+        ctx.accounts.user_margin.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.collateral_vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.collateral_vault.to_account_info(),
+        },
+    ))?;
+
+    ctx.accounts.user_margin.collateral_balance = ctx
+        .accounts
+        .user_margin
+        .collateral_balance
+        .checked_add(lamports)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.engine_stats.credit_collateral(lamports);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositSolCollateral<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user.key().as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Account<'info, UserMargin>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
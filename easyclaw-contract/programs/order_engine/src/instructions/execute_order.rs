@@ -1,19 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use lp_vault::program::LpVault;
-use market_registry::program::MarketRegistry;
+use market_registry::{program::MarketRegistry, KeeperScope};
 
 use crate::{
-    constants::PRICE_SCALE,
     error::ErrorCode,
     helpers::{
-        apply_fill_to_position, assert_executor_authorized, estimate_order_reservation,
-        mul_bps_u64, read_oracle_price_update, reduce_position, settle_user_funding,
-        transfer_from_collateral, update_funding_index, validate_impact_price, validate_oracle,
-        validate_order_price,
+        assert_execution_not_bundled, assert_executor_authorized, clamp_maker_rebate,
+        cpi_apply_trade_fill, cpi_pay_maker_rebate, fill_order, observe_oracle_price,
+        read_index_price, read_oracle_price_update, sync_lp_fee_split, transfer_fee_split,
+        transfer_from_collateral, update_funding_index, MakerRebateCpi, OracleUse, TradeFillCpi,
     },
     state::{
-        EngineConfig, MarketFundingState, Order, OrderStatus, PositionLeg, UserMargin,
+        EngineConfig, EngineStats, MarketFundingState, MarketStats, Order, RecentFills, UserMargin,
         UserMarketPosition,
     },
 };
@@ -31,310 +30,209 @@ pub fn handler(
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     let global_config = &ctx.accounts.global_config;
-    let keeper_set = &ctx.accounts.keeper_set;
     let market = &ctx.accounts.market;
-    let funding_state = &mut ctx.accounts.market_funding_state;
-    let margin = &mut ctx.accounts.user_margin;
-    let order = &mut ctx.accounts.order;
-    let position = &mut ctx.accounts.user_market_position;
     let keeper_rebate = &ctx.accounts.keeper_rebate;
-    let order_side = order.side;
-    let order_type = order.order_type;
-    let order_price = order.price;
-    let order_margin = order.margin;
 
-    assert_executor_authorized(&ctx.accounts.executor, global_config, keeper_set)?;
+    assert_executor_authorized(
+        &ctx.accounts.executor,
+        global_config,
+        ctx.accounts.keeper_auth.as_deref(),
+        KeeperScope::Execute,
+    )?;
+    assert_execution_not_bundled(&ctx.accounts.instructions_sysvar.to_account_info())?;
     require!(!global_config.global_pause, ErrorCode::GlobalPaused);
     require!(
-        market.status == market_registry::MarketStatus::Active,
+        market.status.allows_execution(true),
         ErrorCode::MarketNotActive
     );
-    require!(!funding_state.halted, ErrorCode::MarketHaltedLocal);
+    if let Some(keeper_rebate) = keeper_rebate {
+        require_keys_eq!(
+            keeper_rebate.pool,
+            ctx.accounts.lp_pool.key(),
+            ErrorCode::InvalidKeeperRebateAccount
+        );
+        require_keys_eq!(
+            keeper_rebate.keeper,
+            ctx.accounts.executor.key(),
+            ErrorCode::InvalidKeeperRebateAccount
+        );
+    }
 
-    require!(order.status == OrderStatus::Open, ErrorCode::OrderNotOpen);
+    let order = ctx.accounts.order.load()?;
+    let order_expires_at = order.expires_at;
     require!(
-        order.market_id == market.market_id,
-        ErrorCode::MarketMismatch
+        market.status.allows_execution(order.is_reduce_only()),
+        ErrorCode::MarketReduceOnly
     );
+    drop(order);
+    let (oracle_price, oracle_conf, oracle_publish_time, index_price) = if now > order_expires_at {
+        (oracle_price, oracle_conf, oracle_publish_time, None)
+    } else {
+        let secondary_oracles: Vec<&UncheckedAccount> = [
+            ctx.accounts.secondary_oracle.as_ref(),
+            ctx.accounts.tertiary_oracle.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let (price, conf, publish_time) = read_oracle_price_update(
+            market,
+            &ctx.accounts.oracle_price_update,
+            &secondary_oracles,
+            &clock,
+            OracleUse::Execution,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )?;
+        let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, price)?;
+        (price, conf, publish_time, index_price)
+    };
 
-    let reserved_collateral = estimate_order_reservation(order.reduce_only, order.margin, market)?;
-
-    if now > order.expires_at {
-        margin.collateral_balance = margin
-            .collateral_balance
-            .checked_add(reserved_collateral)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-        order.status = OrderStatus::Expired;
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    require!(!funding_state.is_halted(), ErrorCode::MarketHaltedLocal);
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &accounts.market.funding_params,
+        accounts.market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+    if now <= order_expires_at
+        && observe_oracle_price(
+            &mut funding_state,
+            &accounts.market.pricing_params,
+            now,
+            oracle_price,
+        )?
+    {
         return Ok(());
     }
 
-    require_keys_eq!(margin.owner, order.user, ErrorCode::MarginOrderMismatch);
-    require_keys_eq!(
-        order.user_margin,
-        margin.key(),
-        ErrorCode::MarginOrderMismatch
-    );
-    require_keys_eq!(
-        position.user_margin,
-        margin.key(),
-        ErrorCode::PositionOwnerMismatch
-    );
-    require!(
-        position.market_id == market.market_id,
-        ErrorCode::MarketMismatch
-    );
-    require_keys_eq!(
-        keeper_rebate.pool,
-        ctx.accounts.lp_pool.key(),
-        ErrorCode::InvalidKeeperRebateAccount
-    );
-    require_keys_eq!(
-        keeper_rebate.keeper,
-        ctx.accounts.executor.key(),
-        ErrorCode::InvalidKeeperRebateAccount
-    );
-
-    margin.collateral_balance = margin
-        .collateral_balance
-        .checked_add(reserved_collateral)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-    let (oracle_price, oracle_conf, oracle_publish_time) = read_oracle_price_update(
-        market,
-        &ctx.accounts.oracle_price_update,
+    let mut insurance_oi_headroom = accounts
+        .lp_pool
+        .insurance_oi_headroom(accounts.lp_insurance_vault.amount);
+    let mut order = accounts.order.load_mut()?;
+    let outcome = fill_order(
+        &accounts.market,
+        &mut funding_state,
+        &mut order,
+        &mut accounts.user_margin,
+        &mut *accounts.user_market_position.load_mut()?,
         &clock,
-        oracle_price,
-        oracle_conf,
-        oracle_publish_time,
-    )?;
-
-    validate_oracle(
-        market,
-        now,
         fill_price,
         oracle_price,
         oracle_conf,
         oracle_publish_time,
+        &mut insurance_oi_headroom,
     )?;
-
-    let notional = order_margin;
-    require!(notional > 0, ErrorCode::InvalidAmount);
-    require!(
-        notional <= market.risk_params.max_trade_notional,
-        ErrorCode::MaxTradeNotionalExceeded
-    );
-
-    let raw_qty = ((notional as u128)
-        .checked_mul(PRICE_SCALE)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?)
-    .checked_div(fill_price as u128)
-    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    let order_qty: u64 = raw_qty
-        .try_into()
-        .map_err(|_| error!(ErrorCode::MathOverflow))?;
-    require!(order_qty > 0, ErrorCode::InvalidAmount);
-
-    validate_order_price(order_side, order_type, order_price, fill_price)?;
-
-    update_funding_index(
-        funding_state,
-        now,
-        &market.funding_params,
-        market.risk_params.oi_cap,
-    )?;
-    settle_user_funding(position, funding_state, margin)?;
-
-    if order.reduce_only {
-        let close_leg = match order_side {
-            crate::state::Side::Buy => PositionLeg::Short,
-            crate::state::Side::Sell => PositionLeg::Long,
-        };
-        let reduced_notional = reduce_position(position, close_leg, order_qty)?;
-
-        margin.total_notional = margin
-            .total_notional
-            .checked_sub(reduced_notional)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-        funding_state.open_interest = funding_state
-            .open_interest
-            .checked_sub(reduced_notional)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-        funding_state.skew = match close_leg {
-            PositionLeg::Long => funding_state
-                .skew
-                .checked_sub(reduced_notional as i128)
-                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
-            PositionLeg::Short => funding_state
-                .skew
-                .checked_add(reduced_notional as i128)
-                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
-        };
-
-        let fee = mul_bps_u64(notional, market.fee_params.taker_fee_bps as u64)?;
-        require!(
-            margin.collateral_balance >= fee,
-            ErrorCode::InsufficientCollateral
-        );
-        margin.collateral_balance = margin
-            .collateral_balance
-            .checked_sub(fee)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-        order.status = OrderStatus::Executed;
-        transfer_fee_split(&ctx, fee)?;
-        cpi_apply_trade_fill(&ctx, notional, fee)?;
-
+    let order_id = order.id;
+    drop(order);
+    drop(funding_state);
+    accounts.engine_stats.order_closed();
+    let Some(outcome) = outcome else {
         return Ok(());
-    }
-
-    let projected_oi = funding_state
-        .open_interest
-        .checked_add(notional)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    require!(
-        projected_oi <= market.risk_params.oi_cap,
-        ErrorCode::OiCapExceeded
-    );
-
-    let projected_skew = match order_side {
-        crate::state::Side::Buy => funding_state
-            .skew
-            .checked_add(notional as i128)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
-        crate::state::Side::Sell => funding_state
-            .skew
-            .checked_sub(notional as i128)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
     };
-
-    require!(
-        projected_skew.unsigned_abs() <= market.risk_params.skew_cap as u128,
-        ErrorCode::SkewCapExceeded
-    );
-
-    validate_impact_price(
-        order_side,
-        fill_price,
-        oracle_price,
-        projected_skew,
-        projected_oi,
-        &market.pricing_params,
-    )?;
-
-    let fee = mul_bps_u64(notional, market.fee_params.taker_fee_bps as u64)?;
-    require!(
-        margin.collateral_balance >= fee,
-        ErrorCode::InsufficientCollateral
-    );
-
-    margin.collateral_balance = margin
+    let maker_rebate = clamp_maker_rebate(&ctx.accounts.lp_pool, 0, outcome.maker_rebate);
+    ctx.accounts.user_margin.collateral_balance = ctx
+        .accounts
+        .user_margin
         .collateral_balance
-        .checked_sub(fee)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-    let new_total_notional = margin
-        .total_notional
-        .checked_add(notional)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
-    let imr_required = mul_bps_u64(new_total_notional, market.risk_params.imr_bps as u64)?;
-    require!(
-        margin.collateral_balance >= imr_required,
-        ErrorCode::MarginRequirementViolation
-    );
-
-    let leverage_num = new_total_notional
-        .checked_mul(1)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    let leverage_den = margin.collateral_balance.max(1);
-    require!(
-        leverage_num <= leverage_den.saturating_mul(market.risk_params.max_leverage as u64),
-        ErrorCode::LeverageExceeded
-    );
-
-    apply_fill_to_position(position, order_side, order_qty, notional)?;
-
-    funding_state.open_interest = projected_oi;
-    funding_state.skew = projected_skew;
-    margin.total_notional = new_total_notional;
-    order.status = OrderStatus::Executed;
-
-    transfer_fee_split(&ctx, fee)?;
-    cpi_apply_trade_fill(&ctx, notional, fee)?;
-
-    Ok(())
-}
-
-fn transfer_fee_split(ctx: &Context<ExecuteOrder>, fee: u64) -> Result<()> {
-    if fee == 0 {
-        return Ok(());
-    }
-
-    let lp_fee = mul_bps_u64(fee, ctx.accounts.lp_pool.lp_fee_bps as u64)?;
-    let insurance_fee = mul_bps_u64(fee, ctx.accounts.lp_pool.insurance_fee_bps as u64)?;
-    let protocol_fee = fee
-        .checked_sub(lp_fee)
-        .and_then(|x| x.checked_sub(insurance_fee))
+        .checked_add(maker_rebate)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-
+    ctx.accounts.engine_stats.credit_collateral(maker_rebate);
+    ctx.accounts
+        .market_stats
+        .record_fill(outcome.notional, fill_price, now);
+    ctx.accounts
+        .engine_stats
+        .record_fill(outcome.notional, outcome.fee);
+    ctx.accounts
+        .recent_fills
+        .load_mut()?
+        .push(fill_price, outcome.qty, outcome.side, now);
+
+    ctx.accounts
+        .engine_stats
+        .debit_collateral(outcome.keeper_tip);
     transfer_from_collateral(
         &ctx.accounts.token_program,
+        &ctx.accounts.usdc_mint,
         &ctx.accounts.collateral_vault,
-        &ctx.accounts.lp_liquidity_vault,
+        &ctx.accounts.lp_protocol_fee_vault,
         &ctx.accounts.engine_authority,
         ctx.bumps.engine_authority,
-        lp_fee,
+        outcome.keeper_tip,
     )?;
-
-    transfer_from_collateral(
-        &ctx.accounts.token_program,
-        &ctx.accounts.collateral_vault,
-        &ctx.accounts.lp_insurance_vault,
-        &ctx.accounts.engine_authority,
+    sync_lp_fee_split(
+        &ctx.accounts.lp_vault_program.to_account_info(),
+        &ctx.accounts.engine_authority.to_account_info(),
         ctx.bumps.engine_authority,
-        insurance_fee,
+        &mut ctx.accounts.lp_pool,
+        &ctx.accounts.global_config.fee_split,
     )?;
-
-    transfer_from_collateral(
+    transfer_fee_split(
         &ctx.accounts.token_program,
+        &ctx.accounts.usdc_mint,
         &ctx.accounts.collateral_vault,
+        &ctx.accounts.lp_pool,
+        &ctx.accounts.lp_liquidity_vault,
+        &ctx.accounts.lp_insurance_vault,
         &ctx.accounts.lp_protocol_fee_vault,
         &ctx.accounts.engine_authority,
         ctx.bumps.engine_authority,
-        protocol_fee,
+        outcome.fee,
     )?;
-
-    Ok(())
-}
-
-fn cpi_apply_trade_fill(ctx: &Context<ExecuteOrder>, notional: u64, fee: u64) -> Result<()> {
-    let seeds: &[&[u8]] = &[b"engine-authority", &[ctx.bumps.engine_authority]];
-    let signer_seeds = &[seeds];
-
-    let cpi_accounts = lp_vault::cpi::accounts::ApplyTradeFill {
-        engine_authority: ctx.accounts.engine_authority.to_account_info(),
-        pool: ctx.accounts.lp_pool.to_account_info(),
-        keeper: ctx.accounts.executor.to_account_info(),
-        keeper_rebate: ctx.accounts.keeper_rebate.to_account_info(),
-        liquidity_vault: ctx.accounts.lp_liquidity_vault.to_account_info(),
-        insurance_vault: ctx.accounts.lp_insurance_vault.to_account_info(),
-        protocol_fee_vault: ctx.accounts.lp_protocol_fee_vault.to_account_info(),
-    };
-
-    lp_vault::cpi::apply_trade_fill(
-        CpiContext::new_with_signer(
-            ctx.accounts.lp_vault_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        ),
+    cpi_apply_trade_fill(
+        &TradeFillCpi {
+            lp_vault_program: &ctx.accounts.lp_vault_program.to_account_info(),
+            engine_authority: &ctx.accounts.engine_authority.to_account_info(),
+            engine_authority_bump: ctx.bumps.engine_authority,
+            lp_pool: &ctx.accounts.lp_pool.to_account_info(),
+            keeper: &ctx.accounts.executor.to_account_info(),
+            keeper_rebate: ctx
+                .accounts
+                .keeper_rebate
+                .as_ref()
+                .map(|r| r.to_account_info()),
+            lp_market_insurance: &ctx.accounts.lp_market_insurance.to_account_info(),
+            lp_market_stats: &ctx.accounts.lp_market_stats.to_account_info(),
+            lp_liquidity_vault: &ctx.accounts.lp_liquidity_vault.to_account_info(),
+            lp_insurance_vault: &ctx.accounts.lp_insurance_vault.to_account_info(),
+            lp_protocol_fee_vault: &ctx.accounts.lp_protocol_fee_vault.to_account_info(),
+            lp_protocol_fee_auth: &ctx.accounts.lp_protocol_fee_auth.to_account_info(),
+            keeper_token_account: ctx
+                .accounts
+                .keeper_token_account
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            usdc_mint: &ctx.accounts.usdc_mint.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+        },
         ctx.accounts.market.market_id,
         ctx.accounts.user_margin.owner,
-        ctx.accounts.order.id,
-        notional,
-        fee,
-        0,
+        order_id,
+        outcome.notional,
+        outcome.fee,
+        outcome.realized_pnl,
+        outcome.keeper_tip,
+    )?;
+    cpi_pay_maker_rebate(
+        &MakerRebateCpi {
+            lp_vault_program: &ctx.accounts.lp_vault_program.to_account_info(),
+            engine_authority: &ctx.accounts.engine_authority.to_account_info(),
+            engine_authority_bump: ctx.bumps.engine_authority,
+            lp_pool: &ctx.accounts.lp_pool.to_account_info(),
+            lp_liquidity_auth: &ctx.accounts.lp_liquidity_auth.to_account_info(),
+            lp_liquidity_vault: &ctx.accounts.lp_liquidity_vault.to_account_info(),
+            collateral_vault: &ctx.accounts.collateral_vault.to_account_info(),
+            usdc_mint: &ctx.accounts.usdc_mint.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+        },
+        maker_rebate,
     )
 }
 
@@ -346,55 +244,95 @@ pub struct ExecuteOrder<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
     pub market_registry_program: Program<'info, MarketRegistry>,
-    #[account(address = engine_config.registry_global_config)]
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
     pub global_config: Box<Account<'info, market_registry::GlobalConfig>>,
-    #[account(address = engine_config.keeper_set)]
-    pub keeper_set: Box<Account<'info, market_registry::KeeperSet>>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
     #[account(mut)]
-    pub order: Box<Account<'info, Order>>,
+    pub order: AccountLoader<'info, Order>,
     #[account(
-        seeds = [b"market".as_ref(), &order.market_id.to_le_bytes()],
+        seeds = [b"market".as_ref(), &order.load()?.market_id.to_le_bytes()],
         seeds::program = market_registry_program.key(),
         bump = market.bump,
     )]
     pub market: Box<Account<'info, market_registry::Market>>,
-    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness or fallback source).
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
     pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &order.load()?.market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
     #[account(
         mut,
-        seeds = [b"funding".as_ref(), &order.market_id.to_le_bytes()],
-        bump = market_funding_state.bump,
+        seeds = [b"market-stats".as_ref(), &order.load()?.market_id.to_le_bytes()],
+        bump = market_stats.bump,
     )]
-    pub market_funding_state: Box<Account<'info, MarketFundingState>>,
+    pub market_stats: Box<Account<'info, MarketStats>>,
     #[account(
         mut,
-        seeds = [b"user-margin", order.user.as_ref()],
+        seeds = [b"recent-fills".as_ref(), &order.load()?.market_id.to_le_bytes()],
+        bump = recent_fills.load()?.bump,
+    )]
+    pub recent_fills: AccountLoader<'info, RecentFills>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", order.load()?.user.as_ref()],
         bump = user_margin.bump,
     )]
     pub user_margin: Box<Account<'info, UserMargin>>,
     #[account(
         mut,
-        seeds = [b"user-market-pos", user_margin.key().as_ref(), &order.market_id.to_le_bytes()],
-        bump = user_market_position.bump,
+        seeds = [b"user-market-pos", user_margin.key().as_ref(), &order.load()?.market_id.to_le_bytes()],
+        bump = user_market_position.load()?.bump,
     )]
-    pub user_market_position: Box<Account<'info, UserMarketPosition>>,
+    pub user_market_position: AccountLoader<'info, UserMarketPosition>,
     /// CHECK: engine authority PDA.
     #[account(seeds = [b"engine-authority"], bump)]
     pub engine_authority: UncheckedAccount<'info>,
-    #[account(mut, address = engine_config.collateral_vault)]
-    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub lp_vault_program: Program<'info, LpVault>,
-    #[account(mut, address = engine_config.lp_pool)]
+    #[account(mut, address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
     pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
-    #[account(mut, address = engine_config.lp_liquidity_vault)]
-    pub lp_liquidity_vault: Box<Account<'info, TokenAccount>>,
-    #[account(mut, address = engine_config.lp_insurance_vault)]
-    pub lp_insurance_vault: Box<Account<'info, TokenAccount>>,
-    #[account(mut, address = engine_config.lp_protocol_fee_vault)]
-    pub lp_protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub lp_liquidity_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: liquidity auth PDA for maker rebates, seeds checked by lp_vault.
+    pub lp_liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Omitted when the rebate is paid inline to `keeper_token_account`.
+    #[account(mut)]
+    pub keeper_rebate: Option<Box<Account<'info, lp_vault::KeeperRebate>>>,
+    /// CHECK: keeper USDC account for inline rebates, checked by lp_vault.
+    #[account(mut)]
+    pub keeper_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: protocol fee auth PDA for inline rebates, seeds checked by lp_vault.
+    pub lp_protocol_fee_auth: UncheckedAccount<'info>,
+    /// CHECK: per-market insurance earmark PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_insurance: UncheckedAccount<'info>,
+    /// CHECK: per-market vault stats PDA, seeds checked by lp_vault.
     #[account(mut)]
-    pub keeper_rebate: Box<Account<'info, lp_vault::KeeperRebate>>,
-    pub token_program: Program<'info, Token>,
+    pub lp_market_stats: UncheckedAccount<'info>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: instructions sysvar, inspected by `assert_execution_not_bundled`.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 }
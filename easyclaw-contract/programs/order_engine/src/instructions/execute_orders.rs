@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use lp_vault::program::LpVault;
+use market_registry::{program::MarketRegistry, KeeperScope};
+
+use crate::{
+    error::ErrorCode,
+    helpers::{
+        assert_execution_not_bundled, assert_executor_authorized, clamp_maker_rebate,
+        cpi_apply_trade_fill, cpi_pay_maker_rebate, fill_order, observe_oracle_price,
+        read_index_price, read_oracle_price_update, sync_lp_fee_split, transfer_fee_split,
+        transfer_from_collateral, update_funding_index, validate_fill_batch, MakerRebateCpi,
+        OracleUse, TradeFillCpi,
+    },
+    state::{
+        EngineConfig, EngineStats, MarketFundingState, MarketStats, Order, RecentFills, UserMargin,
+        UserMarketPosition,
+    },
+};
+
+// Remaining accounts are consumed in groups of three per fill:
+// [order (mut), user_margin (mut), user_market_position (mut)].
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteOrders<'info>>,
+    market_id: u64,
+    fill_prices: Vec<u64>,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
+) -> Result<()> {
+    validate_fill_batch(fill_prices.len(), ctx.remaining_accounts.len())?;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let global_config = &ctx.accounts.global_config;
+    let market = &ctx.accounts.market;
+    let keeper_rebate = &ctx.accounts.keeper_rebate;
+
+    assert_executor_authorized(
+        &ctx.accounts.executor,
+        global_config,
+        ctx.accounts.keeper_auth.as_deref(),
+        KeeperScope::Execute,
+    )?;
+    assert_execution_not_bundled(&ctx.accounts.instructions_sysvar.to_account_info())?;
+    require!(!global_config.global_pause, ErrorCode::GlobalPaused);
+    require!(market.market_id == market_id, ErrorCode::MarketMismatch);
+    require!(
+        market.status.allows_execution(true),
+        ErrorCode::MarketNotActive
+    );
+    if let Some(keeper_rebate) = keeper_rebate {
+        require_keys_eq!(
+            keeper_rebate.pool,
+            ctx.accounts.lp_pool.key(),
+            ErrorCode::InvalidKeeperRebateAccount
+        );
+        require_keys_eq!(
+            keeper_rebate.keeper,
+            ctx.accounts.executor.key(),
+            ErrorCode::InvalidKeeperRebateAccount
+        );
+    }
+
+    let secondary_oracles: Vec<&UncheckedAccount> = [
+        ctx.accounts.secondary_oracle.as_ref(),
+        ctx.accounts.tertiary_oracle.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let (oracle_price, oracle_conf, oracle_publish_time) = read_oracle_price_update(
+        market,
+        &ctx.accounts.oracle_price_update,
+        &secondary_oracles,
+        &clock,
+        OracleUse::Execution,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, oracle_price)?;
+
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    require!(!funding_state.is_halted(), ErrorCode::MarketHaltedLocal);
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &accounts.market.funding_params,
+        accounts.market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+    if observe_oracle_price(
+        &mut funding_state,
+        &accounts.market.pricing_params,
+        now,
+        oracle_price,
+    )? {
+        return Ok(());
+    }
+
+    let mut recent_fills = accounts.recent_fills.load_mut()?;
+    let mut fills = Vec::with_capacity(fill_prices.len());
+    let mut total_maker_rebate = 0u64;
+    let mut total_keeper_tip = 0u64;
+    // Shared across the batch so later fills see earlier open interest growth.
+    let mut insurance_oi_headroom = accounts
+        .lp_pool
+        .insurance_oi_headroom(accounts.lp_insurance_vault.amount);
+    for (group, fill_price) in ctx.remaining_accounts.chunks(3).zip(fill_prices) {
+        // Each group is loaded and written back before the next one so that
+        // repeated margin accounts (one user, several orders) see fresh state.
+        let order_loader = AccountLoader::<Order>::try_from(&group[0])?;
+        let mut margin = Account::<UserMargin>::try_from(&group[1])?;
+        let position_loader = AccountLoader::<UserMarketPosition>::try_from(&group[2])?;
+        require!(
+            group.iter().all(|info| info.is_writable),
+            ErrorCode::InvalidBatch
+        );
+        // Remaining accounts carry no seed constraints, so the margin and
+        // position PDAs are re-derived here.
+        let margin_pda = Pubkey::create_program_address(
+            &[b"user-margin", margin.owner.as_ref(), &[margin.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::MarginOrderMismatch))?;
+        require_keys_eq!(margin_pda, margin.key(), ErrorCode::MarginOrderMismatch);
+        let position_bump = position_loader.load()?.bump;
+        let position_pda = Pubkey::create_program_address(
+            &[
+                b"user-market-pos",
+                margin.key().as_ref(),
+                &market_id.to_le_bytes(),
+                &[position_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::PositionOwnerMismatch))?;
+        require_keys_eq!(
+            position_pda,
+            position_loader.key(),
+            ErrorCode::PositionOwnerMismatch
+        );
+
+        let mut order = order_loader.load_mut()?;
+        require!(
+            accounts
+                .market
+                .status
+                .allows_execution(order.is_reduce_only()),
+            ErrorCode::MarketReduceOnly
+        );
+        let outcome = fill_order(
+            &accounts.market,
+            &mut funding_state,
+            &mut order,
+            &mut margin,
+            &mut *position_loader.load_mut()?,
+            &clock,
+            fill_price,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+            &mut insurance_oi_headroom,
+        )?;
+        if let Some(outcome) = &outcome {
+            let maker_rebate =
+                clamp_maker_rebate(&accounts.lp_pool, total_maker_rebate, outcome.maker_rebate);
+            margin.collateral_balance = margin
+                .collateral_balance
+                .checked_add(maker_rebate)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            accounts.engine_stats.credit_collateral(maker_rebate);
+            total_maker_rebate += maker_rebate;
+            total_keeper_tip = total_keeper_tip
+                .checked_add(outcome.keeper_tip)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        }
+
+        margin.exit(&crate::ID)?;
+
+        accounts.engine_stats.order_closed();
+        if let Some(outcome) = outcome {
+            accounts
+                .market_stats
+                .record_fill(outcome.notional, fill_price, now);
+            accounts
+                .engine_stats
+                .record_fill(outcome.notional, outcome.fee);
+            recent_fills.push(fill_price, outcome.qty, outcome.side, now);
+            fills.push((margin.owner, order.id, outcome));
+        }
+    }
+
+    // Tips move in one transfer. Fees are split per fill so the amounts moved
+    // match what `apply_trade_fill` books for each one.
+    accounts.engine_stats.debit_collateral(total_keeper_tip);
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_protocol_fee_vault,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        total_keeper_tip,
+    )?;
+    sync_lp_fee_split(
+        &accounts.lp_vault_program.to_account_info(),
+        &accounts.engine_authority.to_account_info(),
+        ctx.bumps.engine_authority,
+        &mut accounts.lp_pool,
+        &accounts.global_config.fee_split,
+    )?;
+    let trade_fill_cpi = TradeFillCpi {
+        lp_vault_program: &accounts.lp_vault_program.to_account_info(),
+        engine_authority: &accounts.engine_authority.to_account_info(),
+        engine_authority_bump: ctx.bumps.engine_authority,
+        lp_pool: &accounts.lp_pool.to_account_info(),
+        keeper: &accounts.executor.to_account_info(),
+        keeper_rebate: accounts.keeper_rebate.as_ref().map(|r| r.to_account_info()),
+        lp_market_insurance: &accounts.lp_market_insurance.to_account_info(),
+        lp_market_stats: &accounts.lp_market_stats.to_account_info(),
+        lp_liquidity_vault: &accounts.lp_liquidity_vault.to_account_info(),
+        lp_insurance_vault: &accounts.lp_insurance_vault.to_account_info(),
+        lp_protocol_fee_vault: &accounts.lp_protocol_fee_vault.to_account_info(),
+        lp_protocol_fee_auth: &accounts.lp_protocol_fee_auth.to_account_info(),
+        keeper_token_account: accounts
+            .keeper_token_account
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        usdc_mint: &accounts.usdc_mint.to_account_info(),
+        token_program: &accounts.token_program.to_account_info(),
+    };
+    for (user, order_id, outcome) in fills {
+        transfer_fee_split(
+            &accounts.token_program,
+            &accounts.usdc_mint,
+            &accounts.collateral_vault,
+            &accounts.lp_pool,
+            &accounts.lp_liquidity_vault,
+            &accounts.lp_insurance_vault,
+            &accounts.lp_protocol_fee_vault,
+            &accounts.engine_authority,
+            ctx.bumps.engine_authority,
+            outcome.fee,
+        )?;
+        cpi_apply_trade_fill(
+            &trade_fill_cpi,
+            market_id,
+            user,
+            order_id,
+            outcome.notional,
+            outcome.fee,
+            outcome.realized_pnl,
+            outcome.keeper_tip,
+        )?;
+    }
+    cpi_pay_maker_rebate(
+        &MakerRebateCpi {
+            lp_vault_program: &accounts.lp_vault_program.to_account_info(),
+            engine_authority: &accounts.engine_authority.to_account_info(),
+            engine_authority_bump: ctx.bumps.engine_authority,
+            lp_pool: &accounts.lp_pool.to_account_info(),
+            lp_liquidity_auth: &accounts.lp_liquidity_auth.to_account_info(),
+            lp_liquidity_vault: &accounts.lp_liquidity_vault.to_account_info(),
+            collateral_vault: &accounts.collateral_vault.to_account_info(),
+            usdc_mint: &accounts.usdc_mint.to_account_info(),
+            token_program: &accounts.token_program.to_account_info(),
+        },
+        total_maker_rebate,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ExecuteOrders<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
+    pub global_config: Box<Account<'info, market_registry::GlobalConfig>>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, market_registry::Market>>,
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
+    pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+    #[account(
+        mut,
+        seeds = [b"market-stats".as_ref(), &market_id.to_le_bytes()],
+        bump = market_stats.bump,
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+    #[account(
+        mut,
+        seeds = [b"recent-fills".as_ref(), &market_id.to_le_bytes()],
+        bump = recent_fills.load()?.bump,
+    )]
+    pub recent_fills: AccountLoader<'info, RecentFills>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lp_vault_program: Program<'info, LpVault>,
+    #[account(mut, address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
+    pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
+    #[account(mut, address = engine_config.lp_liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub lp_liquidity_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: liquidity auth PDA for maker rebates, seeds checked by lp_vault.
+    pub lp_liquidity_auth: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Omitted when the rebate is paid inline to `keeper_token_account`.
+    #[account(mut)]
+    pub keeper_rebate: Option<Box<Account<'info, lp_vault::KeeperRebate>>>,
+    /// CHECK: keeper USDC account for inline rebates, checked by lp_vault.
+    #[account(mut)]
+    pub keeper_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: protocol fee auth PDA for inline rebates, seeds checked by lp_vault.
+    pub lp_protocol_fee_auth: UncheckedAccount<'info>,
+    /// CHECK: per-market insurance earmark PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_insurance: UncheckedAccount<'info>,
+    /// CHECK: per-market vault stats PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_stats: UncheckedAccount<'info>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: instructions sysvar, inspected by `assert_execution_not_bundled`.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
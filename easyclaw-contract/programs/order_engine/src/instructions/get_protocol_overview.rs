@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    error::ErrorCode,
+    state::{EngineConfig, MarketFundingState, ProtocolOverview},
+};
+
+// Remaining accounts: market funding states, ordered by strictly increasing
+// market id so a market cannot be counted twice.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetProtocolOverview<'info>>,
+) -> Result<ProtocolOverview> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut total_open_interest = 0u64;
+    let mut last_market_id: Option<u64> = None;
+    for info in ctx.remaining_accounts {
+        let loader = AccountLoader::<MarketFundingState>::try_from(info)?;
+        let funding_state = loader.load()?;
+        if let Some(last_market_id) = last_market_id {
+            require!(
+                funding_state.market_id > last_market_id,
+                ErrorCode::InvalidBatch
+            );
+        }
+        last_market_id = Some(funding_state.market_id);
+        total_open_interest = total_open_interest
+            .checked_add(funding_state.open_interest)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    }
+
+    let lp_pool = &ctx.accounts.lp_pool;
+    Ok(ProtocolOverview {
+        pool_tvl: lp_pool.accounted_liquidity,
+        insurance_balance: ctx.accounts.lp_insurance_vault.amount,
+        protocol_fee_balance: ctx.accounts.lp_protocol_fee_vault.amount,
+        total_collateral: ctx.accounts.collateral_vault.amount,
+        total_open_interest,
+        market_count: ctx.remaining_accounts.len() as u32,
+        total_trading_fees: lp_pool.total_trading_fees,
+        fees_24h: lp_pool.trailing_window_fees(now),
+        timestamp: now,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetProtocolOverview<'info> {
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
+    pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
+    #[account(address = engine_config.lp_liquidity_vault @ ErrorCode::LiquidityVaultMismatch)]
+    pub lp_liquidity_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.lp_protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+}
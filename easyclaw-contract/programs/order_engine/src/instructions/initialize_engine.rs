@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use lp_vault::program::LpVault;
 use market_registry::program::MarketRegistry;
 
-use crate::{error::ErrorCode, state::EngineConfig};
+use crate::{error::ErrorCode, helpers::require_config_key, state::EngineConfig};
 
 pub fn handler(
     ctx: Context<InitializeEngine>,
@@ -15,41 +15,49 @@ pub fn handler(
     require!(liquidation_penalty_bps <= 5_000, ErrorCode::InvalidBps);
     require!(max_imr_bps <= 10_000, ErrorCode::InvalidBps);
 
-    require_keys_eq!(
-        ctx.accounts.lp_pool.usdc_mint,
+    let lp_pool = &ctx.accounts.lp_pool;
+    require_config_key(
+        "lp_pool.usdc_mint",
+        lp_pool.usdc_mint,
         ctx.accounts.usdc_mint.key(),
-        ErrorCode::LpPoolConfigMismatch
-    );
-    require_keys_eq!(
+        ErrorCode::LpMintMismatch,
+    )?;
+    require_config_key(
+        "global_config.keeper_set",
         ctx.accounts.global_config.keeper_set,
         ctx.accounts.keeper_set.key(),
-        ErrorCode::RegistryConfigMismatch
-    );
-    require_keys_eq!(
-        ctx.accounts.lp_pool.order_engine_program,
+        ErrorCode::KeeperSetMismatch,
+    )?;
+    require_config_key(
+        "lp_pool.order_engine_program",
+        lp_pool.order_engine_program,
         crate::ID,
-        ErrorCode::LpPoolConfigMismatch
-    );
-    require_keys_eq!(
-        ctx.accounts.lp_pool.engine_authority,
+        ErrorCode::EngineProgramMismatch,
+    )?;
+    require_config_key(
+        "lp_pool.engine_authority",
+        lp_pool.engine_authority,
         ctx.accounts.engine_authority.key(),
-        ErrorCode::LpPoolConfigMismatch
-    );
-    require_keys_eq!(
-        ctx.accounts.lp_pool.liquidity_vault,
+        ErrorCode::EngineAuthorityMismatch,
+    )?;
+    require_config_key(
+        "lp_pool.liquidity_vault",
+        lp_pool.liquidity_vault,
         ctx.accounts.lp_liquidity_vault.key(),
-        ErrorCode::LpPoolConfigMismatch
-    );
-    require_keys_eq!(
-        ctx.accounts.lp_pool.insurance_vault,
+        ErrorCode::LiquidityVaultMismatch,
+    )?;
+    require_config_key(
+        "lp_pool.insurance_vault",
+        lp_pool.insurance_vault,
         ctx.accounts.lp_insurance_vault.key(),
-        ErrorCode::LpPoolConfigMismatch
-    );
-    require_keys_eq!(
-        ctx.accounts.lp_pool.protocol_fee_vault,
+        ErrorCode::InsuranceVaultMismatch,
+    )?;
+    require_config_key(
+        "lp_pool.protocol_fee_vault",
+        lp_pool.protocol_fee_vault,
         ctx.accounts.lp_protocol_fee_vault.key(),
-        ErrorCode::LpPoolConfigMismatch
-    );
+        ErrorCode::ProtocolFeeVaultMismatch,
+    )?;
 
     let config = &mut ctx.accounts.engine_config;
     config.admin = ctx.accounts.admin.key();
@@ -76,19 +84,16 @@ pub fn handler(
 pub struct InitializeEngine<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
     pub market_registry_program: Program<'info, MarketRegistry>,
     #[account(mut)]
     pub global_config: Box<Account<'info, market_registry::GlobalConfig>>,
     pub keeper_set: Box<Account<'info, market_registry::KeeperSet>>,
     pub lp_vault_program: Program<'info, LpVault>,
     pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
-    #[account(address = lp_pool.liquidity_vault)]
-    pub lp_liquidity_vault: Box<Account<'info, TokenAccount>>,
-    #[account(address = lp_pool.insurance_vault)]
-    pub lp_insurance_vault: Box<Account<'info, TokenAccount>>,
-    #[account(address = lp_pool.protocol_fee_vault)]
-    pub lp_protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    pub lp_liquidity_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     /// CHECK: engine authority PDA used for vault signing.
     #[account(seeds = [b"engine-authority"], bump)]
     pub engine_authority: UncheckedAccount<'info>,
@@ -107,8 +112,9 @@ pub struct InitializeEngine<'info> {
         bump,
         token::mint = usdc_mint,
         token::authority = engine_authority,
+        token::token_program = token_program,
     )]
-    pub collateral_vault: Box<Account<'info, TokenAccount>>,
-    pub token_program: Program<'info, Token>,
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
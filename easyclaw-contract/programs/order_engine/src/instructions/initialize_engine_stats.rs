@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{EngineConfig, EngineStats},
+};
+
+pub fn handler(ctx: Context<InitializeEngineStats>) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+
+    let stats = &mut ctx.accounts.engine_stats;
+    stats.bump = ctx.bumps.engine_stats;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeEngineStats<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"engine-stats"],
+        bump,
+        space = 8 + EngineStats::INIT_SPACE,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
+    pub system_program: Program<'info, System>,
+}
@@ -13,13 +13,19 @@ pub fn handler(ctx: Context<InitializeMarketFundingState>, market_id: u64) -> Re
         crate::error::ErrorCode::MarketMismatch
     );
 
-    let state = &mut ctx.accounts.market_funding_state;
+    let mut state = ctx.accounts.market_funding_state.load_init()?;
     state.market_id = market_id;
     state.funding_index = 0;
     state.last_update_ts = Clock::get()?.unix_timestamp;
     state.open_interest = 0;
     state.skew = 0;
-    state.halted = false;
+    state.socialized_loss_index_long = 0;
+    state.socialized_loss_index_short = 0;
+    state.socialized_loss_collected = 0;
+    state.long_open_qty = 0;
+    state.short_open_qty = 0;
+    state.halted = 0;
+    state.breaker_tripped = 0;
     state.bump = ctx.bumps.market_funding_state;
 
     Ok(())
@@ -47,8 +53,8 @@ pub struct InitializeMarketFundingState<'info> {
         payer = admin,
         seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
         bump,
-        space = 8 + MarketFundingState::INIT_SPACE,
+        space = 8 + std::mem::size_of::<MarketFundingState>(),
     )]
-    pub market_funding_state: Account<'info, MarketFundingState>,
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    helpers::require_admin,
+    state::{EngineConfig, MarketMirror},
+};
+
+pub fn handler(ctx: Context<InitializeMarketMirror>, market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+    require!(
+        ctx.accounts.market.market_id == market_id,
+        crate::error::ErrorCode::MarketMismatch
+    );
+
+    let mirror = &mut ctx.accounts.market_mirror;
+    mirror.sync_from(&ctx.accounts.market, Clock::get()?.unix_timestamp);
+    mirror.bump = ctx.bumps.market_mirror;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitializeMarketMirror<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Account<'info, market_registry::Market>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"market-mirror".as_ref(), &market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketMirror::INIT_SPACE,
+    )]
+    pub market_mirror: Account<'info, MarketMirror>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    helpers::require_admin,
+    state::{EngineConfig, MarketStats},
+};
+
+pub fn handler(ctx: Context<InitializeMarketStats>, market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+    require!(
+        ctx.accounts.market.market_id == market_id,
+        crate::error::ErrorCode::MarketMismatch
+    );
+
+    let stats = &mut ctx.accounts.market_stats;
+    stats.market_id = market_id;
+    stats.bump = ctx.bumps.market_stats;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitializeMarketStats<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Account<'info, market_registry::Market>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"market-stats".as_ref(), &market_id.to_le_bytes()],
+        bump,
+        space = 8 + MarketStats::INIT_SPACE,
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+    pub system_program: Program<'info, System>,
+}
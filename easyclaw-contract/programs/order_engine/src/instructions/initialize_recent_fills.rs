@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    helpers::require_admin,
+    state::{EngineConfig, RecentFills},
+};
+
+pub fn handler(ctx: Context<InitializeRecentFills>, market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+    require!(
+        ctx.accounts.market.market_id == market_id,
+        crate::error::ErrorCode::MarketMismatch
+    );
+
+    let mut recent_fills = ctx.accounts.recent_fills.load_init()?;
+    recent_fills.market_id = market_id;
+    recent_fills.bump = ctx.bumps.recent_fills;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct InitializeRecentFills<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Account<'info, market_registry::Market>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"recent-fills".as_ref(), &market_id.to_le_bytes()],
+        bump,
+        space = 8 + std::mem::size_of::<RecentFills>(),
+    )]
+    pub recent_fills: AccountLoader<'info, RecentFills>,
+    pub system_program: Program<'info, System>,
+}
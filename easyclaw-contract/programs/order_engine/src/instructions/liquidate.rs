@@ -1,179 +1,178 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use lp_vault::program::LpVault;
 use market_registry::program::MarketRegistry;
 
 use crate::{
     error::ErrorCode,
+    events::LossSocialized,
     helpers::{
-        assert_keeper_only, mul_bps_u64, reduce_position, settle_user_funding,
-        transfer_from_collateral, update_funding_index,
+        assert_liquidator_authorized, cpi_apply_liquidation, liquidate_position, read_index_price,
+        read_oracle_price_update, socialize_shortfall, transfer_from_collateral,
+        update_funding_index, validate_liquidation_band, validate_liquidation_confidence,
+        validate_liquidation_twap, LiquidationCpi, OracleUse,
+    },
+    state::{
+        EngineConfig, EngineStats, MarketFundingState, PositionLeg, UserMargin, UserMarketPosition,
     },
-    state::{EngineConfig, MarketFundingState, PositionLeg, UserMargin, UserMarketPosition},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<Liquidate>,
     market_id: u64,
     leg: PositionLeg,
     close_qty: u64,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
 ) -> Result<()> {
     require!(close_qty > 0, ErrorCode::InvalidAmount);
 
-    let now = Clock::get()?.unix_timestamp;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
     let market = &ctx.accounts.market;
-    let funding_state = &mut ctx.accounts.market_funding_state;
-    let margin = &mut ctx.accounts.user_margin;
-    let position = &mut ctx.accounts.user_market_position;
-    let keeper_rebate = &ctx.accounts.keeper_rebate;
 
     require!(market.market_id == market_id, ErrorCode::MarketMismatch);
-    require!(position.market_id == market_id, ErrorCode::MarketMismatch);
-    require_keys_eq!(
-        position.user_margin,
-        margin.key(),
-        ErrorCode::PositionOwnerMismatch
-    );
     require!(
-        market.status == market_registry::MarketStatus::Active,
+        market.status.allows_liquidation(),
         ErrorCode::MarketNotActive
     );
-    require!(!funding_state.halted, ErrorCode::MarketHaltedLocal);
-    require_keys_eq!(
-        keeper_rebate.pool,
-        ctx.accounts.lp_pool.key(),
-        ErrorCode::InvalidKeeperRebateAccount
-    );
-    require_keys_eq!(
-        keeper_rebate.keeper,
-        ctx.accounts.executor.key(),
-        ErrorCode::InvalidKeeperRebateAccount
-    );
-
-    assert_keeper_only(&ctx.accounts.executor, &ctx.accounts.keeper_set)?;
 
-    update_funding_index(
-        funding_state,
+    assert_liquidator_authorized(
+        &ctx.accounts.executor,
+        &ctx.accounts.keeper_liveness,
+        ctx.accounts.keeper_auth.as_deref(),
         now,
-        &market.funding_params,
-        market.risk_params.oi_cap,
     )?;
-    settle_user_funding(position, funding_state, margin)?;
-
-    let mmr_required = mul_bps_u64(margin.total_notional, market.risk_params.mmr_bps as u64)?;
-    require!(
-        margin.collateral_balance < mmr_required,
-        ErrorCode::NotLiquidatable
-    );
-
-    let reduced_notional = reduce_position(position, leg, close_qty)?;
-    require!(reduced_notional > 0, ErrorCode::InvalidAmount);
 
-    margin.total_notional = margin
-        .total_notional
-        .checked_sub(reduced_notional)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let secondary_oracles: Vec<&UncheckedAccount> = [
+        ctx.accounts.secondary_oracle.as_ref(),
+        ctx.accounts.tertiary_oracle.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let (oracle_price, oracle_conf, _) = read_oracle_price_update(
+        market,
+        &ctx.accounts.oracle_price_update,
+        &secondary_oracles,
+        &clock,
+        OracleUse::Liquidation,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, oracle_price)?;
+    validate_liquidation_confidence(&market.pricing_params, oracle_price, oracle_conf)?;
+    validate_liquidation_band(&market.pricing_params, oracle_price, index_price)?;
 
-    funding_state.open_interest = funding_state
-        .open_interest
-        .checked_sub(reduced_notional)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    require!(!funding_state.is_halted(), ErrorCode::MarketHaltedLocal);
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &accounts.market.funding_params,
+        accounts.market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+    validate_liquidation_twap(
+        &accounts.market.pricing_params,
+        &funding_state,
+        now,
+        oracle_price,
+    )?;
 
-    funding_state.skew = match leg {
-        PositionLeg::Long => funding_state
-            .skew
-            .checked_sub(reduced_notional as i128)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
-        PositionLeg::Short => funding_state
-            .skew
-            .checked_add(reduced_notional as i128)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
-    };
-
-    let penalty = mul_bps_u64(
-        reduced_notional,
-        ctx.accounts.engine_config.liquidation_penalty_bps as u64,
+    let mut outcome = liquidate_position(
+        &accounts.market,
+        &mut funding_state,
+        &mut accounts.user_margin,
+        &mut *accounts.user_market_position.load_mut()?,
+        leg,
+        close_qty,
+        accounts.engine_config.liquidation_penalty_bps,
+        oracle_price,
+        now,
     )?;
-    let keeper_portion = mul_bps_u64(penalty, 1_000)?;
-    let insurance_portion = penalty
-        .checked_sub(keeper_portion)
-        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let mut bad_debt = 0u64;
-    if margin.collateral_balance >= penalty {
-        margin.collateral_balance = margin
-            .collateral_balance
-            .checked_sub(penalty)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-    } else {
-        bad_debt = penalty
-            .checked_sub(margin.collateral_balance)
-            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
-        margin.collateral_balance = 0;
-    }
+    accounts.engine_stats.debit_collateral(outcome.penalty);
 
     transfer_from_collateral(
-        &ctx.accounts.token_program,
-        &ctx.accounts.collateral_vault,
-        &ctx.accounts.lp_insurance_vault,
-        &ctx.accounts.engine_authority,
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_insurance_vault,
+        &accounts.engine_authority,
         ctx.bumps.engine_authority,
-        insurance_portion,
+        outcome.insurance_portion,
     )?;
 
+    // The keeper is paid directly; only a reward above the pool's cap is left
+    // with protocol fees.
+    let keeper_reward = accounts.lp_pool.liquidation_reward(outcome.keeper_portion);
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.keeper_token_account,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        keeper_reward,
+    )?;
     transfer_from_collateral(
-        &ctx.accounts.token_program,
-        &ctx.accounts.collateral_vault,
-        &ctx.accounts.lp_protocol_fee_vault,
-        &ctx.accounts.engine_authority,
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_protocol_fee_vault,
+        &accounts.engine_authority,
         ctx.bumps.engine_authority,
-        keeper_portion,
+        outcome.keeper_portion - keeper_reward,
     )?;
 
-    let insurance_after_credit = ctx
-        .accounts
+    let insurance_after_credit = accounts
         .lp_insurance_vault
         .amount
-        .checked_add(insurance_portion)
+        .checked_add(outcome.insurance_portion)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    if bad_debt > insurance_after_credit {
-        funding_state.halted = true;
-        return err!(ErrorCode::InsuranceShortfallMarketHalted);
+    // Whatever insurance cannot absorb is haircut from the winning side; only a
+    // market with nobody on that side still halts.
+    let shortfall = outcome.bad_debt.saturating_sub(insurance_after_credit);
+    if shortfall > 0 {
+        if !socialize_shortfall(&mut funding_state, leg, shortfall)? {
+            funding_state.halted = 1;
+            return err!(ErrorCode::InsuranceShortfallMarketHalted);
+        }
+        outcome.bad_debt -= shortfall;
+        emit!(LossSocialized {
+            market_id,
+            liquidated_leg: leg as u8,
+            shortfall,
+            loss_index_long: funding_state.socialized_loss_index_long,
+            loss_index_short: funding_state.socialized_loss_index_short,
+            timestamp: now,
+        });
     }
 
-    cpi_apply_liquidation(&ctx, market_id, penalty, bad_debt)?;
-
-    Ok(())
-}
-
-fn cpi_apply_liquidation(
-    ctx: &Context<Liquidate>,
-    market_id: u64,
-    penalty: u64,
-    bad_debt: u64,
-) -> Result<()> {
-    let seeds: &[&[u8]] = &[b"engine-authority", &[ctx.bumps.engine_authority]];
-    let signer_seeds = &[seeds];
-
-    let cpi_accounts = lp_vault::cpi::accounts::ApplyLiquidation {
-        engine_authority: ctx.accounts.engine_authority.to_account_info(),
-        pool: ctx.accounts.lp_pool.to_account_info(),
-        keeper: ctx.accounts.executor.to_account_info(),
-        keeper_rebate: ctx.accounts.keeper_rebate.to_account_info(),
-        insurance_vault: ctx.accounts.lp_insurance_vault.to_account_info(),
-    };
-
-    lp_vault::cpi::apply_liquidation(
-        CpiContext::new_with_signer(
-            ctx.accounts.lp_vault_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        ),
+    cpi_apply_liquidation(
+        &LiquidationCpi {
+            lp_vault_program: &accounts.lp_vault_program.to_account_info(),
+            engine_authority: &accounts.engine_authority.to_account_info(),
+            engine_authority_bump: ctx.bumps.engine_authority,
+            lp_pool: &accounts.lp_pool.to_account_info(),
+            lp_market_insurance: &accounts.lp_market_insurance.to_account_info(),
+            lp_market_stats: &accounts.lp_market_stats.to_account_info(),
+            lp_insurance_vault: &accounts.lp_insurance_vault.to_account_info(),
+            keeper: &accounts.executor.to_account_info(),
+        },
         market_id,
-        ctx.accounts.user_margin.owner,
-        penalty,
-        bad_debt,
+        accounts.user_margin.owner,
+        outcome.penalty,
+        outcome.bad_debt,
+        outcome.realized_pnl,
     )
 }
 
@@ -186,21 +185,38 @@ pub struct Liquidate<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
     pub market_registry_program: Program<'info, MarketRegistry>,
-    #[account(address = engine_config.keeper_set)]
-    pub keeper_set: Box<Account<'info, market_registry::KeeperSet>>,
+    #[account(
+        seeds = [b"keeper-liveness"],
+        seeds::program = market_registry_program.key(),
+        bump = keeper_liveness.bump,
+    )]
+    pub keeper_liveness: Box<Account<'info, market_registry::KeeperLiveness>>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
     #[account(
         seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
         seeds::program = market_registry_program.key(),
         bump = market.bump,
     )]
     pub market: Box<Account<'info, market_registry::Market>>,
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
+    pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
     #[account(
         mut,
         seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
-        bump = market_funding_state.bump,
+        bump = market_funding_state.load()?.bump,
     )]
-    pub market_funding_state: Box<Account<'info, MarketFundingState>>,
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
     #[account(
         mut,
         seeds = [b"user-margin", user_margin.owner.as_ref()],
@@ -210,23 +226,35 @@ pub struct Liquidate<'info> {
     #[account(
         mut,
         seeds = [b"user-market-pos", user_margin.key().as_ref(), &market_id.to_le_bytes()],
-        bump = user_market_position.bump,
+        bump = user_market_position.load()?.bump,
     )]
-    pub user_market_position: Box<Account<'info, UserMarketPosition>>,
+    pub user_market_position: AccountLoader<'info, UserMarketPosition>,
     /// CHECK: engine authority PDA.
     #[account(seeds = [b"engine-authority"], bump)]
     pub engine_authority: UncheckedAccount<'info>,
-    #[account(mut, address = engine_config.collateral_vault)]
-    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub lp_vault_program: Program<'info, LpVault>,
-    #[account(mut, address = engine_config.lp_pool)]
+    #[account(mut, address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
     pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
-    #[account(mut, address = engine_config.lp_insurance_vault)]
-    pub lp_insurance_vault: Box<Account<'info, TokenAccount>>,
-    #[account(mut, address = engine_config.lp_protocol_fee_vault)]
-    pub lp_protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
+        constraint = keeper_token_account.owner == executor.key() @ ErrorCode::Unauthorized,
+    )]
+    pub keeper_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: per-market insurance earmark PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_insurance: UncheckedAccount<'info>,
+    /// CHECK: per-market vault stats PDA, seeds checked by lp_vault.
     #[account(mut)]
-    pub keeper_rebate: Box<Account<'info, lp_vault::KeeperRebate>>,
-    pub token_program: Program<'info, Token>,
+    pub lp_market_stats: UncheckedAccount<'info>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use lp_vault::program::LpVault;
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    constants::MAX_BATCH_LIQUIDATIONS,
+    error::ErrorCode,
+    events::LossSocialized,
+    helpers::{
+        assert_liquidator_authorized, cpi_apply_liquidation, liquidate_position, read_index_price,
+        read_oracle_price_update, socialize_shortfall, transfer_from_collateral,
+        update_funding_index, validate_liquidation_band, validate_liquidation_confidence,
+        validate_liquidation_twap, LiquidationCpi, OracleUse,
+    },
+    state::{
+        EngineConfig, EngineStats, LiquidationTarget, MarketFundingState, UserMargin,
+        UserMarketPosition,
+    },
+};
+
+// Remaining accounts are consumed in pairs per target:
+// [user_margin (mut), user_market_position (mut)].
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, LiquidateMany<'info>>,
+    market_id: u64,
+    targets: Vec<LiquidationTarget>,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
+) -> Result<()> {
+    require!(
+        !targets.is_empty() && targets.len() <= MAX_BATCH_LIQUIDATIONS,
+        ErrorCode::InvalidBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == targets.len() * 2,
+        ErrorCode::InvalidBatch
+    );
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let market = &ctx.accounts.market;
+
+    require!(market.market_id == market_id, ErrorCode::MarketMismatch);
+    require!(
+        market.status.allows_liquidation(),
+        ErrorCode::MarketNotActive
+    );
+
+    assert_liquidator_authorized(
+        &ctx.accounts.executor,
+        &ctx.accounts.keeper_liveness,
+        ctx.accounts.keeper_auth.as_deref(),
+        now,
+    )?;
+
+    let secondary_oracles: Vec<&UncheckedAccount> = [
+        ctx.accounts.secondary_oracle.as_ref(),
+        ctx.accounts.tertiary_oracle.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let (oracle_price, oracle_conf, _) = read_oracle_price_update(
+        market,
+        &ctx.accounts.oracle_price_update,
+        &secondary_oracles,
+        &clock,
+        OracleUse::Liquidation,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, oracle_price)?;
+    validate_liquidation_confidence(&market.pricing_params, oracle_price, oracle_conf)?;
+    validate_liquidation_band(&market.pricing_params, oracle_price, index_price)?;
+
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    require!(!funding_state.is_halted(), ErrorCode::MarketHaltedLocal);
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &accounts.market.funding_params,
+        accounts.market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+    validate_liquidation_twap(
+        &accounts.market.pricing_params,
+        &funding_state,
+        now,
+        oracle_price,
+    )?;
+
+    let mut liquidations = Vec::with_capacity(targets.len());
+    let mut total_insurance_portion = 0u64;
+    let mut total_keeper_reward = 0u64;
+    let mut total_capped_reward = 0u64;
+    let mut total_bad_debt = 0u64;
+    for (group, target) in ctx.remaining_accounts.chunks(2).zip(targets) {
+        let mut margin = Account::<UserMargin>::try_from(&group[0])?;
+        let position_loader = AccountLoader::<UserMarketPosition>::try_from(&group[1])?;
+        require!(
+            group.iter().all(|info| info.is_writable),
+            ErrorCode::InvalidBatch
+        );
+
+        let outcome = liquidate_position(
+            &accounts.market,
+            &mut funding_state,
+            &mut margin,
+            &mut *position_loader.load_mut()?,
+            target.leg,
+            target.close_qty,
+            accounts.engine_config.liquidation_penalty_bps,
+            oracle_price,
+            now,
+        )?;
+
+        margin.exit(&crate::ID)?;
+
+        total_insurance_portion = total_insurance_portion
+            .checked_add(outcome.insurance_portion)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        // The reward cap applies per liquidation, not to the batch.
+        let keeper_reward = accounts.lp_pool.liquidation_reward(outcome.keeper_portion);
+        total_keeper_reward = total_keeper_reward
+            .checked_add(keeper_reward)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        total_capped_reward = total_capped_reward
+            .checked_add(outcome.keeper_portion - keeper_reward)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        total_bad_debt = total_bad_debt
+            .checked_add(outcome.bad_debt)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        accounts.engine_stats.debit_collateral(outcome.penalty);
+        liquidations.push((margin.owner, target.leg, outcome));
+    }
+
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_insurance_vault,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        total_insurance_portion,
+    )?;
+
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.keeper_token_account,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        total_keeper_reward,
+    )?;
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_protocol_fee_vault,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        total_capped_reward,
+    )?;
+
+    let insurance_after_credit = accounts
+        .lp_insurance_vault
+        .amount
+        .checked_add(total_insurance_portion)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+    // Whatever insurance cannot absorb is haircut from the winning side of the
+    // targets that produced it, latest first; only a market with nobody on
+    // that side still halts.
+    let mut shortfall = total_bad_debt.saturating_sub(insurance_after_credit);
+    for (_, leg, outcome) in liquidations.iter_mut().rev() {
+        if shortfall == 0 {
+            break;
+        }
+        let share = shortfall.min(outcome.bad_debt);
+        if share == 0 {
+            continue;
+        }
+        if !socialize_shortfall(&mut funding_state, *leg, share)? {
+            funding_state.halted = 1;
+            return err!(ErrorCode::InsuranceShortfallMarketHalted);
+        }
+        outcome.bad_debt -= share;
+        shortfall -= share;
+        emit!(LossSocialized {
+            market_id,
+            liquidated_leg: *leg as u8,
+            shortfall: share,
+            loss_index_long: funding_state.socialized_loss_index_long,
+            loss_index_short: funding_state.socialized_loss_index_short,
+            timestamp: now,
+        });
+    }
+
+    let liquidation_cpi = LiquidationCpi {
+        lp_vault_program: &accounts.lp_vault_program.to_account_info(),
+        engine_authority: &accounts.engine_authority.to_account_info(),
+        engine_authority_bump: ctx.bumps.engine_authority,
+        lp_pool: &accounts.lp_pool.to_account_info(),
+        lp_market_insurance: &accounts.lp_market_insurance.to_account_info(),
+        lp_market_stats: &accounts.lp_market_stats.to_account_info(),
+        lp_insurance_vault: &accounts.lp_insurance_vault.to_account_info(),
+        keeper: &accounts.executor.to_account_info(),
+    };
+    for (user, _, outcome) in liquidations {
+        cpi_apply_liquidation(
+            &liquidation_cpi,
+            market_id,
+            user,
+            outcome.penalty,
+            outcome.bad_debt,
+            outcome.realized_pnl,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct LiquidateMany<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(
+        seeds = [b"keeper-liveness"],
+        seeds::program = market_registry_program.key(),
+        bump = keeper_liveness.bump,
+    )]
+    pub keeper_liveness: Box<Account<'info, market_registry::KeeperLiveness>>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, market_registry::Market>>,
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
+    pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lp_vault_program: Program<'info, LpVault>,
+    #[account(mut, address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
+    pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_protocol_fee_vault @ ErrorCode::ProtocolFeeVaultMismatch)]
+    pub lp_protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
+        constraint = keeper_token_account.owner == executor.key() @ ErrorCode::Unauthorized,
+    )]
+    pub keeper_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: per-market insurance earmark PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_insurance: UncheckedAccount<'info>,
+    /// CHECK: per-market vault stats PDA, seeds checked by lp_vault.
+    #[account(mut)]
+    pub lp_market_stats: UncheckedAccount<'info>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
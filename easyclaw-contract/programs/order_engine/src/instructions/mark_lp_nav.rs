@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use lp_vault::program::LpVault;
+use market_registry::{program::MarketRegistry, KeeperScope};
+
+use crate::{
+    error::ErrorCode,
+    events::LpNavMarked,
+    helpers::{assert_covers_market_list, assert_keeper_scope, cpi_mark_nav},
+    state::{EngineConfig, MarketFundingState},
+};
+
+// Remaining accounts: the funding state of every market in the registry's
+// `MarketList`, ordered by strictly increasing market id. Each market is
+// marked at its index price and the net trader PnL is pushed to the LP pool,
+// which prices deposits and withdrawals against it, together with the total
+// open interest that bounds insurance rebalancing.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MarkLpNav<'info>>) -> Result<()> {
+    assert_keeper_scope(
+        &ctx.accounts.executor,
+        &ctx.accounts.keeper_auth,
+        KeeperScope::CrankFunding,
+    )?;
+
+    let mut unrealized_trader_pnl = 0i128;
+    let mut open_interest = 0u64;
+    let mut market_ids = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        let loader = AccountLoader::<MarketFundingState>::try_from(info)?;
+        let funding_state = loader.load()?;
+        market_ids.push(funding_state.market_id);
+        open_interest = open_interest
+            .checked_add(funding_state.open_interest)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        if funding_state.long_open_qty == 0 && funding_state.short_open_qty == 0 {
+            continue;
+        }
+        require!(funding_state.index_price > 0, ErrorCode::InvalidPrice);
+        unrealized_trader_pnl = unrealized_trader_pnl
+            .checked_add(funding_state.unrealized_trader_pnl(funding_state.index_price)?)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    }
+    assert_covers_market_list(&market_ids, &ctx.accounts.market_list)?;
+    let unrealized_trader_pnl =
+        i64::try_from(unrealized_trader_pnl).map_err(|_| error!(ErrorCode::MathOverflow))?;
+
+    cpi_mark_nav(
+        &ctx.accounts.lp_vault_program.to_account_info(),
+        &ctx.accounts.engine_authority.to_account_info(),
+        ctx.bumps.engine_authority,
+        &ctx.accounts.lp_pool.to_account_info(),
+        unrealized_trader_pnl,
+        open_interest,
+    )?;
+
+    emit!(LpNavMarked {
+        markets: ctx.remaining_accounts.len() as u32,
+        unrealized_trader_pnl,
+        open_interest,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MarkLpNav<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    pub keeper_auth: Box<Account<'info, market_registry::KeeperAuthorization>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(
+        seeds = [b"market-list"],
+        seeds::program = market_registry_program.key(),
+        bump = market_list.bump,
+    )]
+    pub market_list: Box<Account<'info, market_registry::MarketList>>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    pub lp_vault_program: Program<'info, LpVault>,
+    #[account(mut, address = engine_config.lp_pool @ ErrorCode::LpPoolMismatch)]
+    pub lp_pool: Box<Account<'info, lp_vault::Pool>>,
+}
@@ -1,23 +1,59 @@
+pub mod auto_top_up;
 pub mod cancel_order;
 pub mod cancel_order_by_executor;
+pub mod compensate_user;
+pub mod crank_funding;
 pub mod create_margin_account;
 pub mod create_user_market_position;
 pub mod deposit_collateral;
+pub mod deposit_sol_collateral;
 pub mod execute_order;
+pub mod execute_orders;
+pub mod get_protocol_overview;
 pub mod initialize_engine;
+pub mod initialize_engine_stats;
 pub mod initialize_market_funding_state;
+pub mod initialize_market_mirror;
+pub mod initialize_market_stats;
+pub mod initialize_recent_fills;
 pub mod liquidate;
+pub mod liquidate_many;
+pub mod mark_lp_nav;
+pub mod place_ladder;
 pub mod place_order;
+pub mod reset_circuit_breaker;
+pub mod set_top_up_allowance;
+pub mod socialize_loss;
+pub mod sweep_dust_position;
+pub mod sync_market_mirror;
 pub mod withdraw_collateral;
 
+pub use auto_top_up::*;
 pub use cancel_order::*;
 pub use cancel_order_by_executor::*;
+pub use compensate_user::*;
+pub use crank_funding::*;
 pub use create_margin_account::*;
 pub use create_user_market_position::*;
 pub use deposit_collateral::*;
+pub use deposit_sol_collateral::*;
 pub use execute_order::*;
+pub use execute_orders::*;
+pub use get_protocol_overview::*;
 pub use initialize_engine::*;
+pub use initialize_engine_stats::*;
 pub use initialize_market_funding_state::*;
+pub use initialize_market_mirror::*;
+pub use initialize_market_stats::*;
+pub use initialize_recent_fills::*;
 pub use liquidate::*;
+pub use liquidate_many::*;
+pub use mark_lp_nav::*;
+pub use place_ladder::*;
 pub use place_order::*;
+pub use reset_circuit_breaker::*;
+pub use set_top_up_allowance::*;
+pub use socialize_loss::*;
+pub use sweep_dust_position::*;
+pub use sync_market_mirror::*;
 pub use withdraw_collateral::*;
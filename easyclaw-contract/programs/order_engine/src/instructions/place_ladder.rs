@@ -0,0 +1,229 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+
+use crate::{
+    constants::MAX_LADDER_STEPS,
+    error::ErrorCode,
+    helpers::{estimate_order_reservation, require_initial_margin_health},
+    state::{
+        EngineConfig, EngineStats, MarketMirror, Order, OrderStatus, OrderType, Side, UserMargin,
+    },
+};
+
+// Remaining accounts are the uninitialized order PDAs, one per step, in nonce
+// order starting at `user_margin.next_order_nonce`. Step `i` is priced at
+// `price_low + (price_high - price_low) * i / (steps - 1)` snapped down to the
+// market tick, carries `client_order_id + i`, and gets `total_margin / steps`
+// (the last step also takes the remainder).
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceLadder<'info>>,
+    market_id: u64,
+    side: Side,
+    reduce_only: bool,
+    total_margin: u64,
+    price_low: u64,
+    price_high: u64,
+    steps: u8,
+    ttl_secs: i64,
+    client_order_id: u64,
+) -> Result<()> {
+    let steps = steps as usize;
+    require!(
+        steps > 0 && steps <= MAX_LADDER_STEPS,
+        ErrorCode::InvalidLadder
+    );
+    require!(
+        ctx.remaining_accounts.len() == steps,
+        ErrorCode::InvalidLadder
+    );
+    require!(price_low > 0, ErrorCode::InvalidLimitPrice);
+    require!(price_low <= price_high, ErrorCode::InvalidLadder);
+    require!(
+        ctx.accounts.market_mirror.is_on_tick(price_low)
+            && ctx.accounts.market_mirror.is_on_tick(price_high),
+        ErrorCode::PriceNotOnTick
+    );
+    require!(
+        steps > 1 || price_low == price_high,
+        ErrorCode::InvalidLadder
+    );
+    let step_margin = total_margin / steps as u64;
+    require!(step_margin > 0, ErrorCode::InvalidAmount);
+    require!(ttl_secs > 0, ErrorCode::InvalidTtl);
+    require!(
+        ttl_secs
+            <= ctx
+                .accounts
+                .market_mirror
+                .max_ttl_secs(ctx.accounts.engine_config.max_ttl_secs),
+        ErrorCode::TtlTooLong
+    );
+    require!(
+        ctx.accounts.market_mirror.market_id == market_id,
+        ErrorCode::MarketMismatch
+    );
+    require!(
+        !ctx.accounts.global_config.global_pause,
+        ErrorCode::GlobalPaused
+    );
+    require!(
+        ctx.accounts.market_mirror.status.allows_placement(true),
+        ErrorCode::MarketNotActive
+    );
+    require!(
+        ctx.accounts
+            .market_mirror
+            .status
+            .allows_placement(reduce_only),
+        ErrorCode::MarketReduceOnly
+    );
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let expires_at = now
+        .checked_add(ttl_secs)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    let space = 8 + std::mem::size_of::<Order>();
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+
+    let accounts = &mut *ctx.accounts;
+    let margin = &mut accounts.user_margin;
+    require_keys_eq!(margin.owner, accounts.user.key(), ErrorCode::Unauthorized);
+    let margin_key = margin.key();
+    let mirror = &accounts.market_mirror;
+    if !reduce_only {
+        require_initial_margin_health(margin, mirror.imr_bps as u64)?;
+    }
+
+    for (i, order_info) in ctx.remaining_accounts.iter().enumerate() {
+        let nonce = margin.next_order_nonce;
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"order", margin_key.as_ref(), &nonce.to_le_bytes()],
+            &crate::ID,
+        );
+        require_keys_eq!(order_info.key(), expected, ErrorCode::InvalidLadder);
+
+        let order_margin = if i + 1 == steps {
+            total_margin
+                .checked_sub(step_margin * (steps as u64 - 1))
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+        } else {
+            step_margin
+        };
+        let price = if steps == 1 {
+            price_low
+        } else {
+            let offset = ((price_high - price_low) as u128)
+                .checked_mul(i as u128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+                / (steps as u128 - 1);
+            let price = price_low
+                .checked_add(offset as u64)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            // Interior steps snap down to the tick; the ends are already on it.
+            match mirror.price_tick_size {
+                0 => price,
+                tick => price - price % tick,
+            }
+        };
+
+        let reserved_collateral = estimate_order_reservation(
+            reduce_only,
+            order_margin,
+            mirror.imr_bps,
+            mirror.taker_fee_bps,
+        )?;
+        require!(
+            margin.collateral_balance >= reserved_collateral,
+            ErrorCode::InsufficientCollateral
+        );
+        margin.collateral_balance = margin
+            .collateral_balance
+            .checked_sub(reserved_collateral)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+
+        create_account(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: accounts.user.to_account_info(),
+                    to: order_info.clone(),
+                },
+                &[&[b"order", margin_key.as_ref(), &nonce.to_le_bytes(), &[bump]]],
+            ),
+            rent_lamports,
+            space as u64,
+            &crate::ID,
+        )?;
+
+        let order_loader = AccountLoader::<Order>::try_from_unchecked(&crate::ID, order_info)?;
+        {
+            let mut order = order_loader.load_init()?;
+            order.id = nonce;
+            order.user_margin = margin_key;
+            order.user = accounts.user.key();
+            order.market_id = market_id;
+            order.side = side as u8;
+            order.order_type = OrderType::Limit as u8;
+            order.reduce_only = reduce_only as u8;
+            order.margin = order_margin;
+            order.reserved_collateral = reserved_collateral;
+            order.price = price;
+            order.created_at = now;
+            order.created_slot = clock.slot;
+            order.expires_at = expires_at;
+            order.client_order_id = client_order_id.wrapping_add(i as u64);
+            order.set_status(OrderStatus::Open);
+            order.bump = bump;
+        }
+        order_loader.exit(&crate::ID)?;
+
+        if !reduce_only {
+            margin.open_order_notional = margin
+                .open_order_notional
+                .checked_add(order_margin)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        }
+        margin.next_order_nonce = nonce
+            .checked_add(1)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    }
+    accounts.engine_stats.orders_opened(steps as u64);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct PlaceLadder<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
+    pub global_config: Account<'info, market_registry::GlobalConfig>,
+    #[account(
+        seeds = [b"market-mirror".as_ref(), &market_id.to_le_bytes()],
+        bump = market_mirror.bump,
+    )]
+    pub market_mirror: Account<'info, MarketMirror>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user.key().as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Account<'info, UserMargin>,
+    pub system_program: Program<'info, System>,
+}
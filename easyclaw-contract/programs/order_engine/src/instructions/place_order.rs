@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
-use market_registry::program::MarketRegistry;
 
 use crate::{
     error::ErrorCode,
-    helpers::estimate_order_reservation,
-    state::{EngineConfig, Order, OrderStatus, OrderType, Side, UserMargin},
+    helpers::{estimate_order_reservation, require_initial_margin_health},
+    state::{
+        EngineConfig, EngineStats, ExecutionGuard, MarketMirror, Order, OrderStatus, OrderTrigger,
+        OrderType, Side, UserMargin,
+    },
 };
 
 pub fn handler(
@@ -17,15 +19,22 @@ pub fn handler(
     price: u64,
     ttl_secs: i64,
     client_order_id: u64,
+    execution_guard: ExecutionGuard,
+    trigger: Option<OrderTrigger>,
+    priority_tip: u64,
 ) -> Result<()> {
     require!(order_margin > 0, ErrorCode::InvalidAmount);
     require!(ttl_secs > 0, ErrorCode::InvalidTtl);
     require!(
-        ttl_secs <= ctx.accounts.engine_config.max_ttl_secs,
+        ttl_secs
+            <= ctx
+                .accounts
+                .market_mirror
+                .max_ttl_secs(ctx.accounts.engine_config.max_ttl_secs),
         ErrorCode::TtlTooLong
     );
     require!(
-        ctx.accounts.market.market_id == market_id,
+        ctx.accounts.market_mirror.market_id == market_id,
         ErrorCode::MarketMismatch
     );
     require!(
@@ -33,13 +42,38 @@ pub fn handler(
         ErrorCode::GlobalPaused
     );
     require!(
-        ctx.accounts.market.status == market_registry::MarketStatus::Active,
+        ctx.accounts.market_mirror.status.allows_placement(true),
         ErrorCode::MarketNotActive
     );
+    require!(
+        ctx.accounts
+            .market_mirror
+            .status
+            .allows_placement(reduce_only),
+        ErrorCode::MarketReduceOnly
+    );
 
     require!(price > 0, ErrorCode::InvalidLimitPrice);
+    require!(
+        ctx.accounts.market_mirror.is_on_tick(price),
+        ErrorCode::PriceNotOnTick
+    );
+    require!(
+        execution_guard.deadline_secs >= 0,
+        ErrorCode::InvalidExecutionGuard
+    );
+    if execution_guard.deadline_secs > 0 {
+        require!(
+            execution_guard.reference_price > 0,
+            ErrorCode::InvalidExecutionGuard
+        );
+    }
+    if let Some(trigger) = trigger {
+        require!(trigger.trigger_price > 0, ErrorCode::InvalidTrigger);
+    }
 
-    let now = Clock::get()?.unix_timestamp;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
     let margin = &mut ctx.accounts.user_margin;
     require_keys_eq!(
         margin.owner,
@@ -47,8 +81,18 @@ pub fn handler(
         ErrorCode::Unauthorized
     );
 
-    let reserved_collateral =
-        estimate_order_reservation(reduce_only, order_margin, &ctx.accounts.market)?;
+    let mirror = &ctx.accounts.market_mirror;
+    if !reduce_only {
+        require_initial_margin_health(margin, mirror.imr_bps as u64)?;
+    }
+    let reserved_collateral = estimate_order_reservation(
+        reduce_only,
+        order_margin,
+        mirror.imr_bps,
+        mirror.taker_fee_bps,
+    )?
+    .checked_add(priority_tip)
+    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
     require!(
         margin.collateral_balance >= reserved_collateral,
         ErrorCode::InsufficientCollateral
@@ -58,28 +102,48 @@ pub fn handler(
         .checked_sub(reserved_collateral)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let order = &mut ctx.accounts.order;
+    let mut order = ctx.accounts.order.load_init()?;
     order.id = margin.next_order_nonce;
     order.user_margin = margin.key();
     order.user = ctx.accounts.user.key();
     order.market_id = market_id;
-    order.side = side;
-    order.order_type = order_type;
-    order.reduce_only = reduce_only;
+    order.side = side as u8;
+    order.order_type = order_type as u8;
+    order.reduce_only = reduce_only as u8;
     order.margin = order_margin;
+    order.reserved_collateral = reserved_collateral;
+    order.priority_tip = priority_tip;
     order.price = price;
     order.created_at = now;
+    order.created_slot = clock.slot;
     order.expires_at = now
         .checked_add(ttl_secs)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
     order.client_order_id = client_order_id;
-    order.status = OrderStatus::Open;
+    if execution_guard.deadline_secs > 0 {
+        order.execution_deadline = now
+            .checked_add(execution_guard.deadline_secs)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        order.reference_price = execution_guard.reference_price;
+        order.max_adverse_move_bps = execution_guard.max_adverse_move_bps;
+    }
+    if let Some(trigger) = trigger {
+        order.set_trigger(trigger);
+    }
+    order.set_status(OrderStatus::Open);
     order.bump = ctx.bumps.order;
 
+    if !reduce_only {
+        margin.open_order_notional = margin
+            .open_order_notional
+            .checked_add(order_margin)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    }
     margin.next_order_nonce = margin
         .next_order_nonce
         .checked_add(1)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+    ctx.accounts.engine_stats.orders_opened(1);
 
     Ok(())
 }
@@ -94,15 +158,19 @@ pub struct PlaceOrder<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Account<'info, EngineConfig>,
-    pub market_registry_program: Program<'info, MarketRegistry>,
-    #[account(address = engine_config.registry_global_config)]
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
     pub global_config: Account<'info, market_registry::GlobalConfig>,
     #[account(
-        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
-        seeds::program = market_registry_program.key(),
-        bump = market.bump,
+        seeds = [b"market-mirror".as_ref(), &market_id.to_le_bytes()],
+        bump = market_mirror.bump,
     )]
-    pub market: Account<'info, market_registry::Market>,
+    pub market_mirror: Account<'info, MarketMirror>,
     #[account(
         mut,
         seeds = [b"user-margin", user.key().as_ref()],
@@ -114,8 +182,8 @@ pub struct PlaceOrder<'info> {
         payer = user,
         seeds = [b"order", user_margin.key().as_ref(), &user_margin.next_order_nonce.to_le_bytes()],
         bump,
-        space = 8 + Order::INIT_SPACE,
+        space = 8 + std::mem::size_of::<Order>(),
     )]
-    pub order: Account<'info, Order>,
+    pub order: AccountLoader<'info, Order>,
     pub system_program: Program<'info, System>,
 }
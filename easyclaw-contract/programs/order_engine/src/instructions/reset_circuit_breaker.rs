@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::require_admin,
+    state::{EngineConfig, MarketFundingState},
+};
+
+pub fn handler(ctx: Context<ResetCircuitBreaker>, _market_id: u64) -> Result<()> {
+    require_admin(&ctx.accounts.admin, &ctx.accounts.engine_config)?;
+
+    // The next accepted price becomes the new breaker reference.
+    let mut funding_state = ctx.accounts.market_funding_state.load_mut()?;
+    funding_state.breaker_tripped = 0;
+    funding_state.last_accepted_price = 0;
+    funding_state.last_accepted_price_ts = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResetCircuitBreaker<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+}
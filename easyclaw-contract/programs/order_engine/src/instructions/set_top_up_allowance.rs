@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Approve, Revoke, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::MAX_TOP_UP_THRESHOLD_BPS,
+    error::ErrorCode,
+    state::{EngineConfig, UserMargin},
+};
+
+// Approves the engine authority as delegate of the user's token account for
+// `allowance`; a zero allowance revokes the delegation.
+pub fn handler(ctx: Context<SetTopUpAllowance>, allowance: u64, threshold_bps: u16) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.user_margin.owner,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let margin = &mut ctx.accounts.user_margin;
+    if allowance == 0 {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+        margin.top_up_source = Pubkey::default();
+        margin.top_up_allowance = 0;
+        margin.top_up_threshold_bps = 0;
+        return Ok(());
+    }
+
+    require!(
+        threshold_bps > 10_000 && threshold_bps <= MAX_TOP_UP_THRESHOLD_BPS,
+        ErrorCode::InvalidTopUpThreshold
+    );
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.user_token_account.to_account_info(),
+                delegate: ctx.accounts.engine_authority.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        allowance,
+    )?;
+    margin.top_up_source = ctx.accounts.user_token_account.key();
+    margin.top_up_allowance = allowance;
+    margin.top_up_threshold_bps = threshold_bps;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTopUpAllowance<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user.key().as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Account<'info, UserMargin>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = user_token_account.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::MAX_BATCH_LIQUIDATIONS,
+    error::ErrorCode,
+    events::SocializedLossCollected,
+    helpers::{assert_keeper_only, settle_socialized_loss, transfer_from_collateral},
+    state::{EngineConfig, EngineStats, MarketFundingState, UserMargin, UserMarketPosition},
+};
+
+// Remaining accounts are consumed in pairs per winning position:
+// [user_margin (mut), user_market_position (mut)]. Each is charged its
+// pro-rata share of the socialized shortfall, and everything collected so far
+// (including haircuts taken lazily on fills) is swept to the insurance vault.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SocializeLoss<'info>>,
+    market_id: u64,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2)
+            && ctx.remaining_accounts.len() <= MAX_BATCH_LIQUIDATIONS * 2,
+        ErrorCode::InvalidBatch
+    );
+    assert_keeper_only(&ctx.accounts.executor, &ctx.accounts.keeper_auth)?;
+
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    require!(
+        funding_state.market_id == market_id,
+        ErrorCode::MarketMismatch
+    );
+
+    for group in ctx.remaining_accounts.chunks(2) {
+        require!(
+            group.iter().all(|info| info.is_writable),
+            ErrorCode::InvalidBatch
+        );
+        let mut margin = Account::<UserMargin>::try_from(&group[0])?;
+        let position_loader = AccountLoader::<UserMarketPosition>::try_from(&group[1])?;
+        {
+            let mut position = position_loader.load_mut()?;
+            require!(position.market_id == market_id, ErrorCode::MarketMismatch);
+            require_keys_eq!(
+                position.user_margin,
+                margin.key(),
+                ErrorCode::PositionOwnerMismatch
+            );
+            settle_socialized_loss(&mut position, &mut funding_state, &mut margin)?;
+        }
+        margin.exit(&crate::ID)?;
+    }
+
+    let collected = funding_state.socialized_loss_collected;
+    funding_state.socialized_loss_collected = 0;
+    drop(funding_state);
+
+    accounts.engine_stats.debit_collateral(collected);
+    transfer_from_collateral(
+        &accounts.token_program,
+        &accounts.usdc_mint,
+        &accounts.collateral_vault,
+        &accounts.lp_insurance_vault,
+        &accounts.engine_authority,
+        ctx.bumps.engine_authority,
+        collected,
+    )?;
+
+    emit!(SocializedLossCollected {
+        market_id,
+        positions: (ctx.remaining_accounts.len() / 2) as u32,
+        amount: collected,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SocializeLoss<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Box<Account<'info, EngineStats>>,
+    pub keeper_auth: Box<Account<'info, market_registry::KeeperAuthorization>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+    /// CHECK: engine authority PDA.
+    #[account(seeds = [b"engine-authority"], bump)]
+    pub engine_authority: UncheckedAccount<'info>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = engine_config.lp_insurance_vault @ ErrorCode::InsuranceVaultMismatch)]
+    pub lp_insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use market_registry::program::MarketRegistry;
+
+use crate::{
+    constants::PRICE_SCALE,
+    error::ErrorCode,
+    events::DustPositionSwept,
+    helpers::{
+        assert_keeper_only, read_index_price, read_oracle_price_update, reduce_position,
+        settle_user_funding, update_funding_index, OracleUse,
+    },
+    state::{EngineConfig, MarketFundingState, PositionLeg, UserMargin, UserMarketPosition},
+};
+
+// Closes every leg worth less than the market's dust threshold at oracle
+// price. No fee or penalty is charged; funding is settled first.
+pub fn handler(
+    ctx: Context<SweepDustPosition>,
+    market_id: u64,
+    oracle_price: u64,
+    oracle_conf: u64,
+    oracle_publish_time: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let market = &ctx.accounts.market;
+
+    assert_keeper_only(&ctx.accounts.executor, &ctx.accounts.keeper_auth)?;
+    require!(market.market_id == market_id, ErrorCode::MarketMismatch);
+    require!(
+        market.risk_params.dust_notional > 0,
+        ErrorCode::PositionNotDust
+    );
+
+    let secondary_oracles: Vec<&UncheckedAccount> = [
+        ctx.accounts.secondary_oracle.as_ref(),
+        ctx.accounts.tertiary_oracle.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let (oracle_price, _, _) = read_oracle_price_update(
+        market,
+        &ctx.accounts.oracle_price_update,
+        &secondary_oracles,
+        &clock,
+        OracleUse::Execution,
+        oracle_price,
+        oracle_conf,
+        oracle_publish_time,
+    )?;
+    let index_price = read_index_price(market, &ctx.accounts.oracle_price_update, oracle_price)?;
+
+    let accounts = &mut *ctx.accounts;
+    let mut funding_state = accounts.market_funding_state.load_mut()?;
+    update_funding_index(
+        &mut funding_state,
+        now,
+        &accounts.market.funding_params,
+        accounts.market.risk_params.oi_cap,
+    )?;
+    if let Some(index_price) = index_price {
+        funding_state.index_price = index_price;
+    }
+
+    let mut position = accounts.user_market_position.load_mut()?;
+    let margin = &mut accounts.user_margin;
+    settle_user_funding(&mut position, &mut funding_state, margin)?;
+
+    let dust_notional = accounts.market.risk_params.dust_notional as u128;
+    let mut swept = [0u64; 2];
+    for (slot, leg) in [PositionLeg::Long, PositionLeg::Short]
+        .into_iter()
+        .enumerate()
+    {
+        let qty = match leg {
+            PositionLeg::Long => position.long_qty,
+            PositionLeg::Short => position.short_qty,
+        };
+        if qty == 0 {
+            continue;
+        }
+        let mark_notional = (qty as u128)
+            .checked_mul(oracle_price as u128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / PRICE_SCALE;
+        if mark_notional >= dust_notional {
+            continue;
+        }
+
+        let reduced_notional = reduce_position(&mut position, leg, qty, oracle_price, now)?;
+        margin.total_notional = margin
+            .total_notional
+            .checked_sub(reduced_notional)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        funding_state.open_interest = funding_state
+            .open_interest
+            .checked_sub(reduced_notional)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        funding_state.skew = match leg {
+            PositionLeg::Long => funding_state
+                .skew
+                .checked_sub(reduced_notional as i128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+            PositionLeg::Short => funding_state
+                .skew
+                .checked_add(reduced_notional as i128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?,
+        };
+        funding_state.remove_open_qty(leg, qty)?;
+        swept[slot] = qty;
+    }
+    require!(swept != [0, 0], ErrorCode::PositionNotDust);
+
+    emit!(DustPositionSwept {
+        market_id,
+        user_margin: margin.key(),
+        long_qty: swept[0],
+        short_qty: swept[1],
+        oracle_price,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SweepDustPosition<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Box<Account<'info, EngineConfig>>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    pub keeper_auth: Box<Account<'info, market_registry::KeeperAuthorization>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, market_registry::Market>>,
+    /// CHECK: validated in `read_oracle_price_update` helper (owner/discriminator/feed id/staleness, instructions sysvar for Lazer and the signed fallback).
+    pub oracle_price_update: UncheckedAccount<'info>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub secondary_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: extra feed for median-of-oracles markets, validated in `read_oracle_price_update`.
+    pub tertiary_oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"funding".as_ref(), &market_id.to_le_bytes()],
+        bump = market_funding_state.load()?.bump,
+    )]
+    pub market_funding_state: AccountLoader<'info, MarketFundingState>,
+    #[account(
+        mut,
+        seeds = [b"user-margin", user_margin.owner.as_ref()],
+        bump = user_margin.bump,
+    )]
+    pub user_margin: Box<Account<'info, UserMargin>>,
+    #[account(
+        mut,
+        seeds = [b"user-market-pos", user_margin.key().as_ref(), &market_id.to_le_bytes()],
+        bump = user_market_position.load()?.bump,
+    )]
+    pub user_market_position: AccountLoader<'info, UserMarketPosition>,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use market_registry::{program::MarketRegistry, KeeperScope};
+
+use crate::{
+    error::ErrorCode,
+    helpers::assert_executor_authorized,
+    state::{EngineConfig, MarketMirror},
+};
+
+pub fn handler(ctx: Context<SyncMarketMirror>, market_id: u64) -> Result<()> {
+    assert_executor_authorized(
+        &ctx.accounts.executor,
+        &ctx.accounts.global_config,
+        ctx.accounts.keeper_auth.as_deref(),
+        KeeperScope::Execute,
+    )?;
+    require!(
+        ctx.accounts.market.market_id == market_id,
+        ErrorCode::MarketMismatch
+    );
+
+    let market = &ctx.accounts.market;
+    let mirror = &mut ctx.accounts.market_mirror;
+    require!(
+        market.params_version >= mirror.params_version,
+        ErrorCode::StaleMarketMirror
+    );
+
+    mirror.sync_from(market, Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SyncMarketMirror<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"engine-config"],
+        bump = engine_config.bump,
+    )]
+    pub engine_config: Account<'info, EngineConfig>,
+    pub market_registry_program: Program<'info, MarketRegistry>,
+    #[account(address = engine_config.registry_global_config @ ErrorCode::GlobalConfigMismatch)]
+    pub global_config: Account<'info, market_registry::GlobalConfig>,
+    pub keeper_auth: Option<Box<Account<'info, market_registry::KeeperAuthorization>>>,
+    #[account(
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        seeds::program = market_registry_program.key(),
+        bump = market.bump,
+    )]
+    pub market: Account<'info, market_registry::Market>,
+    #[account(
+        mut,
+        seeds = [b"market-mirror".as_ref(), &market_id.to_le_bytes()],
+        bump = market_mirror.bump,
+    )]
+    pub market_mirror: Account<'info, MarketMirror>,
+}
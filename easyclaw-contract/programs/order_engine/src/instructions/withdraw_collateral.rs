@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
     error::ErrorCode,
-    helpers::mul_bps_u64,
-    state::{EngineConfig, UserMargin},
+    helpers::{pessimistic_margin_required, require_initial_margin_health},
+    state::{EngineConfig, EngineStats, UserMargin},
 };
 
 pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
@@ -15,6 +15,11 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
         ErrorCode::Unauthorized
     );
 
+    require_initial_margin_health(
+        &ctx.accounts.user_margin,
+        ctx.accounts.engine_config.max_imr_bps as u64,
+    )?;
+
     let collateral_balance = ctx.accounts.user_margin.collateral_balance;
     require!(
         collateral_balance >= amount,
@@ -25,8 +30,11 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
         .checked_sub(amount)
         .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
 
-    let required_margin = mul_bps_u64(
+    // Open orders count as filled so a withdrawal cannot race a keeper fill
+    // into leaving the account below IMR.
+    let required_margin = pessimistic_margin_required(
         ctx.accounts.user_margin.total_notional,
+        ctx.accounts.user_margin.open_order_notional,
         ctx.accounts.engine_config.max_imr_bps as u64,
     )?;
     require!(
@@ -37,20 +45,23 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
     let engine_authority_bump = ctx.bumps.engine_authority;
     let signer_seed_group: &[&[u8]] = &[b"engine-authority", &[engine_authority_bump]];
     let signer_seeds = &[signer_seed_group];
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.collateral_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
         to: ctx.accounts.user_token_account.to_account_info(),
         authority: ctx.accounts.engine_authority.to_account_info(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds,
         ),
         amount,
+        ctx.accounts.usdc_mint.decimals,
     )?;
     ctx.accounts.user_margin.collateral_balance = post_collateral;
+    ctx.accounts.engine_stats.debit_collateral(amount);
 
     Ok(())
 }
@@ -64,6 +75,12 @@ pub struct WithdrawCollateral<'info> {
         bump = engine_config.bump,
     )]
     pub engine_config: Account<'info, EngineConfig>,
+    #[account(
+        mut,
+        seeds = [b"engine-stats"],
+        bump = engine_stats.bump,
+    )]
+    pub engine_stats: Account<'info, EngineStats>,
     #[account(
         mut,
         seeds = [b"user-margin", user.key().as_ref()],
@@ -73,13 +90,15 @@ pub struct WithdrawCollateral<'info> {
     /// CHECK: engine authority PDA.
     #[account(seeds = [b"engine-authority"], bump)]
     pub engine_authority: UncheckedAccount<'info>,
-    #[account(mut, address = engine_config.collateral_vault)]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = engine_config.collateral_vault @ ErrorCode::CollateralVaultMismatch)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         constraint = user_token_account.mint == engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint,
         constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = engine_config.usdc_mint @ ErrorCode::InvalidCollateralMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod helpers;
 pub mod instructions;
 pub mod state;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
 pub use helpers::*;
 pub use instructions::*;
 pub use state::*;
@@ -32,6 +34,10 @@ pub mod order_engine {
         )
     }
 
+    pub fn initialize_engine_stats(ctx: Context<InitializeEngineStats>) -> Result<()> {
+        instructions::initialize_engine_stats::handler(ctx)
+    }
+
     pub fn initialize_market_funding_state(
         ctx: Context<InitializeMarketFundingState>,
         market_id: u64,
@@ -39,6 +45,51 @@ pub mod order_engine {
         instructions::initialize_market_funding_state::handler(ctx, market_id)
     }
 
+    pub fn initialize_market_mirror(
+        ctx: Context<InitializeMarketMirror>,
+        market_id: u64,
+    ) -> Result<()> {
+        instructions::initialize_market_mirror::handler(ctx, market_id)
+    }
+
+    pub fn initialize_market_stats(
+        ctx: Context<InitializeMarketStats>,
+        market_id: u64,
+    ) -> Result<()> {
+        instructions::initialize_market_stats::handler(ctx, market_id)
+    }
+
+    pub fn initialize_recent_fills(
+        ctx: Context<InitializeRecentFills>,
+        market_id: u64,
+    ) -> Result<()> {
+        instructions::initialize_recent_fills::handler(ctx, market_id)
+    }
+
+    pub fn sync_market_mirror(ctx: Context<SyncMarketMirror>, market_id: u64) -> Result<()> {
+        instructions::sync_market_mirror::handler(ctx, market_id)
+    }
+
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>, market_id: u64) -> Result<()> {
+        instructions::reset_circuit_breaker::handler(ctx, market_id)
+    }
+
+    pub fn crank_funding(
+        ctx: Context<CrankFunding>,
+        market_id: u64,
+        oracle_price: u64,
+        oracle_conf: u64,
+        oracle_publish_time: i64,
+    ) -> Result<()> {
+        instructions::crank_funding::handler(
+            ctx,
+            market_id,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )
+    }
+
     pub fn create_margin_account(ctx: Context<CreateMarginAccount>) -> Result<()> {
         instructions::create_margin_account::handler(ctx)
     }
@@ -54,10 +105,26 @@ pub mod order_engine {
         instructions::deposit_collateral::handler(ctx, amount)
     }
 
+    pub fn deposit_sol_collateral(ctx: Context<DepositSolCollateral>, lamports: u64) -> Result<()> {
+        instructions::deposit_sol_collateral::handler(ctx, lamports)
+    }
+
     pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
         instructions::withdraw_collateral::handler(ctx, amount)
     }
 
+    pub fn set_top_up_allowance(
+        ctx: Context<SetTopUpAllowance>,
+        allowance: u64,
+        threshold_bps: u16,
+    ) -> Result<()> {
+        instructions::set_top_up_allowance::handler(ctx, allowance, threshold_bps)
+    }
+
+    pub fn auto_top_up(ctx: Context<AutoTopUp>, market_id: u64) -> Result<()> {
+        instructions::auto_top_up::handler(ctx, market_id)
+    }
+
     pub fn place_order(
         ctx: Context<PlaceOrder>,
         market_id: u64,
@@ -68,6 +135,8 @@ pub mod order_engine {
         price: u64,
         ttl_secs: i64,
         client_order_id: u64,
+        execution_guard: ExecutionGuard,
+        priority_tip: u64,
     ) -> Result<()> {
         instructions::place_order::handler(
             ctx,
@@ -79,6 +148,72 @@ pub mod order_engine {
             price,
             ttl_secs,
             client_order_id,
+            execution_guard,
+            None,
+            priority_tip,
+        )
+    }
+
+    // Stop and take-profit orders: same accounts as `place_order`, but the
+    // keeper can only fill once the verified oracle price crosses the trigger.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_conditional_order(
+        ctx: Context<PlaceOrder>,
+        market_id: u64,
+        side: Side,
+        order_type: OrderType,
+        reduce_only: bool,
+        margin: u64,
+        price: u64,
+        ttl_secs: i64,
+        client_order_id: u64,
+        trigger: OrderTrigger,
+        priority_tip: u64,
+    ) -> Result<()> {
+        instructions::place_order::handler(
+            ctx,
+            market_id,
+            side,
+            order_type,
+            reduce_only,
+            margin,
+            price,
+            ttl_secs,
+            client_order_id,
+            ExecutionGuard {
+                deadline_secs: 0,
+                reference_price: 0,
+                max_adverse_move_bps: 0,
+            },
+            Some(trigger),
+            priority_tip,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_ladder<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceLadder<'info>>,
+        market_id: u64,
+        side: Side,
+        reduce_only: bool,
+        total_margin: u64,
+        price_low: u64,
+        price_high: u64,
+        steps: u8,
+        ttl_secs: i64,
+        client_order_id: u64,
+    ) -> Result<()> {
+        instructions::place_ladder::handler(
+            ctx,
+            market_id,
+            side,
+            reduce_only,
+            total_margin,
+            price_low,
+            price_high,
+            steps,
+            ttl_secs,
+            client_order_id,
         )
     }
 
@@ -106,19 +241,118 @@ pub mod order_engine {
         )
     }
 
+    pub fn execute_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteOrders<'info>>,
+        market_id: u64,
+        fill_prices: Vec<u64>,
+        oracle_price: u64,
+        oracle_conf: u64,
+        oracle_publish_time: i64,
+    ) -> Result<()> {
+        instructions::execute_orders::handler(
+            ctx,
+            market_id,
+            fill_prices,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )
+    }
+
+    pub fn compensate_user(
+        ctx: Context<CompensateUser>,
+        amount: u64,
+        reason_code: u16,
+    ) -> Result<()> {
+        instructions::compensate_user::handler(ctx, amount, reason_code)
+    }
+
+    pub fn get_protocol_overview<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetProtocolOverview<'info>>,
+    ) -> Result<ProtocolOverview> {
+        instructions::get_protocol_overview::handler(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn liquidate(
         ctx: Context<Liquidate>,
         market_id: u64,
         leg: PositionLeg,
         close_qty: u64,
+        oracle_price: u64,
+        oracle_conf: u64,
+        oracle_publish_time: i64,
     ) -> Result<()> {
-        instructions::liquidate::handler(ctx, market_id, leg, close_qty)
+        instructions::liquidate::handler(
+            ctx,
+            market_id,
+            leg,
+            close_qty,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )
+    }
+
+    pub fn sweep_dust_position(
+        ctx: Context<SweepDustPosition>,
+        market_id: u64,
+        oracle_price: u64,
+        oracle_conf: u64,
+        oracle_publish_time: i64,
+    ) -> Result<()> {
+        instructions::sweep_dust_position::handler(
+            ctx,
+            market_id,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )
+    }
+
+    pub fn liquidate_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LiquidateMany<'info>>,
+        market_id: u64,
+        targets: Vec<LiquidationTarget>,
+        oracle_price: u64,
+        oracle_conf: u64,
+        oracle_publish_time: i64,
+    ) -> Result<()> {
+        instructions::liquidate_many::handler(
+            ctx,
+            market_id,
+            targets,
+            oracle_price,
+            oracle_conf,
+            oracle_publish_time,
+        )
+    }
+
+    pub fn socialize_loss<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SocializeLoss<'info>>,
+        market_id: u64,
+    ) -> Result<()> {
+        instructions::socialize_loss::handler(ctx, market_id)
+    }
+
+    pub fn mark_lp_nav<'info>(ctx: Context<'_, '_, 'info, 'info, MarkLpNav<'info>>) -> Result<()> {
+        instructions::mark_lp_nav::handler(ctx)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::{
+        ed25519::parse_ed25519_message,
+        oracle::{
+            decode_switchboard_pull_feed, fallback_message, median_quote, parse_lazer_payload,
+            LAZER_PAYLOAD_MAGIC, LAZER_PROPERTY_CONFIDENCE, LAZER_PROPERTY_EXPONENT,
+            LAZER_PROPERTY_PRICE, LAZER_PROPERTY_PUBLISHER_COUNT, PULL_FEED_DISCRIMINATOR,
+            SB_LAST_UPDATE_TIMESTAMP_OFFSET, SB_RESULT_NUM_SAMPLES_OFFSET,
+            SB_RESULT_STD_DEV_OFFSET, SB_RESULT_VALUE_OFFSET,
+        },
+    };
 
     #[test]
     fn test_mul_bps() {
@@ -131,4 +365,634 @@ mod tests {
         assert_eq!(abs_diff(100, 90), 10);
         assert_eq!(abs_diff(90, 100), 10);
     }
+
+    fn open_order(margin: u64, reserved_collateral: u64, reduce_only: bool) -> Order {
+        let mut order: Order = bytemuck::Zeroable::zeroed();
+        order.margin = margin;
+        order.reserved_collateral = reserved_collateral;
+        order.reduce_only = reduce_only as u8;
+        order
+    }
+
+    #[test]
+    fn test_withdraw_accounts_for_open_orders() {
+        // 1_000 collateral, 2_000 open notional, 10% IMR: 200 reserved by the order.
+        let imr_bps = 1_000;
+        let order = open_order(2_000, 200, false);
+        let mut margin = UserMargin {
+            owner: Pubkey::default(),
+            collateral_balance: 800,
+            next_order_nonce: 1,
+            total_notional: 0,
+            bump: 0,
+            open_order_notional: 2_000,
+            top_up_source: Pubkey::default(),
+            top_up_allowance: 0,
+            top_up_threshold_bps: 0,
+        };
+
+        // Withdrawing all free collateral passes a check that ignores the open order...
+        let post_collateral = 0;
+        assert!(post_collateral >= mul_bps_u64(margin.total_notional, imr_bps).unwrap());
+        // ...but not the pessimistic one.
+        let required =
+            pessimistic_margin_required(margin.total_notional, margin.open_order_notional, imr_bps)
+                .unwrap();
+        assert_eq!(required, 200);
+        assert!(post_collateral < required);
+
+        // Withdrawing 600 leaves enough for the order to fill at IMR in the same slot.
+        margin.collateral_balance = 200;
+        assert!(margin.collateral_balance >= required);
+        release_open_order(&mut margin, &order).unwrap();
+        margin.total_notional += order.margin;
+        assert_eq!(margin.collateral_balance, 400);
+        assert_eq!(margin.open_order_notional, 0);
+        assert!(margin.collateral_balance >= mul_bps_u64(margin.total_notional, imr_bps).unwrap());
+    }
+
+    #[test]
+    fn test_release_reduce_only_order() {
+        let order = open_order(2_000, 0, true);
+        let mut margin = UserMargin {
+            owner: Pubkey::default(),
+            collateral_balance: 100,
+            next_order_nonce: 1,
+            total_notional: 2_000,
+            bump: 0,
+            open_order_notional: 500,
+            top_up_source: Pubkey::default(),
+            top_up_allowance: 0,
+            top_up_threshold_bps: 0,
+        };
+
+        release_open_order(&mut margin, &order).unwrap();
+        assert_eq!(margin.collateral_balance, 100);
+        assert_eq!(margin.open_order_notional, 500);
+    }
+
+    #[test]
+    fn test_circuit_breaker_trips_within_window() {
+        let pricing = market_registry::PricingParams {
+            base_spread_bps: 0,
+            skew_coeff_bps: 0,
+            max_fill_deviation_bps: 0,
+            max_oracle_staleness_sec: 30,
+            max_conf_bps: 0,
+            stale_policy: market_registry::OracleStalePolicy::RejectFills,
+            stale_grace_sec: 0,
+            stale_margin_add_bps: 0,
+            min_order_age_slots: 0,
+            liquidation_ema_band_bps: 0,
+            circuit_breaker_bps: 1_000,
+            circuit_breaker_window_sec: 60,
+            liquidation_max_conf_bps: 0,
+            liquidation_max_staleness_sec: 60,
+            liquidation_twap_band_bps: 0,
+            twap_window_sec: 0,
+        };
+        let mut state: MarketFundingState = bytemuck::Zeroable::zeroed();
+
+        assert!(!observe_oracle_price(&mut state, &pricing, 100, 1_000).unwrap());
+        // A 10% move is tolerated and becomes the new reference.
+        assert!(!observe_oracle_price(&mut state, &pricing, 110, 1_100).unwrap());
+        // Outside the window the reference is simply replaced.
+        assert!(!observe_oracle_price(&mut state, &pricing, 200, 1_500).unwrap());
+        assert!(!state.is_breaker_tripped());
+
+        assert!(observe_oracle_price(&mut state, &pricing, 210, 1_300).unwrap());
+        assert!(state.is_breaker_tripped());
+        assert_eq!(state.last_accepted_price, 1_500);
+    }
+
+    #[test]
+    fn test_oracle_twap_resists_wicks() {
+        let mut pricing = market_registry::PricingParams {
+            base_spread_bps: 0,
+            skew_coeff_bps: 0,
+            max_fill_deviation_bps: 0,
+            max_oracle_staleness_sec: 30,
+            max_conf_bps: 0,
+            stale_policy: market_registry::OracleStalePolicy::RejectFills,
+            stale_grace_sec: 0,
+            stale_margin_add_bps: 0,
+            min_order_age_slots: 0,
+            liquidation_ema_band_bps: 0,
+            circuit_breaker_bps: 0,
+            circuit_breaker_window_sec: 0,
+            liquidation_max_conf_bps: 0,
+            liquidation_max_staleness_sec: 60,
+            liquidation_twap_band_bps: 500,
+            twap_window_sec: 600,
+        };
+        let mut state: MarketFundingState = bytemuck::Zeroable::zeroed();
+
+        // Never cranked: liquidations wait for a TWAP.
+        assert!(validate_liquidation_twap(&pricing, &state, 0, 1_000).is_err());
+
+        accrue_oracle_twap(&mut state, pricing.twap_window_sec, 0, 1_000).unwrap();
+        // A 20% wick a minute later only moves the TWAP by 2%.
+        accrue_oracle_twap(&mut state, pricing.twap_window_sec, 60, 800).unwrap();
+        assert_eq!(state.oracle_twap, 980);
+        assert!(validate_liquidation_twap(&pricing, &state, 60, 800).is_err());
+        assert!(validate_liquidation_twap(&pricing, &state, 60, 950).is_ok());
+
+        // A TWAP older than the window is unusable.
+        assert!(validate_liquidation_twap(&pricing, &state, 700, 980).is_err());
+
+        pricing.liquidation_twap_band_bps = 0;
+        assert!(validate_liquidation_twap(&pricing, &state, 700, 800).is_ok());
+    }
+
+    #[test]
+    fn test_market_stats_rolling_volume() {
+        let mut stats = MarketStats {
+            market_id: 1,
+            cumulative_volume: 0,
+            trade_count: 0,
+            last_fill_price: 0,
+            last_fill_ts: 0,
+            hourly_volume: [0; STATS_BUCKET_COUNT],
+            last_bucket_hour: 0,
+            bump: 0,
+        };
+        let hour = STATS_BUCKET_SECS;
+
+        stats.record_fill(100, 10, 10 * hour);
+        stats.record_fill(50, 11, 10 * hour + 5);
+        stats.record_fill(25, 12, 20 * hour);
+        assert_eq!(stats.rolling_volume(20 * hour), 175);
+        // Ten hours later the first bucket has aged out.
+        assert_eq!(stats.rolling_volume(34 * hour), 25);
+
+        // A fill a day later reuses the ring slot of hour 10 and clears it.
+        stats.record_fill(5, 13, 34 * hour);
+        assert_eq!(stats.rolling_volume(34 * hour), 30);
+        assert_eq!(stats.cumulative_volume, 180);
+        assert_eq!(stats.trade_count, 4);
+        assert_eq!(stats.last_fill_price, 13);
+    }
+
+    #[test]
+    fn test_recent_fills_ring_wraps() {
+        let mut tape: RecentFills = bytemuck::Zeroable::zeroed();
+        for i in 0..(RECENT_FILLS_LEN as u64 + 2) {
+            tape.push(100 + i, 1, Side::Buy, i as i64);
+        }
+        assert_eq!(tape.head, 2);
+        assert_eq!(tape.total_fills, RECENT_FILLS_LEN as u64 + 2);
+        // The two newest fills overwrote the two oldest slots.
+        assert_eq!(tape.fills[1].price, 100 + RECENT_FILLS_LEN as u64 + 1);
+        assert_eq!(tape.fills[2].price, 102);
+    }
+
+    #[test]
+    fn test_socialized_loss_charges_winners_pro_rata() {
+        // 1_000 long and 2_000 short notional open.
+        let mut state: MarketFundingState = bytemuck::Zeroable::zeroed();
+        state.open_interest = 3_000;
+        state.skew = -1_000;
+        let mut margin = UserMargin {
+            owner: Pubkey::default(),
+            collateral_balance: 100,
+            next_order_nonce: 0,
+            total_notional: 500,
+            bump: 0,
+            open_order_notional: 0,
+            top_up_source: Pubkey::default(),
+            top_up_allowance: 0,
+            top_up_threshold_bps: 0,
+        };
+        let mut winner: UserMarketPosition = bytemuck::Zeroable::zeroed();
+        winner.short_qty = 5;
+        winner.short_entry_notional = 500;
+        let mut latecomer: UserMarketPosition = bytemuck::Zeroable::zeroed();
+
+        // A long blows through insurance by 40: shorts hold a quarter of it.
+        assert!(socialize_shortfall(&mut state, PositionLeg::Long, 40).unwrap());
+        assert_eq!(state.socialized_loss_index_long, 0);
+        settle_socialized_loss(&mut latecomer, &mut state, &mut margin).unwrap();
+        assert_eq!(latecomer.socialized_loss_paid, 0);
+        assert_eq!(
+            settle_socialized_loss(&mut winner, &mut state, &mut margin).unwrap(),
+            10
+        );
+        assert_eq!(margin.collateral_balance, 90);
+        assert_eq!(winner.socialized_loss_paid, 10);
+        assert_eq!(state.socialized_loss_collected, 10);
+        // Already settled: nothing more to charge.
+        assert_eq!(
+            settle_socialized_loss(&mut winner, &mut state, &mut margin).unwrap(),
+            0
+        );
+
+        // With no shorts left there is nobody to charge.
+        state.open_interest = 1_000;
+        state.skew = 1_000;
+        assert!(!socialize_shortfall(&mut state, PositionLeg::Long, 40).unwrap());
+    }
+
+    #[test]
+    fn test_quantize_fill_rounds_to_lots() {
+        // 1_000 USDC at 30.000000 is 33.333333 units; lots of 0.01 keep 33.33.
+        let (qty, notional) = quantize_fill(1_000_000_000, 30_000_000, 10_000).unwrap();
+        assert_eq!(qty, 33_330_000);
+        assert_eq!(notional, 999_900_000);
+        // Less than one lot cannot fill.
+        assert!(quantize_fill(100_000, 30_000_000, 10_000).is_err());
+        // No lot size keeps the raw quantity.
+        assert_eq!(
+            quantize_fill(1_000_000_000, 30_000_000, 0).unwrap().0,
+            33_333_333
+        );
+    }
+
+    #[test]
+    fn test_position_leverage_uses_margin_left_by_other_markets() {
+        // 1_000 collateral, 5_000 notional elsewhere at 10% IMR: 500 left here.
+        let margin = UserMargin {
+            owner: Pubkey::default(),
+            collateral_balance: 1_000,
+            next_order_nonce: 0,
+            total_notional: 5_000,
+            bump: 0,
+            open_order_notional: 0,
+            top_up_source: Pubkey::default(),
+            top_up_allowance: 0,
+            top_up_threshold_bps: 0,
+        };
+        // Account-wide 10x would still allow 5_000 more; this market allows 5x of 500.
+        assert!(require_position_leverage(&margin, 0, 2_500, 1_000, 5).is_ok());
+        assert!(require_position_leverage(&margin, 0, 2_501, 1_000, 5).is_err());
+    }
+
+    #[test]
+    fn test_unrealized_trader_pnl_nets_both_sides() {
+        // 10 long entered at 100 and 4 short entered at 120.
+        let mut state: MarketFundingState = bytemuck::Zeroable::zeroed();
+        state.add_open_qty(PositionLeg::Long, 10_000_000).unwrap();
+        state.add_open_qty(PositionLeg::Short, 4_000_000).unwrap();
+        state.open_interest = 1_480_000_000;
+        state.skew = 1_000_000_000 - 480_000_000;
+
+        // At 110 longs are up 100 and shorts up 40.
+        assert_eq!(
+            state.unrealized_trader_pnl(110_000_000).unwrap(),
+            140_000_000
+        );
+        // At 90 longs are down 100 and shorts up 120.
+        assert_eq!(state.unrealized_trader_pnl(90_000_000).unwrap(), 20_000_000);
+
+        state
+            .remove_open_qty(PositionLeg::Long, 10_000_000)
+            .unwrap();
+        assert!(state
+            .remove_open_qty(PositionLeg::Short, 5_000_000)
+            .is_err());
+    }
+
+    #[test]
+    fn test_realized_pnl() {
+        // A long entered at 1_000 of notional and closed for 1_100 gained 100.
+        assert_eq!(realized_pnl(PositionLeg::Long, 1_000, 1_100).unwrap(), 100);
+        assert_eq!(realized_pnl(PositionLeg::Long, 1_000, 900).unwrap(), -100);
+        // A short gains when it buys back for less.
+        assert_eq!(realized_pnl(PositionLeg::Short, 1_000, 900).unwrap(), 100);
+        assert_eq!(
+            realized_pnl(PositionLeg::Short, 1_000, 1_100).unwrap(),
+            -100
+        );
+    }
+
+    #[test]
+    fn test_execution_deadline_guard() {
+        let mut order = open_order(2_000, 200, false);
+        order.side = Side::Buy as u8;
+        order.execution_deadline = 100;
+        order.reference_price = 1_000_000;
+        order.max_adverse_move_bps = 100;
+
+        // Before the deadline the guard does not look at the price.
+        assert!(validate_execution_guard(&order, 100, 2_000_000).is_ok());
+        assert!(validate_execution_guard(&order, 101, 1_010_000).is_ok());
+        assert!(validate_execution_guard(&order, 101, 1_011_000).is_err());
+        assert!(validate_execution_guard(&order, 101, 900_000).is_ok());
+
+        order.side = Side::Sell as u8;
+        assert!(validate_execution_guard(&order, 101, 990_000).is_ok());
+        assert!(validate_execution_guard(&order, 101, 989_000).is_err());
+        assert!(validate_execution_guard(&order, 101, 1_100_000).is_ok());
+
+        // No deadline means no guard.
+        order.execution_deadline = 0;
+        assert!(validate_execution_guard(&order, 101, 1).is_ok());
+    }
+
+    #[test]
+    fn test_require_min_order_age() {
+        let mut order = open_order(2_000, 200, false);
+        order.created_slot = 100;
+        // Disabled at zero: the order may fill in its own slot.
+        assert!(require_min_order_age(&order, 0, 100).is_ok());
+        assert!(require_min_order_age(&order, 3, 102).is_err());
+        assert!(require_min_order_age(&order, 3, 103).is_ok());
+    }
+
+    #[test]
+    fn test_marketable_limit_order_pays_taker() {
+        let mut order = open_order(2_000, 200, false);
+        order.order_type = OrderType::Limit as u8;
+        order.created_slot = 100;
+        // Filled in the slot it was placed: it crossed on arrival.
+        assert!(!earns_maker_fee(&order, 100));
+        // Rested at least one slot before the fill.
+        assert!(earns_maker_fee(&order, 101));
+
+        order.order_type = OrderType::Market as u8;
+        assert!(!earns_maker_fee(&order, 101));
+    }
+
+    fn pull_feed(value: i128, std_dev: i128, num_samples: u8) -> Vec<u8> {
+        let mut data = vec![0u8; SB_RESULT_NUM_SAMPLES_OFFSET + 1];
+        data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+        data[SB_LAST_UPDATE_TIMESTAMP_OFFSET..SB_LAST_UPDATE_TIMESTAMP_OFFSET + 8]
+            .copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[SB_RESULT_VALUE_OFFSET..SB_RESULT_VALUE_OFFSET + 16]
+            .copy_from_slice(&value.to_le_bytes());
+        data[SB_RESULT_STD_DEV_OFFSET..SB_RESULT_STD_DEV_OFFSET + 16]
+            .copy_from_slice(&std_dev.to_le_bytes());
+        data[SB_RESULT_NUM_SAMPLES_OFFSET] = num_samples;
+        data
+    }
+
+    #[test]
+    fn test_decode_switchboard_pull_feed() {
+        // 65_000.5 with 18 decimals and a 0.25 std dev.
+        let value = 65_000_500_000_000_000_000_000i128;
+        let std_dev = 250_000_000_000_000_000i128;
+        assert_eq!(
+            decode_switchboard_pull_feed(&pull_feed(value, std_dev, 3)).unwrap(),
+            (65_000_500_000, 250_000, 1_700_000_000)
+        );
+        // A feed without samples or a negative result is rejected.
+        assert!(decode_switchboard_pull_feed(&pull_feed(value, std_dev, 0)).is_err());
+        assert!(decode_switchboard_pull_feed(&pull_feed(-value, std_dev, 3)).is_err());
+        let mut wrong_account = pull_feed(value, std_dev, 3);
+        wrong_account[0] ^= 1;
+        assert!(decode_switchboard_pull_feed(&wrong_account).is_err());
+    }
+
+    #[test]
+    fn test_median_quote() {
+        // Odd count: the middle price with its own confidence.
+        assert_eq!(
+            median_quote(vec![(103, 3, 50), (100, 1, 40), (250, 9, 45)]).unwrap(),
+            (103, 3, 40)
+        );
+        // Two feeds: their mean and the wider confidence.
+        assert_eq!(
+            median_quote(vec![(101, 2, 40), (100, 5, 41)]).unwrap(),
+            (100, 5, 40)
+        );
+        assert!(median_quote(vec![(100, 1, 40)]).is_err());
+    }
+
+    fn lazer_feed(feed_id: u32, price: i64, exponent: i16, confidence: u64) -> Vec<u8> {
+        let mut feed = feed_id.to_le_bytes().to_vec();
+        feed.push(4);
+        feed.push(LAZER_PROPERTY_PRICE);
+        feed.extend_from_slice(&price.to_le_bytes());
+        feed.push(LAZER_PROPERTY_PUBLISHER_COUNT);
+        feed.extend_from_slice(&7u16.to_le_bytes());
+        feed.push(LAZER_PROPERTY_EXPONENT);
+        feed.extend_from_slice(&exponent.to_le_bytes());
+        feed.push(LAZER_PROPERTY_CONFIDENCE);
+        feed.extend_from_slice(&confidence.to_le_bytes());
+        feed
+    }
+
+    fn lazer_payload(feeds: &[Vec<u8>]) -> Vec<u8> {
+        let mut payload = LAZER_PAYLOAD_MAGIC.to_le_bytes().to_vec();
+        payload.extend_from_slice(&1_700_000_000_250_000u64.to_le_bytes());
+        payload.push(1);
+        payload.push(feeds.len() as u8);
+        for feed in feeds {
+            payload.extend_from_slice(feed);
+        }
+        payload
+    }
+
+    #[test]
+    fn test_parse_lazer_payload() {
+        let payload = lazer_payload(&[
+            lazer_feed(1, 300_000_000_000, -8, 10_000_000),
+            lazer_feed(2, 6_500_050_000_000, -8, 25_000_000),
+        ]);
+        let update = parse_lazer_payload(&payload, 2).unwrap();
+        assert_eq!(update.timestamp_us, 1_700_000_000_250_000);
+        assert_eq!(update.price, 6_500_050_000_000);
+        assert_eq!(update.exponent, -8);
+        assert_eq!(update.confidence, 25_000_000);
+
+        // Unknown feeds and foreign payloads are rejected.
+        assert!(parse_lazer_payload(&payload, 3).is_err());
+        let mut foreign = payload.clone();
+        foreign[0] ^= 1;
+        assert!(parse_lazer_payload(&foreign, 2).is_err());
+        assert!(parse_lazer_payload(&payload[..payload.len() - 1], 2).is_err());
+    }
+
+    // ed25519 program instruction data with one signature whose pubkey,
+    // signature and message all live in the instruction itself.
+    fn ed25519_instruction(signer: &Pubkey, message: &[u8], ix_index: u16) -> Vec<u8> {
+        let pubkey_offset = 16u16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for word in [
+            signature_offset,
+            ix_index,
+            pubkey_offset,
+            ix_index,
+            message_offset,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_ed25519_message() {
+        let signer = Pubkey::new_unique();
+        let message = b"signed lazer payload".to_vec();
+        let data = ed25519_instruction(&signer, &message, u16::MAX);
+        assert_eq!(parse_ed25519_message(&data, &signer).unwrap(), message);
+
+        // Wrong signer, data referenced from another instruction, or a
+        // truncated message are all rejected.
+        assert!(parse_ed25519_message(&data, &Pubkey::new_unique()).is_err());
+        let elsewhere = ed25519_instruction(&signer, &message, 0);
+        assert!(parse_ed25519_message(&elsewhere, &signer).is_err());
+        assert!(parse_ed25519_message(&data[..data.len() - 1], &signer).is_err());
+    }
+
+    #[test]
+    fn test_signed_fallback_message() {
+        let signer = Pubkey::new_unique();
+        let message = fallback_message(7, 65_000_000_000, 25_000, 1_700_000_000);
+        assert_eq!(message.len(), 32);
+        assert_eq!(&message[..8], &7u64.to_le_bytes());
+        assert_eq!(&message[24..], &1_700_000_000i64.to_le_bytes());
+
+        // The keeper's quote must be exactly what the fallback signer signed.
+        let data = ed25519_instruction(&signer, &message, u16::MAX);
+        let signed = parse_ed25519_message(&data, &signer).unwrap();
+        assert_eq!(signed, message);
+        assert_ne!(
+            signed,
+            fallback_message(7, 65_000_000_001, 25_000, 1_700_000_000)
+        );
+        assert_ne!(
+            signed,
+            fallback_message(8, 65_000_000_000, 25_000, 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn test_oracle_circuit_breaker() {
+        let mut pricing = market_registry::PricingParams::deserialize(
+            &mut &vec![0u8; market_registry::PricingParams::INIT_SPACE][..],
+        )
+        .unwrap();
+        pricing.circuit_breaker_bps = 500;
+        pricing.circuit_breaker_window_sec = 60;
+        let mut funding_state: MarketFundingState = bytemuck::Zeroable::zeroed();
+
+        // The first price only sets the reference.
+        assert!(!observe_oracle_price(&mut funding_state, &pricing, 0, 1_000_000).unwrap());
+        assert!(!observe_oracle_price(&mut funding_state, &pricing, 10, 1_040_000).unwrap());
+        assert_eq!(funding_state.last_accepted_price, 1_040_000);
+        // A move past 5% after the window is a new reference, not a trip.
+        assert!(!observe_oracle_price(&mut funding_state, &pricing, 100, 1_200_000).unwrap());
+        // Inside the window it trips once and keeps the last accepted price.
+        assert!(observe_oracle_price(&mut funding_state, &pricing, 110, 1_000_000).unwrap());
+        assert!(funding_state.is_breaker_tripped());
+        assert_eq!(funding_state.last_accepted_price, 1_200_000);
+        assert!(!observe_oracle_price(&mut funding_state, &pricing, 120, 900_000).unwrap());
+    }
+
+    #[test]
+    fn test_validate_fill_batch() {
+        assert!(validate_fill_batch(1, 3).is_ok());
+        assert!(validate_fill_batch(MAX_BATCH_ORDERS, MAX_BATCH_ORDERS * 3).is_ok());
+        assert!(validate_fill_batch(0, 0).is_err());
+        assert!(validate_fill_batch(MAX_BATCH_ORDERS + 1, (MAX_BATCH_ORDERS + 1) * 3).is_err());
+        // Every fill needs its order, margin and position accounts.
+        assert!(validate_fill_batch(2, 5).is_err());
+    }
+
+    #[test]
+    fn test_batch_maker_rebates_share_headroom() {
+        let mut pool =
+            lp_vault::Pool::deserialize(&mut &vec![0u8; lp_vault::Pool::INIT_SPACE][..]).unwrap();
+        pool.cumulative_lp_fees = 1_000;
+        pool.total_maker_rebates = 400;
+        // Fills in one batch draw on the same 600 of headroom.
+        let mut credited = 0;
+        for requested in [250, 250, 250] {
+            credited += clamp_maker_rebate(&pool, credited, requested);
+        }
+        assert_eq!(credited, 600);
+        assert_eq!(clamp_maker_rebate(&pool, credited, 100), 0);
+    }
+
+    #[test]
+    fn test_assert_covers_market_list() {
+        let entry = |market_id| market_registry::MarketListEntry {
+            market_id,
+            status: market_registry::MarketStatus::Active,
+        };
+        let list = market_registry::MarketList {
+            markets: vec![entry(2), entry(0), entry(1)],
+            bump: 255,
+        };
+        assert!(assert_covers_market_list(&[0, 1, 2], &list).is_ok());
+        // Missing, duplicated or out-of-order markets are all rejected.
+        assert!(assert_covers_market_list(&[0, 2], &list).is_err());
+        assert!(assert_covers_market_list(&[0, 1, 1, 2], &list).is_err());
+        assert!(assert_covers_market_list(&[0, 2, 1], &list).is_err());
+    }
+
+    fn market_data(source: market_registry::OracleSource) -> Vec<u8> {
+        let mut market = market_registry::Market::deserialize(
+            &mut &vec![0u8; market_registry::Market::INIT_SPACE][..],
+        )
+        .unwrap();
+        market.oracle_config.source = source;
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_index_price_skips_signed_fallback() {
+        let market_key = Pubkey::new_unique();
+        let sysvar_key = anchor_lang::solana_program::sysvar::instructions::ID;
+        let sysvar_owner = anchor_lang::solana_program::sysvar::ID;
+        let (mut market_lamports, mut sysvar_lamports) = (0u64, 0u64);
+        let mut sysvar_data = Vec::new();
+        let sysvar_info = AccountInfo::new(
+            &sysvar_key,
+            false,
+            false,
+            &mut sysvar_lamports,
+            &mut sysvar_data,
+            &sysvar_owner,
+            false,
+            0,
+        );
+        let price_update = UncheckedAccount::try_from(&sysvar_info);
+
+        // On a Pyth push market the sysvar selects the signed fallback, which
+        // has no EMA to read.
+        let mut data = market_data(market_registry::OracleSource::PythPush);
+        let market_info = AccountInfo::new(
+            &market_key,
+            false,
+            false,
+            &mut market_lamports,
+            &mut data,
+            &market_registry::ID,
+            false,
+            0,
+        );
+        let market = Account::<market_registry::Market>::try_from(&market_info).unwrap();
+        assert_eq!(
+            read_index_price(&market, &price_update, 1_000).unwrap(),
+            None
+        );
+
+        // On a Lazer market the sysvar carries the signed update itself.
+        let mut data = market_data(market_registry::OracleSource::PythLazer);
+        let market_info = AccountInfo::new(
+            &market_key,
+            false,
+            false,
+            &mut market_lamports,
+            &mut data,
+            &market_registry::ID,
+            false,
+            0,
+        );
+        let market = Account::<market_registry::Market>::try_from(&market_info).unwrap();
+        assert_eq!(
+            read_index_price(&market, &price_update, 1_000).unwrap(),
+            Some(1_000)
+        );
+    }
 }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+// Engine-wide counters for dashboards and monitoring. Updates saturate rather
+// than fail so a counter can never block a user instruction; activity from
+// before the account existed is not included.
+#[account]
+#[derive(InitSpace)]
+pub struct EngineStats {
+    pub total_users: u64,
+    // User collateral held by the collateral vault: deposits and compensation
+    // in, withdrawals, fees and liquidation penalties out.
+    pub total_collateral: u64,
+    pub total_volume: u128,
+    pub total_fees: u128,
+    pub open_orders: u64,
+    pub bump: u8,
+}
+
+impl EngineStats {
+    pub fn record_user(&mut self) {
+        self.total_users = self.total_users.saturating_add(1);
+    }
+
+    pub fn credit_collateral(&mut self, amount: u64) {
+        self.total_collateral = self.total_collateral.saturating_add(amount);
+    }
+
+    pub fn debit_collateral(&mut self, amount: u64) {
+        self.total_collateral = self.total_collateral.saturating_sub(amount);
+    }
+
+    pub fn record_fill(&mut self, notional: u64, fee: u64) {
+        self.total_volume = self.total_volume.saturating_add(notional as u128);
+        self.total_fees = self.total_fees.saturating_add(fee as u128);
+        self.debit_collateral(fee);
+    }
+
+    pub fn orders_opened(&mut self, count: u64) {
+        self.open_orders = self.open_orders.saturating_add(count);
+    }
+
+    pub fn order_closed(&mut self) {
+        self.open_orders = self.open_orders.saturating_sub(1);
+    }
+}
@@ -1,13 +1,83 @@
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+use crate::{constants::PRICE_SCALE, error::ErrorCode, state::PositionLeg};
+
+#[account(zero_copy)]
 pub struct MarketFundingState {
-    pub market_id: u64,
     pub funding_index: i128,
+    pub skew: i128,
+    pub market_id: u64,
     pub last_update_ts: i64,
     pub open_interest: u64,
-    pub skew: i128,
-    pub halted: bool,
+    // Price funding premiums are valued at: the Pyth EMA, or spot for sources
+    // without one. Zero until the first oracle read.
+    pub index_price: u64,
+    // Last oracle price accepted for execution, used by the circuit breaker.
+    pub last_accepted_price: u64,
+    pub last_accepted_price_ts: i64,
+    // Short time-weighted oracle average advanced by `crank_funding`; the
+    // liquidation band is checked against it.
+    pub oracle_twap: u64,
+    pub oracle_twap_ts: i64,
+    // Cumulative shortfall charged per unit of entry notional on each side
+    // (SOCIALIZED_LOSS_SCALE); advanced when bad debt exceeds insurance.
+    pub socialized_loss_index_long: u128,
+    pub socialized_loss_index_short: u128,
+    // Haircuts already taken from margins but not yet swept to insurance.
+    pub socialized_loss_collected: u64,
+    // Open qty per side, so the LP vault can be marked against trader PnL.
+    pub long_open_qty: u64,
+    pub short_open_qty: u64,
+    pub halted: u8,
     pub bump: u8,
+    // Set when the circuit breaker trips; only reduce-only fills until reset.
+    pub breaker_tripped: u8,
+    pub _padding: [u8; 5],
+}
+
+impl MarketFundingState {
+    pub fn is_halted(&self) -> bool {
+        self.halted != 0
+    }
+
+    pub fn is_breaker_tripped(&self) -> bool {
+        self.breaker_tripped != 0
+    }
+
+    pub fn add_open_qty(&mut self, leg: PositionLeg, qty: u64) -> Result<()> {
+        let open_qty = match leg {
+            PositionLeg::Long => &mut self.long_open_qty,
+            PositionLeg::Short => &mut self.short_open_qty,
+        };
+        *open_qty = open_qty
+            .checked_add(qty)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    pub fn remove_open_qty(&mut self, leg: PositionLeg, qty: u64) -> Result<()> {
+        let open_qty = match leg {
+            PositionLeg::Long => &mut self.long_open_qty,
+            PositionLeg::Short => &mut self.short_open_qty,
+        };
+        *open_qty = open_qty
+            .checked_sub(qty)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(())
+    }
+
+    // Net PnL of every open position marked at `mark_price`; positive means
+    // traders are up and the pool owes it. Longs are worth
+    // `long_qty * mark - long_entry` and shorts `short_entry - short_qty * mark`,
+    // which nets to `(long_qty - short_qty) * mark - skew`.
+    pub fn unrealized_trader_pnl(&self, mark_price: u64) -> Result<i128> {
+        let net_qty = self.long_open_qty as i128 - self.short_open_qty as i128;
+        let net_value = net_qty
+            .checked_mul(mark_price as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            / PRICE_SCALE as i128;
+        net_value
+            .checked_sub(self.skew)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))
+    }
 }
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use market_registry::{Market, MarketStatus};
+
+// Engine-side copy of the registry fields order placement needs, so placing
+// an order does not have to load (and read-lock) the registry market account.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketMirror {
+    pub market_id: u64,
+    pub params_version: u64,
+    pub status: MarketStatus,
+    pub imr_bps: u16,
+    pub taker_fee_bps: u16,
+    pub max_ttl_secs: i64,
+    pub price_tick_size: u64,
+    pub last_synced_at: i64,
+    pub bump: u8,
+}
+
+impl MarketMirror {
+    pub fn sync_from(&mut self, market: &Market, now: i64) {
+        self.market_id = market.market_id;
+        self.params_version = market.params_version;
+        self.status = market.status;
+        // Reserve against the higher end of any ramp in flight.
+        self.imr_bps = market.imr_bps_at(now).max(market.risk_params.imr_bps);
+        self.taker_fee_bps = market
+            .taker_fee_bps_at(now)
+            .max(market.fee_params.taker_fee_bps);
+        self.max_ttl_secs = market.risk_params.max_ttl_secs;
+        self.price_tick_size = market.risk_params.price_tick_size;
+        self.last_synced_at = now;
+    }
+
+    pub fn is_on_tick(&self, price: u64) -> bool {
+        self.price_tick_size == 0 || price.is_multiple_of(self.price_tick_size)
+    }
+
+    pub fn max_ttl_secs(&self, engine_max_ttl_secs: i64) -> i64 {
+        if self.max_ttl_secs > 0 {
+            self.max_ttl_secs.min(engine_max_ttl_secs)
+        } else {
+            engine_max_ttl_secs
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+pub const STATS_BUCKET_SECS: i64 = 3_600;
+pub const STATS_BUCKET_COUNT: usize = 24;
+
+// Headline numbers for frontends. `hourly_volume` is a ring of hour buckets
+// indexed by `hour % 24`; buckets are cleared as the clock moves past them.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketStats {
+    pub market_id: u64,
+    pub cumulative_volume: u128,
+    pub trade_count: u64,
+    pub last_fill_price: u64,
+    pub last_fill_ts: i64,
+    pub hourly_volume: [u64; STATS_BUCKET_COUNT],
+    pub last_bucket_hour: i64,
+    pub bump: u8,
+}
+
+impl MarketStats {
+    pub fn record_fill(&mut self, notional: u64, fill_price: u64, now: i64) {
+        let hour = now.div_euclid(STATS_BUCKET_SECS);
+        let stale = (hour - self.last_bucket_hour).clamp(0, STATS_BUCKET_COUNT as i64);
+        for offset in 1..=stale {
+            let slot = (self.last_bucket_hour + offset).rem_euclid(STATS_BUCKET_COUNT as i64);
+            self.hourly_volume[slot as usize] = 0;
+        }
+        if hour > self.last_bucket_hour {
+            self.last_bucket_hour = hour;
+        }
+
+        let slot = hour.rem_euclid(STATS_BUCKET_COUNT as i64) as usize;
+        self.hourly_volume[slot] = self.hourly_volume[slot].saturating_add(notional);
+        self.cumulative_volume = self.cumulative_volume.saturating_add(notional as u128);
+        self.trade_count = self.trade_count.saturating_add(1);
+        self.last_fill_price = fill_price;
+        self.last_fill_ts = now;
+    }
+
+    // Volume over the trailing 24 buckets as of `now`, ignoring buckets the
+    // clock has already moved past.
+    pub fn rolling_volume(&self, now: i64) -> u128 {
+        let hour = now.div_euclid(STATS_BUCKET_SECS);
+        (0..STATS_BUCKET_COUNT as i64)
+            .map(|age| hour - age)
+            .filter(|bucket_hour| *bucket_hour <= self.last_bucket_hour)
+            .map(|bucket_hour| {
+                self.hourly_volume[bucket_hour.rem_euclid(STATS_BUCKET_COUNT as i64) as usize]
+                    as u128
+            })
+            .sum()
+    }
+}
@@ -1,13 +1,23 @@
 pub mod engine_config;
+pub mod engine_stats;
 pub mod market_funding_state;
+pub mod market_mirror;
+pub mod market_stats;
 pub mod order;
 pub mod position_leg;
+pub mod protocol_overview;
+pub mod recent_fills;
 pub mod user_margin;
 pub mod user_market_position;
 
 pub use engine_config::*;
+pub use engine_stats::*;
 pub use market_funding_state::*;
+pub use market_mirror::*;
+pub use market_stats::*;
 pub use order::*;
 pub use position_leg::*;
+pub use protocol_overview::*;
+pub use recent_fills::*;
 pub use user_margin::*;
 pub use user_market_position::*;
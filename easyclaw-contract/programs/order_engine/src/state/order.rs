@@ -1,22 +1,120 @@
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+// Zero-copy: enum and bool fields are stored as `u8` and read back through the
+// accessors below. Field order keeps the `repr(C)` layout free of padding.
+#[account(zero_copy)]
 pub struct Order {
     pub id: u64,
     pub user_margin: Pubkey,
     pub user: Pubkey,
     pub market_id: u64,
-    pub side: Side,
-    pub order_type: OrderType,
-    pub reduce_only: bool,
     pub margin: u64,
+    pub reserved_collateral: u64,
     pub price: u64,
     pub created_at: i64,
     pub expires_at: i64,
     pub client_order_id: u64,
-    pub status: OrderStatus,
+    pub created_slot: u64,
+    pub execution_deadline: i64,
+    pub reference_price: u64,
+    // Conditional orders only fill once the verified oracle price has crossed
+    // `trigger_price` in the `trigger_condition` direction (0 = unconditional).
+    pub trigger_price: u64,
+    // Paid to the executing keeper on fill; reserved with the order and
+    // refunded if it is cancelled or expires.
+    pub priority_tip: u64,
+    pub max_adverse_move_bps: u16,
+    pub side: u8,
+    pub order_type: u8,
+    pub reduce_only: u8,
+    pub status: u8,
     pub bump: u8,
+    pub trigger_condition: u8,
+}
+
+impl Order {
+    pub fn side(&self) -> Side {
+        match self.side {
+            0 => Side::Buy,
+            _ => Side::Sell,
+        }
+    }
+
+    pub fn order_type(&self) -> OrderType {
+        match self.order_type {
+            0 => OrderType::Market,
+            _ => OrderType::Limit,
+        }
+    }
+
+    pub fn is_reduce_only(&self) -> bool {
+        self.reduce_only != 0
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        match self.status {
+            0 => OrderStatus::Open,
+            1 => OrderStatus::Executed,
+            2 => OrderStatus::Cancelled,
+            _ => OrderStatus::Expired,
+        }
+    }
+
+    pub fn set_status(&mut self, status: OrderStatus) {
+        self.status = status as u8;
+    }
+
+    pub fn trigger(&self) -> Option<OrderTrigger> {
+        let condition = match self.trigger_condition {
+            0 => return None,
+            1 => TriggerCondition::Above,
+            _ => TriggerCondition::Below,
+        };
+        Some(OrderTrigger {
+            condition,
+            trigger_price: self.trigger_price,
+        })
+    }
+
+    pub fn set_trigger(&mut self, trigger: OrderTrigger) {
+        self.trigger_condition = match trigger.condition {
+            TriggerCondition::Above => 1,
+            TriggerCondition::Below => 2,
+        };
+        self.trigger_price = trigger.trigger_price;
+    }
+}
+
+// Past `deadline_secs` after placement the order only fills if the oracle has not
+// moved against the user by more than `max_adverse_move_bps` from `reference_price`.
+// A zero `deadline_secs` disables the guard.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ExecutionGuard {
+    pub deadline_secs: i64,
+    pub reference_price: u64,
+    pub max_adverse_move_bps: u16,
+}
+
+// Stop/take-profit trigger checked against the oracle price at execution.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct OrderTrigger {
+    pub condition: TriggerCondition,
+    pub trigger_price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum TriggerCondition {
+    Above,
+    Below,
+}
+
+impl OrderTrigger {
+    pub fn is_met(&self, oracle_price: u64) -> bool {
+        match self.condition {
+            TriggerCondition::Above => oracle_price >= self.trigger_price,
+            TriggerCondition::Below => oracle_price <= self.trigger_price,
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
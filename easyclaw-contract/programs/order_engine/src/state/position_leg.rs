@@ -5,3 +5,9 @@ pub enum PositionLeg {
     Long,
     Short,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LiquidationTarget {
+    pub leg: PositionLeg,
+    pub close_qty: u64,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProtocolOverview {
+    pub pool_tvl: u64,
+    pub insurance_balance: u64,
+    pub protocol_fee_balance: u64,
+    pub total_collateral: u64,
+    pub total_open_interest: u64,
+    pub market_count: u32,
+    pub total_trading_fees: u64,
+    pub fees_24h: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Side;
+
+pub const RECENT_FILLS_LEN: usize = 64;
+
+#[zero_copy]
+pub struct FillRecord {
+    pub price: u64,
+    pub qty: u64,
+    pub timestamp: i64,
+    pub side: u8,
+    pub _padding: [u8; 7],
+}
+
+// Trade tape for a market: `fills` is a ring written at `head`, so the newest
+// entry sits at `head - 1` and at most `RECENT_FILLS_LEN` fills are kept.
+#[account(zero_copy)]
+pub struct RecentFills {
+    pub market_id: u64,
+    pub head: u64,
+    pub total_fills: u64,
+    pub fills: [FillRecord; RECENT_FILLS_LEN],
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl RecentFills {
+    pub fn push(&mut self, price: u64, qty: u64, side: Side, timestamp: i64) {
+        let slot = (self.head as usize) % RECENT_FILLS_LEN;
+        self.fills[slot] = FillRecord {
+            price,
+            qty,
+            timestamp,
+            side: side as u8,
+            _padding: [0; 7],
+        };
+        self.head = ((slot + 1) % RECENT_FILLS_LEN) as u64;
+        self.total_fills = self.total_fills.saturating_add(1);
+    }
+}
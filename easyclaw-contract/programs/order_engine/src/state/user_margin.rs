@@ -8,4 +8,12 @@ pub struct UserMargin {
     pub next_order_nonce: u64,
     pub total_notional: u64,
     pub bump: u8,
+    // Notional of open, not reduce-only orders; withdrawals keep margin for it.
+    pub open_order_notional: u64,
+    // Standing top-up authorization: the engine authority is an SPL delegate
+    // of `top_up_source` and keepers may pull up to `top_up_allowance` once
+    // collateral falls below `top_up_threshold_bps` of the MMR requirement.
+    pub top_up_source: Pubkey,
+    pub top_up_allowance: u64,
+    pub top_up_threshold_bps: u16,
 }
@@ -1,15 +1,25 @@
 use anchor_lang::prelude::*;
 
-#[account]
-#[derive(InitSpace)]
+// 128-bit fields lead so the zero-copy layout has no implicit padding.
+#[account(zero_copy)]
 pub struct UserMarketPosition {
-    pub user_margin: Pubkey,
-    pub market_id: u64,
-    pub long_qty: u64,
     pub long_entry_notional: u128,
-    pub short_qty: u64,
     pub short_entry_notional: u128,
     pub last_funding_index_long: i128,
     pub last_funding_index_short: i128,
+    pub last_socialized_loss_index_long: u128,
+    pub last_socialized_loss_index_short: u128,
+    pub user_margin: Pubkey,
+    pub market_id: u64,
+    pub long_qty: u64,
+    pub short_qty: u64,
+    // Derived from entry notional / qty; zero for an empty leg.
+    pub long_avg_entry_price: u64,
+    pub short_avg_entry_price: u64,
+    pub last_fill_price: u64,
+    pub last_update_ts: i64,
+    // Total haircut charged to this position by loss socialization.
+    pub socialized_loss_paid: u64,
     pub bump: u8,
+    pub _padding: [u8; 15],
 }
@@ -219,7 +219,8 @@ async function callRpc(
   methodName: string,
   args: any[],
   accounts: Record<string, anchor.web3.PublicKey>,
-  label: string
+  label: string,
+  remainingAccounts: anchor.web3.AccountMeta[] = []
 ): Promise<void> {
   const toSnake = (value: string) =>
     value.replace(/[A-Z]/g, (match) => `_${match.toLowerCase()}`);
@@ -244,7 +245,10 @@ async function callRpc(
     throw new Error(`Method not found: ${methodName}`);
   }
 
-  const sig = await method(...args).accounts(accountAliases).rpc();
+  const sig = await method(...args)
+    .accounts(accountAliases)
+    .remainingAccounts(remainingAccounts)
+    .rpc();
   console.log(`[ok] ${label}: ${sig}`);
 }
 
@@ -258,6 +262,16 @@ function deriveMarketPda(
   )[0];
 }
 
+function deriveParamHistoryPda(
+  marketRegistryProgramId: anchor.web3.PublicKey,
+  marketId: number | bigint
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("param-history"), u64Seed(marketId)],
+    marketRegistryProgramId
+  )[0];
+}
+
 function deriveFundingPda(
   orderEngineProgramId: anchor.web3.PublicKey,
   marketId: number | bigint
@@ -268,6 +282,48 @@ function deriveFundingPda(
   )[0];
 }
 
+function deriveMarketStatsPda(
+  orderEngineProgramId: anchor.web3.PublicKey,
+  marketId: number | bigint
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market-stats"), u64Seed(marketId)],
+    orderEngineProgramId
+  )[0];
+}
+
+function deriveMarketMirrorPda(
+  orderEngineProgramId: anchor.web3.PublicKey,
+  marketId: number | bigint
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market-mirror"), u64Seed(marketId)],
+    orderEngineProgramId
+  )[0];
+}
+
+function deriveMarketVaultStatsPda(
+  lpVaultProgramId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  marketId: number | bigint
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market-stats"), pool.toBuffer(), u64Seed(marketId)],
+    lpVaultProgramId
+  )[0];
+}
+
+function deriveMarketInsurancePda(
+  lpVaultProgramId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  marketId: number | bigint
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market-insurance"), pool.toBuffer(), u64Seed(marketId)],
+    lpVaultProgramId
+  )[0];
+}
+
 async function main() {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -312,9 +368,15 @@ async function main() {
     throw new Error("GLOBAL_*_BPS sum must be 10000");
   }
   const pauseFlags = getEnvBoolean("GLOBAL_PAUSE", false);
+  // Requires a market_registry built with the `devnet` feature.
+  const allowKeeperFallback = getEnvBoolean("ALLOW_KEEPER_FALLBACK_ORACLE", false);
+  // Key whose ed25519 signature the engine requires on fallback prices.
+  const fallbackSigner = allowKeeperFallback
+    ? getEnvPubkey("FALLBACK_ORACLE_SIGNER") ?? wallet
+    : anchor.web3.PublicKey.default;
 
   const cooldownSecs = getEnvNumber("LP_COOLDOWN_SECS", 86_400);
-  const minLiquidityBufferBps = getEnvNumber("LP_MIN_BUFFER_BPS", 100);
+  const liquidityBufferOiBps = getEnvNumber("LP_BUFFER_OI_BPS", 2_000);
 
   const maxTtlSecs = getEnvNumber("ENGINE_MAX_TTL_SECS", 300);
   const liquidationPenaltyBps = getEnvNumber(
@@ -353,6 +415,14 @@ async function main() {
     [Buffer.from("keeper-set")],
     marketRegistryProgramId
   )[0];
+  const marketList = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market-list")],
+    marketRegistryProgramId
+  )[0];
+  const keeperLiveness = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper-liveness")],
+    marketRegistryProgramId
+  )[0];
 
   const engineAuthority = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("engine-authority")],
@@ -367,8 +437,11 @@ async function main() {
     orderEngineProgramId
   )[0];
 
+  const poolIndex = getEnvNumber("LP_POOL_INDEX", 0);
+  const poolIndexSeed = Buffer.alloc(2);
+  poolIndexSeed.writeUInt16LE(poolIndex);
   const pool = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("pool"), usdcMint.toBuffer()],
+    [Buffer.from("pool"), usdcMint.toBuffer(), poolIndexSeed],
     lpVaultProgramId
   )[0];
   const liquidityAuth = anchor.web3.PublicKey.findProgramAddressSync(
@@ -423,26 +496,92 @@ async function main() {
     console.log("[skip] market_registry.initialize_global (already initialized)");
   }
 
+  if (!(await accountExists(connection, marketList))) {
+    // Markets created before the list existed are backfilled.
+    const existingMarkets: anchor.web3.AccountMeta[] = [];
+    for (const market of bootstrapMarkets) {
+      const marketPda = deriveMarketPda(marketRegistryProgramId, market.marketId);
+      if (await accountExists(connection, marketPda)) {
+        existingMarkets.push({ pubkey: marketPda, isSigner: false, isWritable: false });
+      }
+    }
+    await callRpc(
+      marketRegistryProgram,
+      "initializeMarketList",
+      [],
+      {
+        authority: wallet,
+        globalConfig,
+        keeperSet,
+        marketList,
+        systemProgram: anchor.web3.SystemProgram.programId
+      },
+      "market_registry.initialize_market_list",
+      existingMarkets
+    );
+  } else {
+    console.log("[skip] market_registry.initialize_market_list (already initialized)");
+  }
+
+  if (!(await accountExists(connection, keeperLiveness))) {
+    await callRpc(
+      marketRegistryProgram,
+      "initializeKeeperLiveness",
+      [],
+      {
+        authority: wallet,
+        globalConfig,
+        keeperSet,
+        keeperLiveness,
+        systemProgram: anchor.web3.SystemProgram.programId
+      },
+      "market_registry.initialize_keeper_liveness"
+    );
+  } else {
+    console.log("[skip] market_registry.initialize_keeper_liveness (already initialized)");
+  }
+
   for (const keeper of keepers) {
+    const keeperAuth = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("keeper-auth"), keeper.toBuffer()],
+      marketRegistryProgramId
+    )[0];
+    const keeperMetadata = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("keeper-meta"), keeper.toBuffer()],
+      marketRegistryProgramId
+    )[0];
     try {
       await callRpc(
         marketRegistryProgram,
         "addKeeper",
-        [keeper],
+        [
+          keeper,
+          {
+            operatorName: process.env.KEEPER_OPERATOR_NAME ?? "bootstrap",
+            contactUri: process.env.KEEPER_CONTACT_URI ?? "",
+            payoutAddress: keeper
+          },
+          {
+            execute: true,
+            liquidate: true,
+            cancelByExecutor: true,
+            crankFunding: true
+          }
+        ],
         {
           authority: wallet,
           globalConfig,
-          keeperSet
+          keeperSet,
+          keeperAuth,
+          keeperMetadata,
+          systemProgram: anchor.web3.SystemProgram.programId
         },
         `market_registry.add_keeper(${keeper.toBase58()})`
       );
     } catch (error) {
       const message =
         error instanceof Error ? error.message : JSON.stringify(error);
-      if (
-        message.includes("KeeperAlreadyExists") ||
-        message.includes("already in use")
-      ) {
+      if (message.includes("already in use")) {
         console.log(`[skip] keeper exists: ${keeper.toBase58()}`);
       } else {
         throw error;
@@ -456,19 +595,26 @@ async function main() {
       invalidAsNull: true
     }) ?? wallet;
   for (const market of bootstrapMarkets) {
-    const marketId = toBn(market.marketId);
     const marketPda = deriveMarketPda(marketRegistryProgramId, market.marketId);
+    const paramHistory = deriveParamHistoryPda(marketRegistryProgramId, market.marketId);
     const envKey = `PYTH_FEED_${market.symbol.replace(/-/g, "_").toUpperCase()}`;
     const pythFeed =
       getEnvPubkey(envKey, { allowHex32: true, invalidAsNull: true }) ??
       pythFeedFallback;
 
     if (!(await accountExists(connection, marketPda))) {
+      // The registry assigns ids from its counter, so markets must be created
+      // in id order.
+      const global = await marketRegistryProgram.account.globalConfig.fetch(globalConfig);
+      if (global.nextMarketId.toString() !== market.marketId.toString()) {
+        throw new Error(
+          `Registry assigns market id ${global.nextMarketId.toString()} next, not ${market.marketId} (${market.symbol})`
+        );
+      }
       await callRpc(
         marketRegistryProgram,
         "createMarket",
         [
-          marketId,
           market.symbol,
           pythFeed,
           {
@@ -477,14 +623,29 @@ async function main() {
             mmrBps: market.risk.mmrBps,
             oiCap: toBn(market.risk.oiCap),
             skewCap: toBn(market.risk.skewCap),
-            maxTradeNotional: toBn(market.risk.maxTradeNotional)
+            maxTradeNotional: toBn(market.risk.maxTradeNotional),
+            dustNotional: toBn(market.risk.dustNotional),
+            maxTtlSecs: toBn(market.risk.maxTtlSecs),
+            qtyLotSize: toBn(market.risk.qtyLotSize),
+            priceTickSize: toBn(market.risk.priceTickSize)
           },
           {
             baseSpreadBps: market.pricing.baseSpreadBps,
             skewCoeffBps: market.pricing.skewCoeffBps,
             maxFillDeviationBps: market.pricing.maxFillDeviationBps,
             maxOracleStalenessSec: toBn(market.pricing.maxOracleStalenessSec),
-            maxConfBps: market.pricing.maxConfBps
+            maxConfBps: market.pricing.maxConfBps,
+            stalePolicy: { [market.pricing.stalePolicy]: {} },
+            staleGraceSec: toBn(market.pricing.staleGraceSec),
+            staleMarginAddBps: market.pricing.staleMarginAddBps,
+            minOrderAgeSlots: toBn(market.pricing.minOrderAgeSlots),
+            liquidationEmaBandBps: market.pricing.liquidationEmaBandBps,
+            circuitBreakerBps: market.pricing.circuitBreakerBps,
+            circuitBreakerWindowSec: toBn(market.pricing.circuitBreakerWindowSec),
+            liquidationMaxConfBps: market.pricing.liquidationMaxConfBps,
+            liquidationMaxStalenessSec: toBn(market.pricing.liquidationMaxStalenessSec),
+            liquidationTwapBandBps: market.pricing.liquidationTwapBandBps,
+            twapWindowSec: toBn(market.pricing.twapWindowSec)
           },
           {
             intervalSec: toBn(BALANCED_COMMON.funding.intervalSec),
@@ -503,12 +664,50 @@ async function main() {
           globalConfig,
           keeperSet,
           market: marketPda,
+          paramHistory,
+          marketList,
           systemProgram: anchor.web3.SystemProgram.programId
         },
         `market_registry.create_market(${market.symbol})`
       );
     } else {
       console.log(`[skip] market exists: ${market.symbol}`);
+      if (!(await accountExists(connection, paramHistory))) {
+        await callRpc(
+          marketRegistryProgram,
+          "initializeParamHistory",
+          [],
+          {
+            authority: wallet,
+            globalConfig,
+            keeperSet,
+            market: marketPda,
+            paramHistory,
+            systemProgram: anchor.web3.SystemProgram.programId
+          },
+          `market_registry.initialize_param_history(${market.symbol})`
+        );
+      }
+    }
+
+    const marketAccount = await marketRegistryProgram.account.market.fetch(marketPda);
+    if (
+      marketAccount.oracleConfig.allowKeeperFallback !== allowKeeperFallback ||
+      !marketAccount.oracleConfig.fallbackSigner.equals(fallbackSigner)
+    ) {
+      await callRpc(
+        marketRegistryProgram,
+        "setMarketOracle",
+        [{ ...marketAccount.oracleConfig, allowKeeperFallback, fallbackSigner }],
+        {
+          authority: wallet,
+          globalConfig,
+          keeperSet,
+          market: marketPda,
+          paramHistory
+        },
+        `market_registry.set_market_oracle(${market.symbol})`
+      );
     }
   }
 
@@ -516,7 +715,7 @@ async function main() {
     await callRpc(
       lpVaultProgram,
       "initializePool",
-      [toBn(cooldownSecs), minLiquidityBufferBps],
+      [toBn(cooldownSecs), liquidityBufferOiBps, poolIndex],
       {
         admin: wallet,
         usdcMint,
@@ -566,6 +765,27 @@ async function main() {
     console.log("[skip] order_engine.initialize_engine (already initialized)");
   }
 
+  const engineStats = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("engine-stats")],
+    orderEngineProgramId
+  )[0];
+  if (!(await accountExists(connection, engineStats))) {
+    await callRpc(
+      orderEngineProgram,
+      "initializeEngineStats",
+      [],
+      {
+        admin: wallet,
+        engineConfig,
+        engineStats,
+        systemProgram: anchor.web3.SystemProgram.programId
+      },
+      "order_engine.initialize_engine_stats"
+    );
+  } else {
+    console.log("[skip] engine stats exists");
+  }
+
   for (const market of bootstrapMarkets) {
     const marketPda = deriveMarketPda(marketRegistryProgramId, market.marketId);
     const marketFundingState = deriveFundingPda(
@@ -590,6 +810,119 @@ async function main() {
     } else {
       console.log(`[skip] funding exists: ${market.symbol}`);
     }
+
+    const marketMirror = deriveMarketMirrorPda(
+      orderEngineProgramId,
+      market.marketId
+    );
+    if (!(await accountExists(connection, marketMirror))) {
+      await callRpc(
+        orderEngineProgram,
+        "initializeMarketMirror",
+        [toBn(market.marketId)],
+        {
+          admin: wallet,
+          engineConfig,
+          marketRegistryProgram: marketRegistryProgramId,
+          market: marketPda,
+          marketMirror,
+          systemProgram: anchor.web3.SystemProgram.programId
+        },
+        `order_engine.initialize_market_mirror(${market.symbol})`
+      );
+    } else {
+      console.log(`[skip] market mirror exists: ${market.symbol}`);
+    }
+
+    const marketStats = deriveMarketStatsPda(
+      orderEngineProgramId,
+      market.marketId
+    );
+    if (!(await accountExists(connection, marketStats))) {
+      await callRpc(
+        orderEngineProgram,
+        "initializeMarketStats",
+        [toBn(market.marketId)],
+        {
+          admin: wallet,
+          engineConfig,
+          marketRegistryProgram: marketRegistryProgramId,
+          market: marketPda,
+          marketStats,
+          systemProgram: anchor.web3.SystemProgram.programId
+        },
+        `order_engine.initialize_market_stats(${market.symbol})`
+      );
+    } else {
+      console.log(`[skip] market stats exists: ${market.symbol}`);
+    }
+
+    const recentFills = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recent-fills"), u64Seed(market.marketId)],
+      orderEngineProgramId
+    )[0];
+    if (!(await accountExists(connection, recentFills))) {
+      await callRpc(
+        orderEngineProgram,
+        "initializeRecentFills",
+        [toBn(market.marketId)],
+        {
+          admin: wallet,
+          engineConfig,
+          marketRegistryProgram: marketRegistryProgramId,
+          market: marketPda,
+          recentFills,
+          systemProgram: anchor.web3.SystemProgram.programId
+        },
+        `order_engine.initialize_recent_fills(${market.symbol})`
+      );
+    } else {
+      console.log(`[skip] recent fills exists: ${market.symbol}`);
+    }
+
+    const marketInsurance = deriveMarketInsurancePda(
+      lpVaultProgramId,
+      pool,
+      market.marketId
+    );
+    if (!(await accountExists(connection, marketInsurance))) {
+      await callRpc(
+        lpVaultProgram,
+        "initMarketInsurance",
+        [toBn(market.marketId)],
+        {
+          admin: wallet,
+          pool,
+          marketInsurance,
+          systemProgram: anchor.web3.SystemProgram.programId
+        },
+        `lp_vault.init_market_insurance(${market.symbol})`
+      );
+    } else {
+      console.log(`[skip] market insurance exists: ${market.symbol}`);
+    }
+
+    const marketStats = deriveMarketVaultStatsPda(
+      lpVaultProgramId,
+      pool,
+      market.marketId
+    );
+    if (!(await accountExists(connection, marketStats))) {
+      await callRpc(
+        lpVaultProgram,
+        "initMarketVaultStats",
+        [toBn(market.marketId)],
+        {
+          admin: wallet,
+          pool,
+          marketStats,
+          systemProgram: anchor.web3.SystemProgram.programId
+        },
+        `lp_vault.init_market_vault_stats(${market.symbol})`
+      );
+    } else {
+      console.log(`[skip] market vault stats exists: ${market.symbol}`);
+    }
   }
 
   for (const keeper of keepers) {
@@ -5,6 +5,11 @@ export type RiskParams = {
   oiCap: bigint;
   skewCap: bigint;
   maxTradeNotional: bigint;
+  dustNotional: bigint;
+  // 0 falls back to the engine-wide max TTL.
+  maxTtlSecs: number;
+  qtyLotSize: bigint;
+  priceTickSize: bigint;
 };
 
 export type PricingParams = {
@@ -13,6 +18,17 @@ export type PricingParams = {
   maxFillDeviationBps: number;
   maxOracleStalenessSec: number;
   maxConfBps: number;
+  stalePolicy: "rejectFills" | "reduceOnly" | "widenMargin";
+  staleGraceSec: number;
+  staleMarginAddBps: number;
+  minOrderAgeSlots: number;
+  liquidationEmaBandBps: number;
+  circuitBreakerBps: number;
+  circuitBreakerWindowSec: number;
+  liquidationMaxConfBps: number;
+  liquidationMaxStalenessSec: number;
+  liquidationTwapBandBps: number;
+  twapWindowSec: number;
 };
 
 export type FundingParams = {
@@ -23,6 +39,7 @@ export type FundingParams = {
 
 export type FeeParams = {
   takerFeeBps: number;
+  // Negative values pay resting limit orders a rebate.
   makerFeeBps: number;
 };
 
@@ -30,13 +47,28 @@ export const BALANCED_COMMON = {
   risk: {
     maxLeverage: 20,
     imrBps: 500,
-    mmrBps: 250
+    mmrBps: 250,
+    dustNotional: 10n * 1_000_000n,
+    maxTtlSecs: 0,
+    qtyLotSize: 1n,
+    priceTickSize: 0n
   },
   pricing: {
     baseSpreadBps: 5,
     maxFillDeviationBps: 150,
     maxOracleStalenessSec: 30,
-    maxConfBps: 30
+    maxConfBps: 30,
+    stalePolicy: "reduceOnly",
+    staleGraceSec: 300,
+    staleMarginAddBps: 0,
+    minOrderAgeSlots: 2,
+    liquidationEmaBandBps: 300,
+    circuitBreakerBps: 1_500,
+    circuitBreakerWindowSec: 300,
+    liquidationMaxConfBps: 100,
+    liquidationMaxStalenessSec: 60,
+    liquidationTwapBandBps: 500,
+    twapWindowSec: 600
   },
   funding: {
     intervalSec: 3600,
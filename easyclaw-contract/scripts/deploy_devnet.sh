@@ -166,6 +166,8 @@ echo "PROGRAMS=${PROGRAMS[*]}"
 if [[ "${SKIP_BUILD}" -eq 0 ]]; then
   run_cmd anchor_keys_sync bash -lc "cd '${CONTRACT_DIR}' && ANCHOR_WALLET='${WALLET_KEYPAIR}' anchor keys sync"
   run_cmd anchor_build bash -lc "cd '${CONTRACT_DIR}' && ANCHOR_WALLET='${WALLET_KEYPAIR}' anchor build"
+  # Devnet markets may opt into keeper-supplied fallback prices.
+  run_cmd anchor_build_registry_devnet bash -lc "cd '${CONTRACT_DIR}' && ANCHOR_WALLET='${WALLET_KEYPAIR}' anchor build -p market_registry -- --features devnet"
 else
   echo "[skip] anchor keys sync"
   echo "[skip] anchor build"
@@ -125,8 +125,10 @@ async function main() {
     provider
   );
 
+  const poolIndexSeed = Buffer.alloc(2);
+  poolIndexSeed.writeUInt16LE(Number(process.env.LP_POOL_INDEX ?? 0));
   const pool = PublicKey.findProgramAddressSync(
-    [Buffer.from("pool"), usdcMint.toBuffer()],
+    [Buffer.from("pool"), usdcMint.toBuffer(), poolIndexSeed],
     lpVaultProgramId
   )[0];
 
@@ -162,7 +164,8 @@ async function main() {
         { pubkey: lpPositionPda, isSigner: false, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
       ],
-      Buffer.alloc(0)
+      // referrer: None
+      Buffer.alloc(1)
     );
     const createLpPositionSignature = await sendTx(
       connection,
@@ -183,9 +186,13 @@ async function main() {
       { pubkey: lpPositionPda, isSigner: false, isWritable: true },
       { pubkey: userAta, isSigner: false, isWritable: true },
       { pubkey: liquidityVault, isSigner: false, isWritable: true },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }
+      { pubkey: usdcMint, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      // allowlist_entry: None (open pool).
+      { pubkey: lpVaultProgramId, isSigner: false, isWritable: false }
     ],
-    u64Le(lpDepositAmount)
+    // min_shares_out: u128 = 0, the script accepts any share price.
+    Buffer.concat([u64Le(lpDepositAmount), Buffer.alloc(16)])
   );
   const depositLpSignature = await sendTx(
     connection,
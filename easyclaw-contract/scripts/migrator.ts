@@ -0,0 +1,271 @@
+import "dotenv/config";
+import fs from "node:fs";
+import path from "node:path";
+import * as anchor from "@coral-xyz/anchor";
+
+// Runs a program's migrate instruction over every account of one type.
+//
+//   MIGRATOR_PROGRAM       program name, e.g. order_engine
+//   MIGRATOR_ACCOUNT       account type, e.g. UserMargin
+//   MIGRATOR_INSTRUCTION   migrate instruction, e.g. migrateUserMargin
+//   MIGRATOR_ACCOUNT_ARG   instruction account receiving the target (default: camelCase account type)
+//   MIGRATOR_ACCOUNTS      JSON map of other instruction accounts; "$wallet" resolves to the payer
+//   MIGRATOR_BATCH_SIZE    instructions per transaction (default 8)
+//   MIGRATOR_MAX_RETRIES   attempts per batch before it is recorded as failed (default 3)
+//   MIGRATOR_CHECKPOINT    resume file (default .migrator/<program>-<account>.json)
+//   MIGRATOR_REPORT        report file (default .migrator/<program>-<account>-report.json)
+//   MIGRATOR_DRY_RUN       list pending accounts without sending transactions
+
+type Checkpoint = {
+  program: string;
+  account: string;
+  instruction: string;
+  migrated: string[];
+};
+
+type BatchFailure = {
+  accounts: string[];
+  error: string;
+};
+
+function getRequiredEnv(name: string): string {
+  const value = process.env[name]?.trim();
+  if (!value) {
+    throw new Error(`Missing required env: ${name}`);
+  }
+  return value;
+}
+
+function getEnvNumber(name: string, fallback: number): number {
+  const raw = process.env[name];
+  if (!raw || raw.trim().length === 0) {
+    return fallback;
+  }
+  const parsed = Number(raw);
+  if (!Number.isInteger(parsed) || parsed <= 0) {
+    throw new Error(`Invalid numeric env ${name}: ${raw}`);
+  }
+  return parsed;
+}
+
+function getEnvBoolean(name: string, fallback: boolean): boolean {
+  const raw = process.env[name];
+  if (!raw || raw.trim().length === 0) {
+    return fallback;
+  }
+  return ["1", "true", "yes", "on"].includes(raw.trim().toLowerCase());
+}
+
+function toCamel(value: string): string {
+  const camel = value.replace(/_([a-z])/g, (_, letter: string) =>
+    letter.toUpperCase()
+  );
+  return camel.charAt(0).toLowerCase() + camel.slice(1);
+}
+
+function readIdl(programName: string): any {
+  const upper = programName.toUpperCase();
+  const candidates = [
+    process.env[`${upper}_IDL_PATH`]?.trim(),
+    path.resolve(process.cwd(), `idl/${programName}.json`),
+    path.resolve(process.cwd(), `target/idl/${programName}.json`)
+  ].filter((value): value is string => Boolean(value && value.length > 0));
+
+  for (const candidate of candidates) {
+    if (fs.existsSync(candidate)) {
+      return JSON.parse(fs.readFileSync(candidate, "utf8"));
+    }
+  }
+
+  throw new Error(
+    `${programName} IDL not found. Run \`anchor build\` first or set ${upper}_IDL_PATH.`
+  );
+}
+
+function loadCheckpoint(
+  filePath: string,
+  program: string,
+  account: string,
+  instruction: string
+): Checkpoint {
+  if (!fs.existsSync(filePath)) {
+    return { program, account, instruction, migrated: [] };
+  }
+  const checkpoint = JSON.parse(fs.readFileSync(filePath, "utf8")) as Checkpoint;
+  if (
+    checkpoint.program !== program ||
+    checkpoint.account !== account ||
+    checkpoint.instruction !== instruction
+  ) {
+    throw new Error(
+      `Checkpoint ${filePath} belongs to ${checkpoint.program}.${checkpoint.instruction}(${checkpoint.account})`
+    );
+  }
+  return checkpoint;
+}
+
+function writeJson(filePath: string, value: unknown): void {
+  fs.mkdirSync(path.dirname(filePath), { recursive: true });
+  fs.writeFileSync(filePath, `${JSON.stringify(value, null, 2)}\n`);
+}
+
+function resolveExtraAccounts(
+  wallet: anchor.web3.PublicKey
+): Record<string, anchor.web3.PublicKey> {
+  const raw = process.env.MIGRATOR_ACCOUNTS?.trim();
+  if (!raw) {
+    return {};
+  }
+  const parsed = JSON.parse(raw) as Record<string, string>;
+  return Object.fromEntries(
+    Object.entries(parsed).map(([name, value]) => [
+      name,
+      value === "$wallet" ? wallet : new anchor.web3.PublicKey(value)
+    ])
+  );
+}
+
+async function sleep(ms: number): Promise<void> {
+  await new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+async function main(): Promise<void> {
+  const programName = getRequiredEnv("MIGRATOR_PROGRAM");
+  const accountName = getRequiredEnv("MIGRATOR_ACCOUNT");
+  const instructionName = toCamel(getRequiredEnv("MIGRATOR_INSTRUCTION"));
+  const accountArg = process.env.MIGRATOR_ACCOUNT_ARG?.trim() || toCamel(accountName);
+  const batchSize = getEnvNumber("MIGRATOR_BATCH_SIZE", 8);
+  const maxRetries = getEnvNumber("MIGRATOR_MAX_RETRIES", 3);
+  const dryRun = getEnvBoolean("MIGRATOR_DRY_RUN", false);
+  const checkpointPath = path.resolve(
+    process.env.MIGRATOR_CHECKPOINT?.trim() ||
+      `.migrator/${programName}-${accountName}.json`
+  );
+  const reportPath = path.resolve(
+    process.env.MIGRATOR_REPORT?.trim() ||
+      `.migrator/${programName}-${accountName}-report.json`
+  );
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+  const wallet = provider.wallet.publicKey;
+
+  const idl = readIdl(programName);
+  const program = new anchor.Program(idl, provider) as any;
+  const accountIdl = (idl.accounts ?? []).find(
+    (item: any) => item.name === accountName
+  );
+  if (!accountIdl?.discriminator) {
+    throw new Error(`Account ${accountName} not found in ${programName} IDL`);
+  }
+  if (typeof program.methods?.[instructionName] !== "function") {
+    throw new Error(`Instruction ${instructionName} not found in ${programName} IDL`);
+  }
+
+  const discovered = await connection.getProgramAccounts(program.programId, {
+    dataSlice: { offset: 0, length: 0 },
+    filters: [
+      {
+        memcmp: {
+          offset: 0,
+          bytes: anchor.utils.bytes.bs58.encode(
+            Buffer.from(accountIdl.discriminator)
+          )
+        }
+      }
+    ]
+  });
+  const targets = discovered
+    .map((item) => item.pubkey.toBase58())
+    .sort();
+
+  const checkpoint = loadCheckpoint(
+    checkpointPath,
+    programName,
+    accountName,
+    instructionName
+  );
+  const alreadyMigrated = new Set(checkpoint.migrated);
+  const pending = targets.filter((key) => !alreadyMigrated.has(key));
+  console.log(
+    `[info] ${programName}.${accountName}: ${targets.length} found, ${targets.length - pending.length} already migrated, ${pending.length} pending`
+  );
+
+  const startedAt = new Date().toISOString();
+  const extraAccounts = resolveExtraAccounts(wallet);
+  const failures: BatchFailure[] = [];
+  let migratedThisRun = 0;
+
+  for (let start = 0; start < pending.length; start += batchSize) {
+    const batch = pending.slice(start, start + batchSize);
+    if (dryRun) {
+      console.log(`[dry-run] batch ${start / batchSize}: ${batch.join(",")}`);
+      continue;
+    }
+
+    let lastError = "";
+    let done = false;
+    for (let attempt = 1; attempt <= maxRetries && !done; attempt += 1) {
+      try {
+        const instructions = await Promise.all(
+          batch.map((key) =>
+            program.methods[instructionName]()
+              .accounts({
+                ...extraAccounts,
+                [accountArg]: new anchor.web3.PublicKey(key)
+              })
+              .instruction()
+          )
+        );
+        const tx = new anchor.web3.Transaction().add(...instructions);
+        const sig = await provider.sendAndConfirm(tx);
+        console.log(`[ok] batch ${start / batchSize} (${batch.length}): ${sig}`);
+        done = true;
+      } catch (error) {
+        lastError = error instanceof Error ? error.message : JSON.stringify(error);
+        console.warn(
+          `[warn] batch ${start / batchSize} attempt ${attempt}/${maxRetries} failed: ${lastError}`
+        );
+        if (attempt < maxRetries) {
+          await sleep(500 * 2 ** (attempt - 1));
+        }
+      }
+    }
+
+    if (done) {
+      checkpoint.migrated.push(...batch);
+      migratedThisRun += batch.length;
+      writeJson(checkpointPath, checkpoint);
+    } else {
+      failures.push({ accounts: batch, error: lastError });
+    }
+  }
+
+  const report = {
+    program: programName,
+    account: accountName,
+    instruction: instructionName,
+    dryRun,
+    startedAt,
+    finishedAt: new Date().toISOString(),
+    found: targets.length,
+    previouslyMigrated: targets.length - pending.length,
+    migratedThisRun,
+    failed: failures.reduce((sum, failure) => sum + failure.accounts.length, 0),
+    failures
+  };
+  writeJson(reportPath, report);
+  console.log(
+    `[done] migrated=${migratedThisRun} failed=${report.failed} report=${reportPath}`
+  );
+
+  if (failures.length > 0) {
+    process.exitCode = 1;
+  }
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exitCode = 1;
+});
@@ -177,6 +177,10 @@ async function main() {
     [Buffer.from("engine-config")],
     orderEngineProgramId,
   )[0]
+  const engineStats = PublicKey.findProgramAddressSync(
+    [Buffer.from("engine-stats")],
+    orderEngineProgramId,
+  )[0]
   const collateralVault = PublicKey.findProgramAddressSync(
     [Buffer.from("collateral-vault")],
     orderEngineProgramId,
@@ -190,8 +194,10 @@ async function main() {
     orderEngineProgramId,
   )[0]
 
+  const poolIndexSeed = Buffer.alloc(2)
+  poolIndexSeed.writeUInt16LE(readEnvNumber("LP_POOL_INDEX", 0))
   const pool = PublicKey.findProgramAddressSync(
-    [Buffer.from("pool"), usdcMint.toBuffer()],
+    [Buffer.from("pool"), usdcMint.toBuffer(), poolIndexSeed],
     lpVaultProgramId,
   )[0]
 
@@ -229,6 +235,7 @@ async function main() {
         [
           { pubkey: signer.publicKey, isSigner: true, isWritable: true },
           { pubkey: engineConfig, isSigner: false, isWritable: false },
+          { pubkey: engineStats, isSigner: false, isWritable: true },
           { pubkey: userMarginPda, isSigner: false, isWritable: true },
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         ],
@@ -255,9 +262,11 @@ async function main() {
         [
           { pubkey: signer.publicKey, isSigner: true, isWritable: true },
           { pubkey: engineConfig, isSigner: false, isWritable: false },
+          { pubkey: engineStats, isSigner: false, isWritable: true },
           { pubkey: userMarginPda, isSigner: false, isWritable: true },
           { pubkey: userAta, isSigner: false, isWritable: true },
           { pubkey: collateralVault, isSigner: false, isWritable: true },
+          { pubkey: usdcMint, isSigner: false, isWritable: false },
           { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         ],
         Buffer.concat([u64Le(marginTopUp)]),
@@ -286,7 +295,8 @@ async function main() {
           { pubkey: lpPositionPda, isSigner: false, isWritable: true },
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         ],
-        Buffer.alloc(0),
+        // referrer: None
+        Buffer.alloc(1),
       ),
     )
     const sig = await sendTx(connection, tx, signer)
@@ -307,9 +317,13 @@ async function main() {
           { pubkey: lpPositionPda, isSigner: false, isWritable: true },
           { pubkey: userAta, isSigner: false, isWritable: true },
           { pubkey: liquidityVault, isSigner: false, isWritable: true },
+          { pubkey: usdcMint, isSigner: false, isWritable: false },
           { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          // allowlist_entry: None (open pool).
+          { pubkey: lpVaultProgramId, isSigner: false, isWritable: false },
         ],
-        u64Le(lpTopUp),
+        // min_shares_out: u128 = 0, the script accepts any share price.
+        Buffer.concat([u64Le(lpTopUp), Buffer.alloc(16)]),
       ),
     )
     const sig = await sendTx(connection, tx, signer)
@@ -368,9 +382,9 @@ async function main() {
 
   for (const [idx, item] of orderConfig.entries()) {
     const { side, orderType, margin, limitPrice, marketId } = item
-    const marketPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("market"), u64Le(marketId)],
-      marketRegistryProgramId,
+    const marketMirrorPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("market-mirror"), u64Le(marketId)],
+      orderEngineProgramId,
     )[0]
     const orderPda = PublicKey.findProgramAddressSync(
       [Buffer.from("order"), userMarginPda.toBuffer(), u64Le(orderNonce)],
@@ -384,9 +398,9 @@ async function main() {
       [
         { pubkey: signer.publicKey, isSigner: true, isWritable: true },
         { pubkey: engineConfig, isSigner: false, isWritable: false },
-        { pubkey: marketRegistryProgramId, isSigner: false, isWritable: false },
+        { pubkey: engineStats, isSigner: false, isWritable: true },
         { pubkey: globalConfig, isSigner: false, isWritable: false },
-        { pubkey: marketPda, isSigner: false, isWritable: false },
+        { pubkey: marketMirrorPda, isSigner: false, isWritable: false },
         { pubkey: userMarginPda, isSigner: false, isWritable: true },
         { pubkey: orderPda, isSigner: false, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
@@ -400,6 +414,12 @@ async function main() {
         u64Le(limitPrice),
         i64Le(300),
         u64Le(1_000 + idx),
+        // execution guard disabled: deadline_secs, reference_price, max_adverse_move_bps
+        i64Le(0),
+        u64Le(0),
+        Buffer.alloc(2),
+        // priority_tip
+        u64Le(0),
       ]),
     )
 
@@ -47,6 +47,26 @@ Options:
   --no-client-gen       Skip go-anchor client regeneration
   -h, --help            Show this help
 
+Notes:
+  Singleton accounts added and accounts whose layout changed since the first
+  deployment, and what each needs after upgrading over existing state:
+    market_registry GlobalConfig      migrate_global_config (run first: every
+                                      other registry instruction loads it)
+    market_registry KeeperSet         migrate_keeper_set, in batches
+    market_registry KeeperLiveness    initialize_keeper_liveness (new account)
+    market_registry MarketList        initialize_market_list (new account)
+    market_registry Market            no migration: redeploy
+    lp_vault Pool                     migrate_pool
+    lp_vault LpPosition               migrate_lp_position (permissionless)
+    lp_vault KeeperRebate             migrate_keeper_rebate (permissionless)
+    lp_vault WithdrawRequest          migrate_withdraw_request (permissionless)
+    order_engine Order                no migration: redeploy
+    order_engine UserMargin           no migration: redeploy
+    order_engine UserMarketPosition   no migration: redeploy
+    order_engine MarketFundingState   no migration: redeploy
+  A cluster holding any account marked redeploy (including the zero-copy
+  conversions) cannot be upgraded in place; redeploy it from scratch instead.
+
 Examples:
   $(basename "$0")
   $(basename "$0") order_engine